@@ -0,0 +1,96 @@
+use bevy::ecs::{
+    entity::Entity,
+    index::ComponentIndex,
+    schedule::{Stage, SystemStage},
+    world::World,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+criterion_group!(benches, index_get, query_scan, index_update);
+criterion_main!(benches);
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct TeamId(u32);
+
+const RANGE: std::ops::Range<u32> = 1..6;
+const TEAM_COUNT: u32 = 100;
+
+fn setup(entity_count: u32) -> (World, SystemStage) {
+    let mut world = World::default();
+    world.insert_resource(ComponentIndex::<TeamId>::default());
+    world.spawn_batch((0..entity_count).map(|i| (TeamId(i % TEAM_COUNT),)));
+    let mut stage =
+        SystemStage::single_threaded().with_system(ComponentIndex::<TeamId>::update_system);
+    stage.run(&mut world);
+    world.clear_trackers();
+    (world, stage)
+}
+
+fn index_get(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("index_get");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+
+    for entity_count in RANGE.map(|i| i * 10_000) {
+        group.bench_function(format!("{}_entities", entity_count), |bencher| {
+            let (world, _) = setup(entity_count);
+            let index = world.get_resource::<ComponentIndex<TeamId>>().unwrap();
+
+            bencher.iter(|| {
+                let count = index.get(&TeamId(black_box(3))).count() as u32;
+                assert_eq!(count, entity_count / TEAM_COUNT);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn query_scan(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("index_query_scan");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+
+    for entity_count in RANGE.map(|i| i * 10_000) {
+        group.bench_function(format!("{}_entities", entity_count), |bencher| {
+            let (mut world, _) = setup(entity_count);
+            let mut query = world.query::<(Entity, &TeamId)>();
+
+            bencher.iter(|| {
+                let team = TeamId(black_box(3));
+                let count = query.iter(&world).filter(|(_, id)| **id == team).count() as u32;
+                assert_eq!(count, entity_count / TEAM_COUNT);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn index_update(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("index_update");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+
+    for entity_count in RANGE.map(|i| i * 10_000) {
+        group.bench_function(
+            format!("{}_entities_1_percent_changed", entity_count),
+            |bencher| {
+                let (mut world, mut stage) = setup(entity_count);
+                let mut query = world.query::<&mut TeamId>();
+                let mut offset = 0;
+
+                bencher.iter(|| {
+                    offset += 1;
+                    for mut id in query.iter_mut(&mut world).step_by(100) {
+                        id.0 = (id.0 + offset) % TEAM_COUNT;
+                    }
+                    stage.run(&mut world);
+                    world.clear_trackers();
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
@@ -0,0 +1,342 @@
+use bevy_core::Name;
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::TypeUuid;
+use bevy_utils::HashMap;
+use std::ops::{Add, Mul};
+
+/// The way values are interpolated between the keyframes of a [`VariableCurve`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// The value of a keyframe is held until the next keyframe is reached
+    Step,
+    /// Values are linearly interpolated between keyframes. Rotations use spherical linear
+    /// interpolation
+    Linear,
+    /// Values are interpolated along a cubic Hermite spline. Every keyframe stores an in-tangent,
+    /// a value and an out-tangent, in that order
+    CubicSpline,
+}
+
+/// The keyframe values of a [`VariableCurve`], along with the property they animate
+#[derive(Debug, Clone)]
+pub enum Keyframes {
+    Rotation(Vec<Quat>),
+    Translation(Vec<Vec3>),
+    Scale(Vec<Vec3>),
+    /// Morph target weights. Every keyframe holds one weight per morph target
+    Weights(Vec<f32>),
+}
+
+/// A value sampled from a [`VariableCurve`] at a given time
+#[derive(Debug, Clone, PartialEq)]
+pub enum CurveValue {
+    Rotation(Quat),
+    Translation(Vec3),
+    Scale(Vec3),
+    Weights(Vec<f32>),
+}
+
+/// Describes how a single property of an entity changes over time
+#[derive(Debug, Clone)]
+pub struct VariableCurve {
+    /// The time of every keyframe, in seconds. Must be sorted in ascending order
+    pub keyframe_timestamps: Vec<f32>,
+    pub keyframes: Keyframes,
+    pub interpolation: Interpolation,
+}
+
+impl VariableCurve {
+    /// The time of the last keyframe, in seconds
+    pub fn duration(&self) -> f32 {
+        self.keyframe_timestamps.last().copied().unwrap_or(0.0)
+    }
+
+    /// Samples the curve at `seconds`. Times before the first keyframe or after the last one are
+    /// clamped to those keyframes. Returns `None` if the curve has no keyframes.
+    pub fn sample(&self, seconds: f32) -> Option<CurveValue> {
+        let (from, to, s) = self.keyframe_segment(seconds)?;
+        let dt = self.keyframe_timestamps[to] - self.keyframe_timestamps[from];
+        let sampler = KeyframeSampler {
+            from,
+            to,
+            s,
+            dt,
+            interpolation: self.interpolation,
+        };
+        Some(match &self.keyframes {
+            Keyframes::Rotation(keyframes) => {
+                let rotation = if self.interpolation == Interpolation::Linear {
+                    let (start, end) = (
+                        sampler.value(keyframes, 1, 0),
+                        sampler.next_value(keyframes, 1, 0),
+                    );
+                    // take the shortest path between the two rotations
+                    let end = if start.dot(end) < 0.0 { -end } else { end };
+                    start.slerp(end, s)
+                } else {
+                    sampler.sample(keyframes, 1, 0)
+                };
+                CurveValue::Rotation(rotation.normalize())
+            }
+            Keyframes::Translation(keyframes) => {
+                CurveValue::Translation(sampler.sample(keyframes, 1, 0))
+            }
+            Keyframes::Scale(keyframes) => CurveValue::Scale(sampler.sample(keyframes, 1, 0)),
+            Keyframes::Weights(keyframes) => {
+                let target_count = self.morph_target_count();
+                CurveValue::Weights(
+                    (0..target_count)
+                        .map(|target| sampler.sample(keyframes, target_count, target))
+                        .collect(),
+                )
+            }
+        })
+    }
+
+    /// The number of morph targets animated by a [`Keyframes::Weights`] curve, or `1` for any
+    /// other kind of curve
+    pub fn morph_target_count(&self) -> usize {
+        match &self.keyframes {
+            Keyframes::Weights(weights) => {
+                let values_per_keyframe = match self.interpolation {
+                    Interpolation::CubicSpline => 3,
+                    Interpolation::Step | Interpolation::Linear => 1,
+                };
+                let keyframe_count = self.keyframe_timestamps.len() * values_per_keyframe;
+                weights.len().checked_div(keyframe_count).unwrap_or(0)
+            }
+            _ => 1,
+        }
+    }
+
+    /// Finds the two keyframes surrounding `seconds`, and how far between them `seconds` is
+    fn keyframe_segment(&self, seconds: f32) -> Option<(usize, usize, f32)> {
+        let timestamps = &self.keyframe_timestamps;
+        let last = timestamps.len().checked_sub(1)?;
+        if seconds <= timestamps[0] {
+            return Some((0, 0, 0.0));
+        }
+        if seconds >= timestamps[last] {
+            return Some((last, last, 0.0));
+        }
+        let to = timestamps.partition_point(|timestamp| *timestamp <= seconds);
+        let from = to - 1;
+        let s = (seconds - timestamps[from]) / (timestamps[to] - timestamps[from]);
+        Some((from, to, s))
+    }
+}
+
+/// Values that can be interpolated by a [`VariableCurve`]
+trait Keyframe: Copy + Add<Output = Self> + Mul<f32, Output = Self> {
+    fn lerp(self, end: Self, s: f32) -> Self {
+        self * (1.0 - s) + end * s
+    }
+}
+
+impl Keyframe for f32 {}
+impl Keyframe for Vec3 {}
+impl Keyframe for Quat {}
+
+struct KeyframeSampler {
+    from: usize,
+    to: usize,
+    s: f32,
+    dt: f32,
+    interpolation: Interpolation,
+}
+
+impl KeyframeSampler {
+    /// Samples element `offset` of keyframes which each hold `stride` elements
+    fn sample<T: Keyframe>(&self, keyframes: &[T], stride: usize, offset: usize) -> T {
+        if self.from == self.to {
+            return self.value(keyframes, stride, offset);
+        }
+        match self.interpolation {
+            Interpolation::Step => self.value(keyframes, stride, offset),
+            Interpolation::Linear => self
+                .value(keyframes, stride, offset)
+                .lerp(self.next_value(keyframes, stride, offset), self.s),
+            Interpolation::CubicSpline => {
+                let s = self.s;
+                let s2 = s * s;
+                let s3 = s2 * s;
+                let start = self.value(keyframes, stride, offset);
+                let start_out_tangent = keyframes[self.from * stride * 3 + stride * 2 + offset];
+                let end = self.next_value(keyframes, stride, offset);
+                let end_in_tangent = keyframes[self.to * stride * 3 + offset];
+                start * (2.0 * s3 - 3.0 * s2 + 1.0)
+                    + start_out_tangent * (self.dt * (s3 - 2.0 * s2 + s))
+                    + end * (-2.0 * s3 + 3.0 * s2)
+                    + end_in_tangent * (self.dt * (s3 - s2))
+            }
+        }
+    }
+
+    fn value<T: Keyframe>(&self, keyframes: &[T], stride: usize, offset: usize) -> T {
+        keyframes[self.index(self.from, stride, offset)]
+    }
+
+    fn next_value<T: Keyframe>(&self, keyframes: &[T], stride: usize, offset: usize) -> T {
+        keyframes[self.index(self.to, stride, offset)]
+    }
+
+    fn index(&self, keyframe: usize, stride: usize, offset: usize) -> usize {
+        match self.interpolation {
+            Interpolation::CubicSpline => keyframe * stride * 3 + stride + offset,
+            Interpolation::Step | Interpolation::Linear => keyframe * stride + offset,
+        }
+    }
+}
+
+/// Path to an entity, made of the [`Name`]s of the entities leading to it from an
+/// [`AnimationPlayer`](crate::AnimationPlayer)'s entity
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct EntityPath {
+    pub parts: Vec<Name>,
+}
+
+impl EntityPath {
+    pub fn new(parts: Vec<Name>) -> Self {
+        EntityPath { parts }
+    }
+}
+
+/// A list of [`VariableCurve`]s, and the [`EntityPath`]s of the entities they animate
+#[derive(Debug, Clone, Default, TypeUuid)]
+#[uuid = "d81b7179-0448-4eb0-89fe-c067222725bf"]
+pub struct AnimationClip {
+    curves: HashMap<EntityPath, Vec<VariableCurve>>,
+    duration: f32,
+}
+
+impl AnimationClip {
+    /// The curves of this clip, grouped by the entity they animate
+    pub fn curves(&self) -> &HashMap<EntityPath, Vec<VariableCurve>> {
+        &self.curves
+    }
+
+    /// The time of the last keyframe of any curve in this clip, in seconds
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    /// Adds a [`VariableCurve`] animating the entity at `path`
+    pub fn add_curve_to_path(&mut self, path: EntityPath, curve: VariableCurve) {
+        self.duration = self.duration.max(curve.duration());
+        self.curves.entry(path).or_default().push(curve);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(keyframes: Keyframes, interpolation: Interpolation) -> VariableCurve {
+        VariableCurve {
+            keyframe_timestamps: vec![0.0, 1.0, 3.0],
+            keyframes,
+            interpolation,
+        }
+    }
+
+    #[test]
+    fn step() {
+        let curve = curve(
+            Keyframes::Translation(vec![Vec3::ZERO, Vec3::X, Vec3::Y]),
+            Interpolation::Step,
+        );
+        assert_eq!(curve.sample(0.5), Some(CurveValue::Translation(Vec3::ZERO)));
+        assert_eq!(curve.sample(1.0), Some(CurveValue::Translation(Vec3::X)));
+        assert_eq!(curve.sample(2.9), Some(CurveValue::Translation(Vec3::X)));
+        assert_eq!(curve.sample(5.0), Some(CurveValue::Translation(Vec3::Y)));
+    }
+
+    #[test]
+    fn linear() {
+        let curve = curve(
+            Keyframes::Scale(vec![Vec3::ZERO, Vec3::ONE, Vec3::ONE * 3.0]),
+            Interpolation::Linear,
+        );
+        assert_eq!(curve.sample(-1.0), Some(CurveValue::Scale(Vec3::ZERO)));
+        assert_eq!(curve.sample(0.5), Some(CurveValue::Scale(Vec3::ONE * 0.5)));
+        assert_eq!(curve.sample(2.0), Some(CurveValue::Scale(Vec3::ONE * 2.0)));
+        assert_eq!(curve.duration(), 3.0);
+    }
+
+    #[test]
+    fn linear_rotation_takes_shortest_path() {
+        let curve = VariableCurve {
+            keyframe_timestamps: vec![0.0, 1.0],
+            keyframes: Keyframes::Rotation(vec![
+                Quat::IDENTITY,
+                -Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+            ]),
+            interpolation: Interpolation::Linear,
+        };
+        match curve.sample(0.5) {
+            Some(CurveValue::Rotation(rotation)) => {
+                let expected = Quat::from_rotation_y(std::f32::consts::FRAC_PI_4);
+                assert!(rotation.dot(expected).abs() > 0.9999);
+            }
+            value => panic!("unexpected value {:?}", value),
+        }
+    }
+
+    #[test]
+    fn cubic_spline() {
+        // in-tangent, value, out-tangent for every keyframe
+        let curve = VariableCurve {
+            keyframe_timestamps: vec![0.0, 2.0],
+            keyframes: Keyframes::Translation(vec![
+                Vec3::ZERO,
+                Vec3::ZERO,
+                Vec3::X,
+                Vec3::X,
+                Vec3::X * 2.0,
+                Vec3::ZERO,
+            ]),
+            interpolation: Interpolation::CubicSpline,
+        };
+        // with tangents matching the slope between keyframes, the spline is a straight line
+        assert_eq!(curve.sample(0.0), Some(CurveValue::Translation(Vec3::ZERO)));
+        assert_eq!(curve.sample(1.0), Some(CurveValue::Translation(Vec3::X)));
+        assert_eq!(
+            curve.sample(2.0),
+            Some(CurveValue::Translation(Vec3::X * 2.0))
+        );
+    }
+
+    #[test]
+    fn morph_weights() {
+        let curve = curve(
+            Keyframes::Weights(vec![0.0, 1.0, 1.0, 0.0, 0.0, 0.0]),
+            Interpolation::Linear,
+        );
+        assert_eq!(curve.morph_target_count(), 2);
+        assert_eq!(curve.sample(0.5), Some(CurveValue::Weights(vec![0.5, 0.5])));
+        assert_eq!(curve.sample(2.0), Some(CurveValue::Weights(vec![0.5, 0.0])));
+    }
+
+    #[test]
+    fn empty_curve() {
+        let curve = VariableCurve {
+            keyframe_timestamps: vec![],
+            keyframes: Keyframes::Weights(vec![]),
+            interpolation: Interpolation::Linear,
+        };
+        assert_eq!(curve.sample(0.0), None);
+        assert_eq!(curve.morph_target_count(), 0);
+    }
+
+    #[test]
+    fn clip_duration() {
+        let mut clip = AnimationClip::default();
+        let path = EntityPath::new(vec![Name::new("root")]);
+        clip.add_curve_to_path(
+            path.clone(),
+            curve(Keyframes::Scale(vec![Vec3::ONE; 3]), Interpolation::Step),
+        );
+        assert_eq!(clip.duration(), 3.0);
+        assert_eq!(clip.curves()[&path].len(), 1);
+    }
+}
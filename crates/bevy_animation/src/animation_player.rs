@@ -0,0 +1,283 @@
+use crate::{AnimationClip, CurveValue, EntityPath};
+use bevy_asset::{Assets, Handle};
+use bevy_core::{Name, Time};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_reflect::Reflect;
+use bevy_transform::prelude::{Children, Transform};
+
+/// Plays an [`AnimationClip`] on the entity it is attached to and its descendants.
+///
+/// Curves of the clip target entities through the [`Name`]s leading to them from this entity.
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct AnimationPlayer {
+    paused: bool,
+    repeat: bool,
+    speed: f32,
+    elapsed: f32,
+    animation_clip: Handle<AnimationClip>,
+}
+
+impl Default for AnimationPlayer {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            repeat: false,
+            speed: 1.0,
+            elapsed: 0.0,
+            animation_clip: Default::default(),
+        }
+    }
+}
+
+impl AnimationPlayer {
+    /// Starts playing `handle` from the beginning
+    pub fn play(&mut self, handle: Handle<AnimationClip>) -> &mut Self {
+        *self = Self {
+            speed: self.speed,
+            repeat: self.repeat,
+            animation_clip: handle,
+            ..Default::default()
+        };
+        self
+    }
+
+    /// The clip currently played by this player
+    pub fn animation_clip(&self) -> &Handle<AnimationClip> {
+        &self.animation_clip
+    }
+
+    /// Restarts the clip from the beginning whenever its end is reached
+    pub fn repeat(&mut self) -> &mut Self {
+        self.repeat = true;
+        self
+    }
+
+    /// Holds the last frame of the clip once its end is reached
+    pub fn stop_repeating(&mut self) -> &mut Self {
+        self.repeat = false;
+        self
+    }
+
+    pub fn is_repeating(&self) -> bool {
+        self.repeat
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// The playback speed, as a multiplier of [`Time::delta_seconds`]
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: f32) -> &mut Self {
+        self.speed = speed;
+        self
+    }
+
+    /// The time elapsed since the clip started playing, in seconds
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Seeks to `elapsed` seconds into the clip
+    pub fn set_elapsed(&mut self, elapsed: f32) -> &mut Self {
+        self.elapsed = elapsed;
+        self
+    }
+
+    /// Advances the player by `delta` seconds, scaled by its speed
+    pub fn tick(&mut self, delta: f32) {
+        if !self.paused {
+            self.elapsed += delta * self.speed;
+        }
+    }
+
+    /// The time at which the clip should be sampled, accounting for repetition
+    fn sample_time(&self, clip: &AnimationClip) -> f32 {
+        let duration = clip.duration();
+        if self.repeat && duration > 0.0 {
+            self.elapsed.rem_euclid(duration)
+        } else {
+            self.elapsed
+        }
+    }
+}
+
+/// Morph target weights of a mesh entity, driven by [`Keyframes::Weights`](crate::Keyframes)
+/// curves
+#[derive(Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct MorphWeights {
+    pub weights: Vec<f32>,
+}
+
+/// Finds the entity at `path`, starting the search from the children of `root`
+fn find_entity(
+    root: Entity,
+    path: &EntityPath,
+    children: &Query<&Children>,
+    names: &Query<&Name>,
+) -> Option<Entity> {
+    let mut current = root;
+    for part in path.parts.iter() {
+        current = children
+            .get(current)
+            .ok()?
+            .iter()
+            .copied()
+            .find(|child| matches!(names.get(*child), Ok(name) if name == part))?;
+    }
+    Some(current)
+}
+
+/// Advances every [`AnimationPlayer`] and applies the sampled values of its clip to the
+/// [`Transform`]s and [`MorphWeights`] of the targeted entities
+pub fn animation_player_system(
+    time: Res<Time>,
+    animations: Res<Assets<AnimationClip>>,
+    mut animation_players: Query<(Entity, &mut AnimationPlayer)>,
+    names: Query<&Name>,
+    children: Query<&Children>,
+    mut transforms: Query<&mut Transform>,
+    mut morph_weights: Query<&mut MorphWeights>,
+) {
+    for (entity, mut player) in animation_players.iter_mut() {
+        let animation_clip = match animations.get(&player.animation_clip) {
+            Some(animation_clip) => animation_clip,
+            None => continue,
+        };
+        player.tick(time.delta_seconds());
+        let seconds = player.sample_time(animation_clip);
+        for (path, curves) in animation_clip.curves() {
+            let target = match find_entity(entity, path, &children, &names) {
+                Some(target) => target,
+                None => continue,
+            };
+            for curve in curves.iter() {
+                let value = match curve.sample(seconds) {
+                    Some(value) => value,
+                    None => continue,
+                };
+                if let CurveValue::Weights(weights) = value {
+                    if let Ok(mut morph_weights) = morph_weights.get_mut(target) {
+                        morph_weights.weights = weights;
+                    }
+                } else if let Ok(mut transform) = transforms.get_mut(target) {
+                    match value {
+                        CurveValue::Rotation(rotation) => transform.rotation = rotation,
+                        CurveValue::Translation(translation) => transform.translation = translation,
+                        CurveValue::Scale(scale) => transform.scale = scale,
+                        CurveValue::Weights(_) => unreachable!(),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Interpolation, Keyframes, VariableCurve};
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::CorePlugin;
+    use bevy_ecs::schedule::{Stage, SystemStage};
+    use bevy_math::Vec3;
+    use bevy_transform::hierarchy::BuildWorldChildren;
+
+    #[test]
+    fn animates_named_descendants() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<AnimationClip>();
+        let mut clip = AnimationClip::default();
+        let path = EntityPath::new(vec![Name::new("arm"), Name::new("hand")]);
+        clip.add_curve_to_path(
+            path,
+            VariableCurve {
+                keyframe_timestamps: vec![0.0, 2.0],
+                keyframes: Keyframes::Translation(vec![Vec3::ZERO, Vec3::X * 2.0]),
+                interpolation: Interpolation::Linear,
+            },
+        );
+        clip.add_curve_to_path(
+            EntityPath::new(vec![Name::new("arm")]),
+            VariableCurve {
+                keyframe_timestamps: vec![0.0, 2.0],
+                keyframes: Keyframes::Weights(vec![0.0, 1.0]),
+                interpolation: Interpolation::Linear,
+            },
+        );
+        let handle = app
+            .world
+            .get_resource_mut::<Assets<AnimationClip>>()
+            .unwrap()
+            .add(clip);
+        let world = &mut app.world;
+
+        let mut player = AnimationPlayer::default();
+        player.play(handle).repeat().set_elapsed(3.0);
+        let mut hand = None;
+        let mut arm = None;
+        world.spawn().insert(player).with_children(|parent| {
+            arm = Some(
+                parent
+                    .spawn_bundle((
+                        Name::new("arm"),
+                        Transform::default(),
+                        MorphWeights::default(),
+                    ))
+                    .with_children(|parent| {
+                        hand = Some(
+                            parent
+                                .spawn_bundle((Name::new("hand"), Transform::default()))
+                                .id(),
+                        );
+                        parent.spawn_bundle((Name::new("finger"), Transform::default()));
+                    })
+                    .id(),
+            );
+        });
+
+        let mut stage = SystemStage::single(animation_player_system);
+        stage.run(world);
+
+        // 3 seconds into a 2 second repeating clip
+        let hand_transform = world.get::<Transform>(hand.unwrap()).unwrap();
+        assert_eq!(hand_transform.translation, Vec3::X);
+        let arm = arm.unwrap();
+        assert_eq!(world.get::<Transform>(arm).unwrap().translation, Vec3::ZERO);
+        assert_eq!(world.get::<MorphWeights>(arm).unwrap().weights, vec![0.5]);
+    }
+
+    #[test]
+    fn paused_player_does_not_advance() {
+        let mut player = AnimationPlayer::default();
+        player.set_speed(2.0).tick(1.0);
+        assert_eq!(player.elapsed(), 2.0);
+        player.pause();
+        player.tick(1.0);
+        assert_eq!(player.elapsed(), 2.0);
+        player.resume();
+        player.play(Handle::default());
+        assert_eq!(player.elapsed(), 0.0);
+        assert_eq!(player.speed(), 2.0);
+    }
+}
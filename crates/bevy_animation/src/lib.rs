@@ -0,0 +1,53 @@
+mod animation_clip;
+mod animation_player;
+mod tween;
+
+pub use animation_clip::*;
+pub use animation_player::*;
+pub use tween::*;
+
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        AnimationClip, AnimationPlayer, AnimationPlugin, EaseFunction, RepeatMode, Tween,
+        TweenCompleted, Tweens,
+    };
+}
+
+use bevy_app::prelude::*;
+use bevy_asset::AddAsset;
+use bevy_ecs::{
+    schedule::{ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion, SystemLabel},
+    system::IntoExclusiveSystem,
+};
+use bevy_transform::TransformSystem;
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub enum AnimationSystem {
+    AnimationPlayer,
+}
+
+/// Adds support for playing [`AnimationClip`]s on entity hierarchies and for [`Tween`]ing
+/// reflected components
+#[derive(Default)]
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<AnimationClip>()
+            .register_type::<AnimationPlayer>()
+            .register_type::<MorphWeights>()
+            .add_event::<TweenCompleted>()
+            // runs at the start of the stage, before transforms are propagated
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                tween_system.exclusive_system().at_start(),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                animation_player_system
+                    .label(AnimationSystem::AnimationPlayer)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
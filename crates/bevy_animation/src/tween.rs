@@ -0,0 +1,810 @@
+use bevy_core::Time;
+use bevy_ecs::{
+    component::Component, entity::Entity, event::Events, reflect::ReflectComponent, world::World,
+};
+use bevy_math::{Quat, Vec2, Vec3, Vec4};
+use bevy_reflect::{GetPath, Reflect, TypeRegistryArc};
+use bevy_utils::tracing::warn;
+use std::{any::TypeId, f32::consts::PI};
+use thiserror::Error;
+
+/// Shapes the progress of a [`Tween`] between two keyframes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EaseFunction {
+    Linear,
+    QuadraticIn,
+    QuadraticOut,
+    QuadraticInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExponentialIn,
+    ExponentialOut,
+    ExponentialInOut,
+    /// Holds the start value until the end of the segment
+    Step,
+}
+
+impl Default for EaseFunction {
+    fn default() -> Self {
+        EaseFunction::Linear
+    }
+}
+
+impl EaseFunction {
+    /// Maps the linear progress `t` in `[0, 1]` to the eased progress
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            EaseFunction::Linear => t,
+            EaseFunction::QuadraticIn => t * t,
+            EaseFunction::QuadraticOut => t * (2.0 - t),
+            EaseFunction::QuadraticInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+            EaseFunction::CubicIn => t * t * t,
+            EaseFunction::CubicOut => (t - 1.0).powi(3) + 1.0,
+            EaseFunction::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    (t - 1.0) * (2.0 * t - 2.0) * (2.0 * t - 2.0) + 1.0
+                }
+            }
+            EaseFunction::SineIn => 1.0 - (t * PI / 2.0).cos(),
+            EaseFunction::SineOut => (t * PI / 2.0).sin(),
+            EaseFunction::SineInOut => -((PI * t).cos() - 1.0) / 2.0,
+            EaseFunction::ExponentialIn => {
+                if t == 0.0 {
+                    0.0
+                } else {
+                    2.0f32.powf(10.0 * t - 10.0)
+                }
+            }
+            EaseFunction::ExponentialOut => {
+                if t == 1.0 {
+                    1.0
+                } else {
+                    1.0 - 2.0f32.powf(-10.0 * t)
+                }
+            }
+            EaseFunction::ExponentialInOut => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else if t < 0.5 {
+                    2.0f32.powf(20.0 * t - 10.0) / 2.0
+                } else {
+                    (2.0 - 2.0f32.powf(-20.0 * t + 10.0)) / 2.0
+                }
+            }
+            EaseFunction::Step => {
+                if t < 1.0 {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+        }
+    }
+}
+
+/// A value a [`Tween`] can write to a reflected field
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TweenValue {
+    F32(f32),
+    Vec2(Vec2),
+    Vec3(Vec3),
+    Vec4(Vec4),
+    Quat(Quat),
+}
+
+impl TweenValue {
+    /// Interpolates between two values of the same kind. Rotations are spherically interpolated.
+    ///
+    /// # Panics
+    /// Panics if `self` and `end` are different kinds of values.
+    pub fn lerp(&self, end: &TweenValue, t: f32) -> TweenValue {
+        match (self, end) {
+            (TweenValue::F32(a), TweenValue::F32(b)) => TweenValue::F32(a + (b - a) * t),
+            (TweenValue::Vec2(a), TweenValue::Vec2(b)) => TweenValue::Vec2(a.lerp(*b, t)),
+            (TweenValue::Vec3(a), TweenValue::Vec3(b)) => TweenValue::Vec3(a.lerp(*b, t)),
+            (TweenValue::Vec4(a), TweenValue::Vec4(b)) => TweenValue::Vec4(a.lerp(*b, t)),
+            (TweenValue::Quat(a), TweenValue::Quat(b)) => {
+                // take the shortest path between the two rotations
+                let b = if a.dot(*b) < 0.0 { -*b } else { *b };
+                TweenValue::Quat(a.slerp(b, t))
+            }
+            _ => panic!("cannot interpolate between {:?} and {:?}", self, end),
+        }
+    }
+
+    fn same_kind(&self, other: &TweenValue) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            TweenValue::F32(_) => std::any::type_name::<f32>(),
+            TweenValue::Vec2(_) => std::any::type_name::<Vec2>(),
+            TweenValue::Vec3(_) => std::any::type_name::<Vec3>(),
+            TweenValue::Vec4(_) => std::any::type_name::<Vec4>(),
+            TweenValue::Quat(_) => std::any::type_name::<Quat>(),
+        }
+    }
+
+    fn apply(&self, field: &mut dyn Reflect) -> bool {
+        fn set<T: Reflect + Copy>(field: &mut dyn Reflect, value: T) -> bool {
+            match field.downcast_mut::<T>() {
+                Some(field) => {
+                    *field = value;
+                    true
+                }
+                None => false,
+            }
+        }
+        match *self {
+            TweenValue::F32(value) => set(field, value),
+            TweenValue::Vec2(value) => set(field, value),
+            TweenValue::Vec3(value) => set(field, value),
+            TweenValue::Vec4(value) => set(field, value),
+            TweenValue::Quat(value) => set(field, value),
+        }
+    }
+
+    /// glam vectors are reflected as opaque values, so their components can't be reached
+    /// through [`GetPath`]. This sets a single component of such a vector instead.
+    fn apply_axis(&self, field: &mut dyn Reflect, axis: &str) -> bool {
+        let value = match *self {
+            TweenValue::F32(value) => value,
+            _ => return false,
+        };
+        let component = if let Some(vec) = field.downcast_mut::<Vec2>() {
+            match axis {
+                "x" => &mut vec.x,
+                "y" => &mut vec.y,
+                _ => return false,
+            }
+        } else if let Some(vec) = field.downcast_mut::<Vec3>() {
+            match axis {
+                "x" => &mut vec.x,
+                "y" => &mut vec.y,
+                "z" => &mut vec.z,
+                _ => return false,
+            }
+        } else if let Some(vec) = field.downcast_mut::<Vec4>() {
+            match axis {
+                "x" => &mut vec.x,
+                "y" => &mut vec.y,
+                "z" => &mut vec.z,
+                "w" => &mut vec.w,
+                _ => return false,
+            }
+        } else {
+            return false;
+        };
+        *component = value;
+        true
+    }
+}
+
+impl From<f32> for TweenValue {
+    fn from(value: f32) -> Self {
+        TweenValue::F32(value)
+    }
+}
+
+impl From<Vec2> for TweenValue {
+    fn from(value: Vec2) -> Self {
+        TweenValue::Vec2(value)
+    }
+}
+
+impl From<Vec3> for TweenValue {
+    fn from(value: Vec3) -> Self {
+        TweenValue::Vec3(value)
+    }
+}
+
+impl From<Vec4> for TweenValue {
+    fn from(value: Vec4) -> Self {
+        TweenValue::Vec4(value)
+    }
+}
+
+impl From<Quat> for TweenValue {
+    fn from(value: Quat) -> Self {
+        TweenValue::Quat(value)
+    }
+}
+
+/// A value reached at a given time of a [`Tween`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TweenKeyframe {
+    pub time: f32,
+    pub value: TweenValue,
+    /// Easing of the segment leading to this keyframe
+    pub ease: EaseFunction,
+}
+
+/// What a [`Tween`] does once it reaches its last keyframe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepeatMode {
+    /// Holds the last value, then removes the tween
+    Once,
+    /// Restarts from the first keyframe
+    Loop,
+    /// Plays backward to the first keyframe, then forward again
+    PingPong,
+}
+
+impl Default for RepeatMode {
+    fn default() -> Self {
+        RepeatMode::Once
+    }
+}
+
+/// Animates a field of a reflected component over time.
+///
+/// The field is addressed by a [`GetPath`] path on the component, like `"translation"` on a
+/// [`Transform`](bevy_transform::components::Transform). A trailing `.x`, `.y`, `.z` or `.w`
+/// addresses a single component of a vector field, like `"translation.x"`.
+#[derive(Debug, Clone)]
+pub struct Tween {
+    component: TypeId,
+    component_name: &'static str,
+    path: String,
+    keyframes: Vec<TweenKeyframe>,
+    repeat: RepeatMode,
+    speed: f32,
+    elapsed: f32,
+    paused: bool,
+    finished: bool,
+}
+
+impl Tween {
+    /// Creates a tween on the component `C`, starting at `start`. Add keyframes with [`Tween::then`].
+    pub fn new<C: Component + Reflect>(
+        path: impl Into<String>,
+        start: impl Into<TweenValue>,
+    ) -> Self {
+        Self {
+            component: TypeId::of::<C>(),
+            component_name: std::any::type_name::<C>(),
+            path: path.into(),
+            keyframes: vec![TweenKeyframe {
+                time: 0.0,
+                value: start.into(),
+                ease: EaseFunction::Linear,
+            }],
+            repeat: RepeatMode::Once,
+            speed: 1.0,
+            elapsed: 0.0,
+            paused: false,
+            finished: false,
+        }
+    }
+
+    /// Creates a tween on the component `C` going from `start` to `end` in `duration` seconds
+    pub fn from_to<C: Component + Reflect>(
+        path: impl Into<String>,
+        start: impl Into<TweenValue>,
+        end: impl Into<TweenValue>,
+        duration: f32,
+        ease: EaseFunction,
+    ) -> Self {
+        Self::new::<C>(path, start).then(end, duration, ease)
+    }
+
+    /// Appends a keyframe reaching `value` `duration` seconds after the previous one
+    ///
+    /// # Panics
+    /// Panics if `value` isn't the same kind of value as the previous keyframes.
+    pub fn then(mut self, value: impl Into<TweenValue>, duration: f32, ease: EaseFunction) -> Self {
+        let value = value.into();
+        let last = self.keyframes.last().unwrap();
+        assert!(
+            last.value.same_kind(&value),
+            "keyframe {:?} doesn't match the previous keyframes of the tween",
+            value
+        );
+        let time = last.time + duration.max(0.0);
+        self.keyframes.push(TweenKeyframe { time, value, ease });
+        self
+    }
+
+    /// Sets what happens once the last keyframe is reached
+    pub fn with_repeat(mut self, repeat: RepeatMode) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Sets the playback speed
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// The path of the animated field on the component
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The [`TypeId`] of the animated component
+    pub fn component(&self) -> TypeId {
+        self.component
+    }
+
+    pub fn keyframes(&self) -> &[TweenKeyframe] {
+        &self.keyframes
+    }
+
+    pub fn repeat(&self) -> RepeatMode {
+        self.repeat
+    }
+
+    /// Duration of a single pass through the keyframes, in seconds
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().unwrap().time
+    }
+
+    /// Time since the start of the tween, in seconds
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn set_elapsed(&mut self, elapsed: f32) -> &mut Self {
+        self.elapsed = elapsed;
+        self
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: f32) -> &mut Self {
+        self.speed = speed;
+        self
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Whether a [`RepeatMode::Once`] tween reached its last keyframe
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Advances the tween by `delta` seconds, returning how many passes through the
+    /// keyframes were completed
+    pub fn tick(&mut self, delta: f32) -> u32 {
+        if self.paused || self.finished {
+            return 0;
+        }
+        let duration = self.duration();
+        let previous = self.elapsed;
+        self.elapsed += delta * self.speed;
+        // a tween without duration can't repeat, so it finishes whatever its repeat mode
+        if duration <= 0.0 {
+            self.finished = true;
+            return 1;
+        }
+        match self.repeat {
+            RepeatMode::Once => {
+                if self.elapsed >= duration {
+                    self.finished = true;
+                    1
+                } else {
+                    0
+                }
+            }
+            RepeatMode::Loop | RepeatMode::PingPong => {
+                let passes = (self.elapsed / duration).floor() - (previous / duration).floor();
+                passes.max(0.0) as u32
+            }
+        }
+    }
+
+    /// The value of the tween after `elapsed` seconds, taking the repeat mode into account
+    pub fn sample(&self, elapsed: f32) -> TweenValue {
+        let duration = self.duration();
+        let time = if duration <= 0.0 {
+            duration
+        } else {
+            match self.repeat {
+                RepeatMode::Once => elapsed.clamp(0.0, duration),
+                RepeatMode::Loop => elapsed.rem_euclid(duration),
+                RepeatMode::PingPong => {
+                    let time = elapsed.rem_euclid(2.0 * duration);
+                    if time > duration {
+                        2.0 * duration - time
+                    } else {
+                        time
+                    }
+                }
+            }
+        };
+        self.sample_keyframes(time)
+    }
+
+    /// The current value of the tween
+    pub fn value(&self) -> TweenValue {
+        self.sample(self.elapsed)
+    }
+
+    fn sample_keyframes(&self, time: f32) -> TweenValue {
+        let next = match self.keyframes.iter().position(|k| k.time > time) {
+            Some(0) => return self.keyframes[0].value,
+            Some(next) => next,
+            None => return self.keyframes.last().unwrap().value,
+        };
+        let from = &self.keyframes[next - 1];
+        let to = &self.keyframes[next];
+        let t = (time - from.time) / (to.time - from.time);
+        from.value.lerp(&to.value, to.ease.ease(t))
+    }
+}
+
+/// The [`Tween`]s animating the components of an entity
+#[derive(Debug, Clone, Default)]
+pub struct Tweens {
+    tweens: Vec<Tween>,
+}
+
+impl Tweens {
+    pub fn new(tweens: Vec<Tween>) -> Self {
+        Self { tweens }
+    }
+
+    pub fn add(&mut self, tween: Tween) -> &mut Self {
+        self.tweens.push(tween);
+        self
+    }
+
+    /// Removes the tweens animating `path` on the component `C`
+    pub fn remove<C: Component>(&mut self, path: &str) {
+        self.tweens
+            .retain(|tween| tween.component != TypeId::of::<C>() || tween.path != path);
+    }
+
+    pub fn clear(&mut self) {
+        self.tweens.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Tween> {
+        self.tweens.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Tween> {
+        self.tweens.iter_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tweens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tweens.is_empty()
+    }
+}
+
+impl From<Tween> for Tweens {
+    fn from(tween: Tween) -> Self {
+        Self::new(vec![tween])
+    }
+}
+
+/// Sent when a [`Tween`] completes a pass through its keyframes. Looping tweens send it on
+/// every pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TweenCompleted {
+    pub entity: Entity,
+    /// The [`TypeId`] of the animated component
+    pub component: TypeId,
+    pub path: String,
+}
+
+#[derive(Error, Debug)]
+enum TweenError {
+    #[error("component {0} is not registered or does not reflect Component")]
+    UnregisteredComponent(&'static str),
+    #[error("entity does not have the component {0}")]
+    MissingComponent(&'static str),
+    #[error("path {path} does not exist on {component}")]
+    InvalidPath {
+        path: String,
+        component: &'static str,
+    },
+    #[error("path {path} on {component} is not a {expected}")]
+    TypeMismatch {
+        path: String,
+        component: &'static str,
+        expected: &'static str,
+    },
+}
+
+fn apply_tween(
+    world: &mut World,
+    type_registry: &TypeRegistryArc,
+    entity: Entity,
+    tween: &Tween,
+) -> Result<(), TweenError> {
+    let type_registry = type_registry.read();
+    let reflect_component = type_registry
+        .get(tween.component)
+        .and_then(|registration| registration.data::<ReflectComponent>())
+        .ok_or(TweenError::UnregisteredComponent(tween.component_name))?;
+    let mut component = reflect_component
+        .reflect_component_mut(world, entity)
+        .ok_or(TweenError::MissingComponent(tween.component_name))?;
+    let value = tween.value();
+    let invalid_path = || TweenError::InvalidPath {
+        path: tween.path.clone(),
+        component: tween.component_name,
+    };
+    let applied = match component.path_mut(&tween.path) {
+        Ok(field) => value.apply(field),
+        Err(_) => {
+            let split = tween.path.rfind('.').ok_or_else(invalid_path)?;
+            let (parent, axis) = (&tween.path[..split], &tween.path[split + 1..]);
+            let field = component.path_mut(parent).map_err(|_| invalid_path())?;
+            if !value.apply_axis(field, axis) {
+                return Err(invalid_path());
+            }
+            true
+        }
+    };
+    if applied {
+        Ok(())
+    } else {
+        Err(TweenError::TypeMismatch {
+            path: tween.path.clone(),
+            component: tween.component_name,
+            expected: value.type_name(),
+        })
+    }
+}
+
+/// Advances every [`Tween`] and writes its value to the animated component
+pub fn tween_system(world: &mut World) {
+    let delta = match world.get_resource::<Time>() {
+        Some(time) => time.delta_seconds(),
+        None => return,
+    };
+    // only the `Tweens` with running tweens are changed
+    let mut query = world.query::<(Entity, &Tweens)>();
+    let entities = query
+        .iter(world)
+        .filter(|(_, tweens)| tweens.iter().any(|tween| !tween.paused && !tween.finished))
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    if entities.is_empty() {
+        return;
+    }
+
+    let type_registry = world.get_resource::<TypeRegistryArc>().unwrap().clone();
+    let mut completed = Vec::new();
+    for entity in entities {
+        // taken out of the entity while they are applied, as applying them needs the world
+        let mut tweens = std::mem::take(&mut world.get_mut::<Tweens>(entity).unwrap().tweens);
+        let mut index = 0;
+        while index < tweens.len() {
+            let tween = &mut tweens[index];
+            if tween.paused || tween.finished {
+                index += 1;
+                continue;
+            }
+            let passes = tween.tick(delta);
+            if let Err(err) = apply_tween(world, &type_registry, entity, tween) {
+                warn!("removing tween on entity {:?}: {}", entity, err);
+                tweens.remove(index);
+                continue;
+            }
+            for _ in 0..passes {
+                completed.push(TweenCompleted {
+                    entity,
+                    component: tween.component,
+                    path: tween.path.clone(),
+                });
+            }
+            if tween.finished {
+                tweens.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+        world.get_mut::<Tweens>(entity).unwrap().tweens = tweens;
+    }
+
+    if let Some(mut events) = world.get_resource_mut::<Events<TweenCompleted>>() {
+        for event in completed {
+            events.send(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+    use bevy_core::CorePlugin;
+    use bevy_ecs::{
+        query::Changed,
+        schedule::{Stage, SystemStage},
+        system::IntoExclusiveSystem,
+    };
+    use bevy_transform::components::Transform;
+
+    #[test]
+    fn ease_functions_reach_endpoints() {
+        let functions = [
+            EaseFunction::Linear,
+            EaseFunction::QuadraticIn,
+            EaseFunction::QuadraticOut,
+            EaseFunction::QuadraticInOut,
+            EaseFunction::CubicIn,
+            EaseFunction::CubicOut,
+            EaseFunction::CubicInOut,
+            EaseFunction::SineIn,
+            EaseFunction::SineOut,
+            EaseFunction::SineInOut,
+            EaseFunction::ExponentialIn,
+            EaseFunction::ExponentialOut,
+            EaseFunction::ExponentialInOut,
+            EaseFunction::Step,
+        ];
+        for function in functions.iter() {
+            assert!(function.ease(0.0).abs() < 1e-5, "{:?}", function);
+            assert!((function.ease(1.0) - 1.0).abs() < 1e-5, "{:?}", function);
+        }
+        assert_eq!(EaseFunction::QuadraticIn.ease(0.5), 0.25);
+        assert_eq!(EaseFunction::QuadraticInOut.ease(0.5), 0.5);
+    }
+
+    #[test]
+    fn samples_keyframes_with_repeat_modes() {
+        let tween = Tween::new::<Transform>("scale", Vec3::ZERO)
+            .then(Vec3::ONE, 1.0, EaseFunction::Linear)
+            .then(Vec3::ONE * 3.0, 1.0, EaseFunction::Linear);
+        assert_eq!(tween.duration(), 2.0);
+        assert_eq!(tween.sample(0.5), TweenValue::Vec3(Vec3::ONE * 0.5));
+        assert_eq!(tween.sample(1.5), TweenValue::Vec3(Vec3::ONE * 2.0));
+        assert_eq!(tween.sample(5.0), TweenValue::Vec3(Vec3::ONE * 3.0));
+
+        let tween = tween.with_repeat(RepeatMode::Loop);
+        assert_eq!(tween.sample(2.5), TweenValue::Vec3(Vec3::ONE * 0.5));
+
+        let mut tween = tween.with_repeat(RepeatMode::PingPong);
+        assert_eq!(tween.sample(2.5), TweenValue::Vec3(Vec3::ONE * 2.0));
+        assert_eq!(tween.sample(4.5), TweenValue::Vec3(Vec3::ONE * 0.5));
+        assert_eq!(tween.tick(4.5), 2);
+        assert!(!tween.is_finished());
+    }
+
+    #[test]
+    fn tween_system_animates_reflected_fields() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_event::<TweenCompleted>()
+            .register_type::<Transform>();
+        let world = &mut app.world;
+
+        let mut tweens = Tweens::default();
+        tweens
+            .add(Tween::from_to::<Transform>(
+                "translation.x",
+                0.0,
+                4.0,
+                2.0,
+                EaseFunction::Linear,
+            ))
+            .add(Tween::from_to::<Transform>(
+                "scale",
+                Vec3::ONE,
+                Vec3::ONE * 2.0,
+                1.0,
+                EaseFunction::Linear,
+            ))
+            .add(Tween::new::<Transform>("missing", 0.0).then(1.0, 2.0, EaseFunction::Linear))
+            // fails on the same field as a valid tween, which is kept
+            .add(Tween::from_to::<Transform>(
+                "translation.x",
+                Vec3::ZERO,
+                Vec3::ONE,
+                2.0,
+                EaseFunction::Linear,
+            ));
+        for tween in tweens.iter_mut() {
+            tween.set_elapsed(1.0);
+        }
+        let entity = world
+            .spawn()
+            .insert_bundle((Transform::default(), tweens))
+            .id();
+
+        let mut stage = SystemStage::single(tween_system.exclusive_system());
+        stage.run(world);
+
+        let transform = world.get::<Transform>(entity).unwrap();
+        assert_eq!(transform.translation, Vec3::new(2.0, 0.0, 0.0));
+        assert_eq!(transform.scale, Vec3::ONE * 2.0);
+        // the finished scale tween and the failed tweens are removed
+        let tweens = world.get::<Tweens>(entity).unwrap();
+        assert_eq!(tweens.len(), 1);
+        assert_eq!(tweens.iter().next().unwrap().path(), "translation.x");
+
+        let events = world.get_resource::<Events<TweenCompleted>>().unwrap();
+        let mut reader = events.get_reader();
+        let completed = reader.iter(events).collect::<Vec<_>>();
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].path, "scale");
+        assert_eq!(completed[0].entity, entity);
+    }
+
+    #[test]
+    fn tween_system_completes_applied_tweens() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_event::<TweenCompleted>()
+            .register_type::<Transform>();
+        let world = &mut app.world;
+
+        let mut tweens = Tweens::default();
+        tweens
+            // a tween without duration finishes even when it repeats
+            .add(Tween::new::<Transform>("scale", Vec3::ONE * 2.0).with_repeat(RepeatMode::Loop))
+            // fails as it finishes, so it is not completed
+            .add(Tween::new::<Transform>("missing", 0.0));
+        let entity = world
+            .spawn()
+            .insert_bundle((Transform::default(), tweens))
+            .id();
+        let mut paused = Tween::from_to::<Transform>(
+            "translation",
+            Vec3::ZERO,
+            Vec3::ONE,
+            1.0,
+            EaseFunction::Linear,
+        );
+        paused.pause();
+        let paused_entity = world
+            .spawn()
+            .insert_bundle((Transform::default(), Tweens::new(vec![paused])))
+            .id();
+        world.clear_trackers();
+
+        let mut stage = SystemStage::single(tween_system.exclusive_system());
+        stage.run(world);
+        stage.run(world);
+
+        assert_eq!(
+            world.get::<Transform>(entity).unwrap().scale,
+            Vec3::ONE * 2.0
+        );
+        assert!(world.get::<Tweens>(entity).unwrap().is_empty());
+        let events = world.get_resource::<Events<TweenCompleted>>().unwrap();
+        let mut reader = events.get_reader();
+        let completed = reader.iter(events).collect::<Vec<_>>();
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].path, "scale");
+
+        // the `Tweens` of paused tweens are left unchanged
+        let mut changed = world.query_filtered::<Entity, Changed<Tweens>>();
+        assert!(!changed.iter(world).any(|entity| entity == paused_entity));
+    }
+}
@@ -1,14 +1,18 @@
-use crate::{CoreStage, Events, Plugin, PluginGroup, PluginGroupBuilder, StartupStage};
+use crate::{
+    extract_component_system, extract_resource_system, AppLabel, CoreStage, Events, Plugin,
+    PluginGroup, PluginGroupBuilder, StartupStage, SubApp,
+};
 use bevy_ecs::{
     component::{Component, ComponentDescriptor},
-    prelude::{FromWorld, IntoExclusiveSystem},
+    index::ComponentIndex,
+    prelude::{ExclusiveSystemDescriptorCoercion, FromWorld, IntoExclusiveSystem},
     schedule::{
         IntoSystemDescriptor, RunOnce, Schedule, Stage, StageLabel, State, SystemSet, SystemStage,
     },
     world::World,
 };
 use bevy_utils::tracing::debug;
-use std::{fmt::Debug, hash::Hash};
+use std::{fmt::Debug, hash::Hash, path::PathBuf};
 
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
@@ -40,6 +44,10 @@ pub struct App {
     pub world: World,
     pub runner: Box<dyn Fn(App)>,
     pub schedule: Schedule,
+    sub_apps: Vec<SubApp>,
+    /// Where to write the schedule graph after the next update, set by
+    /// [`ScheduleDotPlugin`](crate::ScheduleDotPlugin)
+    pub(crate) schedule_dot_path: Option<PathBuf>,
 }
 
 impl Default for App {
@@ -71,15 +79,28 @@ impl App {
             world: Default::default(),
             schedule: Default::default(),
             runner: Box::new(run_once),
+            sub_apps: Vec::new(),
+            schedule_dot_path: None,
         }
     }
 
+    /// Runs the schedule once, then extracts data into each sub-app and updates it
     pub fn update(&mut self) {
         #[cfg(feature = "trace")]
         let bevy_frame_update_span = info_span!("frame");
         #[cfg(feature = "trace")]
         let _bevy_frame_update_guard = bevy_frame_update_span.enter();
         self.schedule.run(&mut self.world);
+        for sub_app in self.sub_apps.iter_mut() {
+            #[cfg(feature = "trace")]
+            let bevy_sub_app_span = info_span!("sub app", name = ?sub_app.label);
+            #[cfg(feature = "trace")]
+            let _bevy_sub_app_guard = bevy_sub_app_span.enter();
+            sub_app.update(&mut self.world);
+        }
+        if let Some(path) = self.schedule_dot_path.take() {
+            crate::schedule_dot::write_schedule_dot(&path, &self.schedule, &self.world);
+        }
     }
 
     /// Start the application (through main runner)
@@ -338,6 +359,24 @@ impl App {
             .add_system_to_stage(CoreStage::First, Events::<T>::update_system)
     }
 
+    /// Setup the application to index the entities with a component of type `T` by its value.
+    ///
+    /// This is done by adding a `Resource` of type `ComponentIndex::<T>`, and inserting a
+    /// `ComponentIndex::<T>::update_system` system at the end of `CoreStage::PostUpdate`, before
+    /// removed components are cleared. Read the index with an `IndexReader<T>`.
+    pub fn add_index<T>(&mut self) -> &mut Self
+    where
+        T: Component + Eq + Hash + Clone,
+    {
+        self.insert_resource(ComponentIndex::<T>::default())
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                ComponentIndex::<T>::update_system
+                    .exclusive_system()
+                    .at_end(),
+            )
+    }
+
     /// Inserts a resource to the current [App] and overwrites any resource previously added of the same type.
     ///
     /// A resource in Bevy represents globally unique data. Resources must be added to Bevy Apps
@@ -565,6 +604,106 @@ impl App {
         self
     }
 
+    /// Adds a sub-app with its own [`World`] and [`Schedule`], updated after this app's
+    /// schedule each frame.
+    ///
+    /// Data reaches the sub-app through its extract systems, which run against the sub-app world
+    /// with this app's world available as the [`MainWorld`](crate::MainWorld) resource. See
+    /// [`App::add_extract_system`], [`App::extract_resource`] and [`App::extract_component`].
+    /// The runner of the sub-app is not used.
+    ///
+    /// Sub-apps are updated on the same thread as this app, and `bevy_render` still renders from
+    /// this app's world in its render stages: moving it into a sub-app, and pipelining it, are not
+    /// done yet.
+    ///
+    /// ## Example
+    /// ```
+    /// # use bevy_app::{prelude::*, AppLabel};
+    /// #
+    /// #[derive(Debug, Clone, PartialEq, Eq, Hash, AppLabel)]
+    /// struct PresentationApp;
+    ///
+    /// #[derive(Clone)]
+    /// struct Score(u32);
+    ///
+    /// App::new()
+    ///     .insert_resource(Score(0))
+    ///     .add_sub_app(PresentationApp, App::new())
+    ///     .extract_resource::<Score>(PresentationApp);
+    /// ```
+    pub fn add_sub_app(&mut self, label: impl AppLabel, app: App) -> &mut Self {
+        let label: Box<dyn AppLabel> = Box::new(label);
+        if self.get_sub_app(&*label).is_some() {
+            panic!("Sub-app '{:?}' already exists", label);
+        }
+        self.sub_apps.push(SubApp::new(label, app));
+        self
+    }
+
+    pub fn get_sub_app(&self, label: &dyn AppLabel) -> Option<&App> {
+        self.sub_apps
+            .iter()
+            .find(|sub_app| &*sub_app.label == label)
+            .map(|sub_app| &sub_app.app)
+    }
+
+    pub fn get_sub_app_mut(&mut self, label: &dyn AppLabel) -> Option<&mut App> {
+        self.get_sub_app_entry(label)
+            .map(|sub_app| &mut sub_app.app)
+    }
+
+    /// Returns the sub-app with the given label
+    ///
+    /// # Panics
+    /// Panics if no sub-app was added with this label.
+    pub fn sub_app_mut(&mut self, label: impl AppLabel) -> &mut App {
+        match self.get_sub_app_mut(&label) {
+            Some(app) => app,
+            None => panic!("Sub-app '{:?}' does not exist", label),
+        }
+    }
+
+    /// Adds a system run before each update of the sub-app, while the world of this app is
+    /// available as the [`MainWorld`](crate::MainWorld) resource.
+    ///
+    /// # Panics
+    /// Panics if no sub-app was added with this label.
+    pub fn add_extract_system<Params>(
+        &mut self,
+        label: impl AppLabel,
+        system: impl IntoSystemDescriptor<Params>,
+    ) -> &mut Self {
+        match self.get_sub_app_entry(&label) {
+            Some(sub_app) => {
+                sub_app.extract.add_system(system);
+            }
+            None => panic!("Sub-app '{:?}' does not exist", label),
+        }
+        self
+    }
+
+    /// Copies the resource `R` of this app into the sub-app before each of its updates. The
+    /// resource is removed from the sub-app when it is removed from this app.
+    pub fn extract_resource<R: Component + Clone>(&mut self, label: impl AppLabel) -> &mut Self {
+        self.add_extract_system(label, extract_resource_system::<R>)
+    }
+
+    /// Copies the component `C` of the entities of this app into the sub-app before each of its
+    /// updates.
+    ///
+    /// Extracted entities carry a [`MainEntity`](crate::MainEntity) linking them to the entity of
+    /// this app they were extracted from, and the mapping between both is kept in the
+    /// [`ExtractedEntities`](crate::ExtractedEntities) resource of the sub-app.
+    pub fn extract_component<C: Component + Clone>(&mut self, label: impl AppLabel) -> &mut Self {
+        self.add_extract_system(label, extract_component_system::<C>)
+    }
+
+    fn get_sub_app_entry(&mut self, label: &dyn AppLabel) -> Option<&mut SubApp> {
+        self.sub_apps
+            .iter_mut()
+            .find(|sub_app| &*sub_app.label == label)
+    }
+
     #[cfg(feature = "bevy_reflect")]
     pub fn register_type<T: bevy_reflect::GetTypeRegistration>(&mut self) -> &mut Self {
         {
@@ -1,17 +1,21 @@
 mod app;
 mod plugin;
 mod plugin_group;
+mod schedule_dot;
 mod schedule_runner;
+mod sub_app;
 
 #[cfg(feature = "bevy_ci_testing")]
 mod ci_testing;
 
 pub use app::*;
-pub use bevy_derive::DynamicPlugin;
+pub use bevy_derive::{AppLabel, DynamicPlugin};
 pub use bevy_ecs::event::*;
 pub use plugin::*;
 pub use plugin_group::*;
+pub use schedule_dot::*;
 pub use schedule_runner::*;
+pub use sub_app::*;
 
 pub mod prelude {
     #[doc(hidden)]
@@ -0,0 +1,41 @@
+use crate::{App, Plugin};
+use bevy_ecs::{
+    schedule::{schedule_to_dot, Schedule},
+    world::World,
+};
+use bevy_utils::tracing::{error, info};
+use std::path::{Path, PathBuf};
+
+/// Writes the [`Schedule`] of the [`App`] to a file in the Graphviz DOT language, as formatted by
+/// [`schedule_to_dot`].
+///
+/// The file is written after the first update of the app, once every stage ran and the execution
+/// order ambiguities between their systems are known. Render it with `dot -Tsvg schedule.dot`.
+pub struct ScheduleDotPlugin {
+    pub path: PathBuf,
+}
+
+impl Default for ScheduleDotPlugin {
+    fn default() -> Self {
+        ScheduleDotPlugin {
+            path: PathBuf::from("schedule.dot"),
+        }
+    }
+}
+
+impl Plugin for ScheduleDotPlugin {
+    fn build(&self, app: &mut App) {
+        app.schedule_dot_path = Some(self.path.clone());
+    }
+}
+
+pub(crate) fn write_schedule_dot(path: &Path, schedule: &Schedule, world: &World) {
+    match std::fs::write(path, schedule_to_dot(schedule, world)) {
+        Ok(()) => info!("Wrote the schedule graph to {}", path.display()),
+        Err(err) => error!(
+            "Failed to write the schedule graph to {}: {}",
+            path.display(),
+            err
+        ),
+    }
+}
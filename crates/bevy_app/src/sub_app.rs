@@ -0,0 +1,324 @@
+use crate::App;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::QueryState,
+    schedule::{DynHash, Stage, SystemStage},
+    system::{Commands, Local, Res, ResMut},
+    world::World,
+};
+use bevy_utils::HashMap;
+use std::{
+    borrow::Cow,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+};
+
+/// A label identifying a sub-app of an [`App`]
+pub trait AppLabel: DynHash + Debug + Send + Sync + 'static {
+    #[doc(hidden)]
+    fn dyn_clone(&self) -> Box<dyn AppLabel>;
+}
+pub(crate) type BoxedAppLabel = Box<dyn AppLabel>;
+
+impl PartialEq for dyn AppLabel {
+    fn eq(&self, other: &Self) -> bool {
+        self.dyn_eq(other.as_dyn_eq())
+    }
+}
+
+impl Eq for dyn AppLabel {}
+
+impl Hash for dyn AppLabel {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dyn_hash(state);
+    }
+}
+
+impl Clone for Box<dyn AppLabel> {
+    fn clone(&self) -> Self {
+        self.dyn_clone()
+    }
+}
+
+impl AppLabel for Cow<'static, str> {
+    fn dyn_clone(&self) -> Box<dyn AppLabel> {
+        Box::new(self.clone())
+    }
+}
+
+impl AppLabel for &'static str {
+    fn dyn_clone(&self) -> Box<dyn AppLabel> {
+        Box::new(<&str>::clone(self))
+    }
+}
+
+/// The [`World`] of the main [`App`], lent to a sub-app while its extract systems run.
+///
+/// Extract systems run against the world of the sub-app, and read the data to copy over from
+/// this resource.
+#[derive(Default)]
+pub struct MainWorld(World);
+
+impl Deref for MainWorld {
+    type Target = World;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for MainWorld {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Links an entity of a sub-app to the entity of the main [`App`] it was extracted from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MainEntity(pub Entity);
+
+/// Maps the entities of the main [`App`] to the entities extracted from them in a sub-app.
+///
+/// Extracted entities are despawned once their main entity is despawned.
+#[derive(Debug, Default)]
+pub struct ExtractedEntities {
+    entities: HashMap<Entity, Entity>,
+}
+
+impl ExtractedEntities {
+    /// The sub-app entity extracted from the main `entity`
+    pub fn get(&self, entity: Entity) -> Option<Entity> {
+        self.entities.get(&entity).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    fn get_or_spawn(&mut self, commands: &mut Commands, entity: Entity) -> Entity {
+        *self
+            .entities
+            .entry(entity)
+            .or_insert_with(|| commands.spawn().insert(MainEntity(entity)).id())
+    }
+}
+
+pub(crate) struct SubApp {
+    pub(crate) label: BoxedAppLabel,
+    pub(crate) app: App,
+    pub(crate) extract: SystemStage,
+}
+
+impl SubApp {
+    pub(crate) fn new(label: BoxedAppLabel, mut app: App) -> Self {
+        app.init_resource::<MainWorld>()
+            .init_resource::<ExtractedEntities>();
+        Self {
+            label,
+            app,
+            extract: SystemStage::parallel(),
+        }
+    }
+
+    /// Runs the extract systems with the main world, then updates the sub-app
+    pub(crate) fn update(&mut self, main_world: &mut World) {
+        {
+            let lent = LentMainWorld::new(&mut self.app.world, main_world);
+            self.extract.run(lent.world);
+        }
+
+        let world = &mut self.app.world;
+        let stale = world
+            .get_resource::<ExtractedEntities>()
+            .unwrap()
+            .entities
+            .iter()
+            .filter(|(entity, _)| main_world.get_entity(**entity).is_none())
+            .map(|(entity, extracted)| (*entity, *extracted))
+            .collect::<Vec<_>>();
+        for (entity, extracted) in stale {
+            world.despawn(extracted);
+            world
+                .get_resource_mut::<ExtractedEntities>()
+                .unwrap()
+                .entities
+                .remove(&entity);
+        }
+
+        self.app.update();
+    }
+}
+
+/// Lends the main world to a sub-app world through its [`MainWorld`] resource, and gives it back
+/// when dropped, even if an extract system panics.
+///
+/// The worlds are swapped rather than moved, which avoids allocating a placeholder world every
+/// frame.
+struct LentMainWorld<'a> {
+    world: &'a mut World,
+    main_world: &'a mut World,
+}
+
+impl<'a> LentMainWorld<'a> {
+    fn new(world: &'a mut World, main_world: &'a mut World) -> Self {
+        let mut lent = Self { world, main_world };
+        lent.swap();
+        lent
+    }
+
+    fn swap(&mut self) {
+        let mut lent = self.world.get_resource_mut::<MainWorld>().unwrap();
+        std::mem::swap(&mut lent.0, self.main_world);
+    }
+}
+
+impl<'a> Drop for LentMainWorld<'a> {
+    fn drop(&mut self) {
+        self.swap();
+    }
+}
+
+/// Copies the resource `R` from the main world into the sub-app world
+pub(crate) fn extract_resource_system<R: Component + Clone>(
+    mut commands: Commands,
+    main_world: Res<MainWorld>,
+) {
+    if let Some(resource) = main_world.get_resource::<R>() {
+        commands.insert_resource(resource.clone());
+    } else {
+        commands.remove_resource::<R>();
+    }
+}
+
+/// Copies the component `C` of every entity of the main world into the sub-app world
+pub(crate) fn extract_component_system<C: Component + Clone>(
+    mut commands: Commands,
+    mut main_world: ResMut<MainWorld>,
+    mut extracted_entities: ResMut<ExtractedEntities>,
+    mut query: Local<Option<QueryState<(Entity, &'static C)>>>,
+    mut previous: Local<Vec<Entity>>,
+) {
+    let query = query.get_or_insert_with(|| main_world.query());
+    let mut current = Vec::new();
+    for (entity, component) in query.iter(&main_world) {
+        let extracted = extracted_entities.get_or_spawn(&mut commands, entity);
+        commands.entity(extracted).insert(component.clone());
+        current.push(entity);
+    }
+    for entity in previous.iter() {
+        if main_world.get::<C>(*entity).is_none() {
+            if let Some(extracted) = extracted_entities.get(*entity) {
+                commands.entity(extracted).remove::<C>();
+            }
+        }
+    }
+    *previous = current;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extract_resource_system;
+    use crate::{App, ExtractedEntities, MainEntity, MainWorld};
+    use bevy_ecs::{
+        system::{IntoSystem, Res, ResMut, System},
+        world::World,
+    };
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Score(u32);
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position(f32);
+
+    struct PresentedScore(u32);
+
+    #[test]
+    fn extracts_into_sub_app() {
+        let mut render_app = App::new();
+        render_app.insert_resource(PresentedScore(0)).add_system(
+            |score: Res<Score>, mut presented: ResMut<PresentedScore>| {
+                presented.0 = score.0;
+            },
+        );
+
+        let mut app = App::new();
+        app.insert_resource(Score(3))
+            .add_sub_app("render", render_app)
+            .extract_resource::<Score>("render")
+            .extract_component::<Position>("render");
+        let kept = app.world.spawn().insert(Position(1.0)).id();
+        let removed = app.world.spawn().insert(Position(2.0)).id();
+        let despawned = app.world.spawn().insert(Position(3.0)).id();
+        app.update();
+
+        let render_world = &app.sub_app_mut("render").world;
+        assert_eq!(render_world.get_resource::<PresentedScore>().unwrap().0, 3);
+        let extracted = render_world.get_resource::<ExtractedEntities>().unwrap();
+        assert_eq!(extracted.len(), 3);
+        let extracted_kept = extracted.get(kept).unwrap();
+        let extracted_removed = extracted.get(removed).unwrap();
+        let extracted_despawned = extracted.get(despawned).unwrap();
+        assert_eq!(
+            render_world.get::<Position>(extracted_kept),
+            Some(&Position(1.0))
+        );
+        assert_eq!(
+            render_world.get::<MainEntity>(extracted_kept),
+            Some(&MainEntity(kept))
+        );
+
+        app.world.get_mut::<Position>(kept).unwrap().0 = 4.0;
+        app.world.entity_mut(removed).remove::<Position>();
+        app.world.despawn(despawned);
+        app.world.insert_resource(Score(5));
+        app.update();
+
+        let render_world = &app.sub_app_mut("render").world;
+        assert_eq!(render_world.get_resource::<PresentedScore>().unwrap().0, 5);
+        assert_eq!(
+            render_world.get::<Position>(extracted_kept),
+            Some(&Position(4.0))
+        );
+        assert!(render_world.get::<Position>(extracted_removed).is_none());
+        assert!(render_world.get_entity(extracted_despawned).is_none());
+        assert_eq!(
+            render_world
+                .get_resource::<ExtractedEntities>()
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn resource_extract_systems_run_in_parallel() {
+        let mut world = World::default();
+        world.insert_resource(MainWorld::default());
+        let mut score = extract_resource_system::<Score>.system();
+        let mut position = extract_resource_system::<Position>.system();
+        score.initialize(&mut world);
+        position.initialize(&mut world);
+        assert!(score
+            .component_access()
+            .is_compatible(position.component_access()));
+    }
+
+    #[test]
+    fn main_world_is_restored_after_extract_panic() {
+        let mut app = App::new();
+        app.insert_resource(Score(3))
+            .add_sub_app("render", App::new())
+            .add_extract_system("render", |_main_world: ResMut<MainWorld>| {
+                panic!("extract failed")
+            });
+        assert!(catch_unwind(AssertUnwindSafe(|| app.update())).is_err());
+        assert_eq!(app.world.get_resource::<Score>(), Some(&Score(3)));
+    }
+}
@@ -1,8 +1,10 @@
 use crate::{
+    is_meta_path, meta_path,
     path::{AssetPath, AssetPathId, SourcePathId},
-    Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    processed_path, Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel,
+    AssetLifecycleEvent, AssetLoader, AssetLoaderDynamic, AssetProcessor, Assets, Handle, HandleId,
+    HandleUntyped, LabelId, LoadContext, LoadState, ProcessContext, ProcessorMeta, RefChange,
+    RefChangeChannel, SourceInfo, SourceMeta, DEFAULT_PROCESSED_FOLDER,
 };
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut};
@@ -11,7 +13,13 @@ use bevy_tasks::TaskPool;
 use bevy_utils::{HashMap, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::{Mutex, RwLock};
-use std::{collections::hash_map::Entry, path::Path, sync::Arc};
+use serde::Serialize;
+use std::{
+    any::TypeId,
+    collections::hash_map::Entry,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an AssetServer
@@ -27,6 +35,17 @@ pub enum AssetServerError {
     AssetLoaderError(anyhow::Error),
     #[error("encountered an error while reading an asset: {0}")]
     AssetIoError(#[from] AssetIoError),
+    #[error("encountered an error while reading a `.meta` file: {0}")]
+    InvalidMeta(#[from] ron::Error),
+    #[error("the settings are not valid `{loader_settings}`: {error}")]
+    InvalidSettings {
+        loader_settings: &'static str,
+        error: ron::Error,
+    },
+    #[error("no `AssetProcessor` found with the name: {0}")]
+    MissingAssetProcessor(String),
+    #[error("encountered an error while processing an asset: {0}")]
+    AssetProcessorError(anyhow::Error),
 }
 
 fn format_missing_asset_ext(exts: &[String]) -> String {
@@ -53,8 +72,10 @@ pub struct AssetServerInternal {
     pub(crate) asset_ref_counter: AssetRefCounter,
     pub(crate) asset_sources: Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>,
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
-    loaders: RwLock<Vec<Arc<Box<dyn AssetLoader>>>>,
+    loaders: RwLock<Vec<Arc<Box<dyn AssetLoaderDynamic>>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
+    processors: RwLock<HashMap<String, Arc<Box<dyn AssetProcessor>>>>,
+    processed_folder: RwLock<PathBuf>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
     task_pool: TaskPool,
 }
@@ -82,6 +103,8 @@ impl AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
                 extension_to_loader_index: Default::default(),
+                processors: Default::default(),
+                processed_folder: RwLock::new(DEFAULT_PROCESSED_FOLDER.into()),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
@@ -104,6 +127,7 @@ impl AssetServer {
     where
         T: AssetLoader,
     {
+        let loader: Arc<Box<dyn AssetLoaderDynamic>> = Arc::new(Box::new(loader));
         let mut loaders = self.server.loaders.write();
         let loader_index = loaders.len();
         for extension in loader.extensions().iter() {
@@ -112,7 +136,24 @@ impl AssetServer {
                 .write()
                 .insert(extension.to_string(), loader_index);
         }
-        loaders.push(Arc::new(Box::new(loader)));
+        loaders.push(loader);
+    }
+
+    /// Adds a processor, which `.meta` files select by its [name](AssetProcessor::name)
+    pub fn add_processor<T>(&self, processor: T)
+    where
+        T: AssetProcessor,
+    {
+        self.server
+            .processors
+            .write()
+            .insert(processor.name().to_string(), Arc::new(Box::new(processor)));
+    }
+
+    /// Sets the folder processed artifacts are written to and loaded from, relative to the asset
+    /// folder. Defaults to [`DEFAULT_PROCESSED_FOLDER`].
+    pub fn set_processed_folder<P: Into<PathBuf>>(&self, path: P) {
+        *self.server.processed_folder.write() = path.into();
     }
 
     pub fn watch_for_changes(&self) -> Result<(), AssetServerError> {
@@ -133,7 +174,7 @@ impl AssetServer {
     fn get_asset_loader(
         &self,
         extension: &str,
-    ) -> Result<Arc<Box<dyn AssetLoader>>, AssetServerError> {
+    ) -> Result<Arc<Box<dyn AssetLoaderDynamic>>, AssetServerError> {
         let index = {
             // scope map to drop lock as soon as possible
             let map = self.server.extension_to_loader_index.read();
@@ -149,7 +190,7 @@ impl AssetServer {
     fn get_path_asset_loader<P: AsRef<Path>>(
         &self,
         path: P,
-    ) -> Result<Arc<Box<dyn AssetLoader>>, AssetServerError> {
+    ) -> Result<Arc<Box<dyn AssetLoaderDynamic>>, AssetServerError> {
         let s = path
             .as_ref()
             .file_name()
@@ -233,7 +274,38 @@ impl AssetServer {
         self.load_untyped(path).typed()
     }
 
-    async fn load_async(
+    /// Loads an Asset at the provided relative path with the provided loader settings, which
+    /// replace the settings of its `.meta` file. See [`LoadContext::settings`].
+    ///
+    /// The settings are part of the identity of the asset: loading the same path with different
+    /// settings loads different assets, and their handles are different.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `S` is not the [`AssetLoader::Settings`] of the loader of the path, when the
+    /// loader is already added.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_settings<'a, T: Asset, S: Serialize + 'static, P: Into<AssetPath<'a>>>(
+        &self,
+        path: P,
+        settings: S,
+    ) -> Handle<T> {
+        let path = path.into();
+        if let Ok(loader) = self.get_path_asset_loader(path.path()) {
+            assert!(
+                loader.settings_type_id() == TypeId::of::<S>(),
+                "`{}` are not the settings of the loader of {:?}, which are `{}`",
+                std::any::type_name::<S>(),
+                path.path(),
+                loader.settings_type_name()
+            );
+        }
+        let settings =
+            ron::ser::to_string(&settings).expect("Failed to serialize loader settings.");
+        self.load(path.with_settings(settings))
+    }
+
+    pub(crate) async fn load_async(
         &self,
         asset_path: AssetPath<'_>,
         force: bool,
@@ -252,6 +324,7 @@ impl AssetServer {
                     load_state: LoadState::NotLoaded,
                     meta: None,
                     path: asset_path.path().to_owned(),
+                    settings: asset_path.settings().map(String::from),
                     version: 0,
                 }),
             };
@@ -281,27 +354,43 @@ impl AssetServer {
             source_info.load_state = LoadState::Failed;
         };
 
-        // get the according asset loader
-        let asset_loader = match self.get_path_asset_loader(asset_path.path()) {
-            Ok(loader) => loader,
+        // load the `.meta` file of the source, if it has one
+        let meta = match self.load_meta(asset_path.path()).await {
+            Ok(meta) => meta,
             Err(err) => {
                 set_asset_failed();
                 return Err(err);
             }
         };
 
-        // load the asset bytes
-        let bytes = match self.server.asset_io.load_path(asset_path.path()).await {
-            Ok(bytes) => bytes,
+        // get the according asset loader and load the asset bytes
+        let (asset_loader, bytes) = match self
+            .load_source_bytes(asset_path.path(), meta.as_deref())
+            .await
+        {
+            Ok(loaded) => loaded,
             Err(err) => {
                 set_asset_failed();
-                return Err(AssetServerError::AssetIoError(err));
+                return Err(err);
             }
         };
 
+        // the settings may come from a path, or be meant for another loader
+        if let Some(settings) = asset_path.settings() {
+            if let Err(error) = asset_loader.check_settings(settings) {
+                set_asset_failed();
+                return Err(AssetServerError::InvalidSettings {
+                    loader_settings: asset_loader.settings_type_name(),
+                    error,
+                });
+            }
+        }
+
         // load the asset source using the corresponding AssetLoader
         let mut load_context = LoadContext::new(
             asset_path.path(),
+            asset_path.settings(),
+            meta.as_deref(),
             &self.server.asset_ref_counter.channel,
             &*self.server.asset_io,
             version,
@@ -355,10 +444,191 @@ impl AssetServer {
             .asset_io
             .watch_path_for_changes(asset_path.path())
             .unwrap();
+        // watched even if it doesn't exist, so that adding it reloads the source
+        self.server
+            .asset_io
+            .watch_path_for_changes(&meta_path(asset_path.path()))
+            .unwrap();
         self.create_assets_in_load_context(&mut load_context);
         Ok(asset_path_id)
     }
 
+    async fn load_meta(&self, path: &Path) -> Result<Option<Vec<u8>>, AssetServerError> {
+        match self.server.asset_io.load_path(&meta_path(path)).await {
+            Ok(meta) => Ok(Some(meta)),
+            Err(AssetIoError::NotFound(_)) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn get_meta_processor(
+        &self,
+        meta: Option<&[u8]>,
+    ) -> Result<Option<Arc<Box<dyn AssetProcessor>>>, AssetServerError> {
+        let name = match meta {
+            Some(meta) => ron::de::from_bytes::<ProcessorMeta>(meta)?.processor,
+            None => None,
+        };
+        name.map(|name| {
+            let processor = self.server.processors.read().get(&name).cloned();
+            processor.ok_or(AssetServerError::MissingAssetProcessor(name))
+        })
+        .transpose()
+    }
+
+    /// Returns the bytes of the processed artifact of an asset source and the loader for them if
+    /// the artifact exists, or the bytes of the source and its loader otherwise
+    async fn load_source_bytes(
+        &self,
+        path: &Path,
+        meta: Option<&[u8]>,
+    ) -> Result<(Arc<Box<dyn AssetLoaderDynamic>>, Vec<u8>), AssetServerError> {
+        let processor = match (self.get_meta_processor(meta)?, meta) {
+            (Some(processor), Some(meta)) => Some((processor, meta)),
+            _ => None,
+        };
+        if let Some((processor, meta)) = processor {
+            let bytes = self.server.asset_io.load_path(path).await?;
+            let processed_path = processed_path(
+                &self.server.processed_folder.read(),
+                &**processor,
+                meta,
+                &bytes,
+            )?;
+            return match self.server.asset_io.load_path(&processed_path).await {
+                Ok(processed_bytes) => Ok((
+                    self.get_asset_loader(processor.extension())?,
+                    processed_bytes,
+                )),
+                // the source has not been processed yet
+                Err(AssetIoError::NotFound(_)) => Ok((self.get_path_asset_loader(path)?, bytes)),
+                Err(err) => Err(err.into()),
+            };
+        }
+
+        let asset_loader = self.get_path_asset_loader(path)?;
+        let bytes = self.server.asset_io.load_path(path).await?;
+        Ok((asset_loader, bytes))
+    }
+
+    /// Processes the asset source at the provided relative path with the [`AssetProcessor`]
+    /// selected by its `.meta` file, and returns the path of the artifact. Returns `None` if the
+    /// source has no processor.
+    ///
+    /// Artifacts are written with [`AssetIo::write_path`] to the processed folder, see
+    /// [`AssetServer::set_processed_folder`]. They are keyed by the hash of the source, the
+    /// `processor` and `processor_settings` of its `.meta` file and the processor version, so
+    /// unchanged sources are not processed again. Loading a
+    /// source loads its artifact instead when there is one.
+    pub async fn process<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Option<PathBuf>, AssetServerError> {
+        let path = path.as_ref();
+        let meta = self.load_meta(path).await?;
+        let (processor, meta) = match (self.get_meta_processor(meta.as_deref())?, meta) {
+            (Some(processor), Some(meta)) => (processor, meta),
+            _ => return Ok(None),
+        };
+
+        let bytes = self.server.asset_io.load_path(path).await?;
+        let processed_path = processed_path(
+            &self.server.processed_folder.read(),
+            &**processor,
+            &meta,
+            &bytes,
+        )?;
+        match self.server.asset_io.load_path(&processed_path).await {
+            Ok(_) => return Ok(Some(processed_path)),
+            Err(AssetIoError::NotFound(_)) => {}
+            Err(err) => return Err(err.into()),
+        }
+
+        let mut process_context = ProcessContext {
+            path,
+            meta: &meta,
+            asset_io: &*self.server.asset_io,
+        };
+        let processed = processor
+            .process(&bytes, &mut process_context)
+            .await
+            .map_err(AssetServerError::AssetProcessorError)?;
+        self.server
+            .asset_io
+            .write_path(&processed_path, &processed)
+            .await?;
+        Ok(Some(processed_path))
+    }
+
+    /// Processes every asset source in the folder at the provided relative path and its
+    /// subfolders, see [`AssetServer::process`]. Blocks until they are processed, and returns the
+    /// paths of their artifacts.
+    pub fn process_folder<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Vec<PathBuf>, AssetServerError> {
+        let mut source_paths = Vec::new();
+        self.collect_source_paths(path.as_ref(), &mut source_paths)?;
+        self.server
+            .task_pool
+            .scope(|scope| {
+                for source_path in source_paths.iter() {
+                    scope.spawn(async move { self.process(source_path).await });
+                }
+            })
+            .into_iter()
+            .filter_map(Result::transpose)
+            .collect()
+    }
+
+    fn collect_source_paths(
+        &self,
+        path: &Path,
+        source_paths: &mut Vec<PathBuf>,
+    ) -> Result<(), AssetServerError> {
+        if !self.server.asset_io.is_directory(path) {
+            return Err(AssetServerError::AssetFolderNotADirectory(
+                path.to_str().unwrap().to_string(),
+            ));
+        }
+
+        for child_path in self.server.asset_io.read_directory(path)? {
+            if self.server.asset_io.is_directory(&child_path) {
+                if child_path != *self.server.processed_folder.read() {
+                    self.collect_source_paths(&child_path, source_paths)?;
+                }
+            } else if !is_meta_path(&child_path) {
+                source_paths.push(child_path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reloads the assets of a source, for every settings it is loaded with
+    pub(crate) fn reload_source(&self, path: &Path) {
+        for asset_path in self.loaded_source_paths(path) {
+            let _ = self.load_untracked(asset_path, true);
+        }
+    }
+
+    /// The asset paths the source at `path` was loaded with, one per distinct settings. Sources
+    /// that were never loaded with some settings are not loaded with them on reload.
+    fn loaded_source_paths(&self, path: &Path) -> Vec<AssetPath<'static>> {
+        self.server
+            .asset_sources
+            .read()
+            .values()
+            .filter(|source_info| source_info.path == path)
+            .map(|source_info| {
+                let asset_path = AssetPath::new(path.to_owned(), None);
+                match &source_info.settings {
+                    Some(settings) => asset_path.with_settings(settings.clone()),
+                    None => asset_path,
+                }
+            })
+            .collect()
+    }
+
     #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
     pub fn load_untyped<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> HandleUntyped {
         let handle_id = self.load_untracked(path.into(), false);
@@ -402,6 +672,9 @@ impl AssetServer {
         let mut handles = Vec::new();
         for child_path in self.server.asset_io.read_directory(path.as_ref())? {
             if self.server.asset_io.is_directory(&child_path) {
+                if child_path == *self.server.processed_folder.read() {
+                    continue;
+                }
                 handles.extend(self.load_folder(&child_path)?);
             } else {
                 if self.get_path_asset_loader(&child_path).is_err() {
@@ -477,8 +750,11 @@ impl AssetServer {
                 .take()
                 .expect("Asset should exist at this point.");
             if let Some(asset_lifecycle) = asset_lifecycles.get(&asset_value.type_uuid()) {
-                let asset_path =
-                    AssetPath::new_ref(load_context.path, label.as_ref().map(|l| l.as_str()));
+                let asset_path = AssetPath::new_source_ref(
+                    load_context.path,
+                    label.as_ref().map(|l| l.as_str()),
+                    load_context.settings,
+                );
                 asset_lifecycle.create_asset(asset_path.into(), asset_value, load_context.version);
             } else {
                 panic!(
@@ -557,6 +833,8 @@ mod test {
     use bevy_ecs::prelude::*;
     use bevy_reflect::TypeUuid;
     use bevy_utils::BoxedFuture;
+    use serde::{Deserialize, Serialize};
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     #[derive(Debug, TypeUuid)]
     #[uuid = "a5189b72-0572-4290-a2e0-96f73a491c44"]
@@ -564,6 +842,8 @@ mod test {
 
     struct FakePngLoader;
     impl AssetLoader for FakePngLoader {
+        type Settings = ();
+
         fn load<'a>(
             &'a self,
             _: &'a [u8],
@@ -580,6 +860,8 @@ mod test {
 
     struct FailingLoader;
     impl AssetLoader for FailingLoader {
+        type Settings = ();
+
         fn load<'a>(
             &'a self,
             _: &'a [u8],
@@ -595,6 +877,8 @@ mod test {
 
     struct FakeMultipleDotLoader;
     impl AssetLoader for FakeMultipleDotLoader {
+        type Settings = ();
+
         fn load<'a>(
             &'a self,
             _: &'a [u8],
@@ -608,6 +892,70 @@ mod test {
         }
     }
 
+    #[derive(Debug, TypeUuid)]
+    #[uuid = "5d2b4a8e-7c0b-4f49-9a5e-2ad0a2c36c71"]
+    struct TextAsset(String);
+
+    #[derive(Serialize, Deserialize, Default)]
+    #[serde(default, deny_unknown_fields)]
+    struct TextSettings {
+        suffix: String,
+    }
+
+    struct TextLoader;
+    impl AssetLoader for TextLoader {
+        type Settings = TextSettings;
+
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let settings = ctx.settings::<Self::Settings>()?;
+                let text = String::from_utf8(bytes.to_vec())? + &settings.suffix;
+                ctx.set_default_asset(LoadedAsset::new(TextAsset(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt", "upper"]
+        }
+    }
+
+    #[derive(Deserialize, Default)]
+    struct UppercaseSettings {
+        exclaim: bool,
+    }
+
+    #[derive(Default)]
+    struct UppercaseProcessor(Arc<AtomicUsize>);
+    impl AssetProcessor for UppercaseProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut ProcessContext,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>> {
+            Box::pin(async move {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                let mut processed = bytes.to_ascii_uppercase();
+                if ctx.processor_settings::<UppercaseSettings>()?.exclaim {
+                    processed.push(b'!');
+                }
+                Ok(processed)
+            })
+        }
+
+        fn name(&self) -> &str {
+            "uppercase"
+        }
+
+        fn extension(&self) -> &str {
+            "upper"
+        }
+    }
+
     fn setup(asset_path: impl AsRef<Path>) -> AssetServer {
         use crate::FileAssetIo;
 
@@ -615,6 +963,8 @@ mod test {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
                 extension_to_loader_index: Default::default(),
+                processors: Default::default(),
+                processed_folder: RwLock::new(DEFAULT_PROCESSED_FOLDER.into()),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
@@ -842,6 +1192,210 @@ mod test {
         assert!(get_asset(&handle, &world).is_some());
     }
 
+    fn load_text<'a>(path: impl Into<AssetPath<'a>>, asset_server: &AssetServer) -> String {
+        let mut assets = asset_server.register_asset_type::<TextAsset>();
+        let handle =
+            futures_lite::future::block_on(asset_server.load_async(path.into(), true)).unwrap();
+        let mut world = World::new();
+        world.insert_resource(asset_server.clone());
+        world.insert_resource(assets);
+        let mut update_asset_storage_system = update_asset_storage_system::<TextAsset>.system();
+        update_asset_storage_system.initialize(&mut world);
+        update_asset_storage_system.run((), &mut world);
+        assets = world.remove_resource::<Assets<TextAsset>>().unwrap();
+        assets.get(handle).unwrap().0.clone()
+    }
+
+    #[test]
+    fn test_process() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("text")).unwrap();
+        std::fs::write(dir.path().join("text/processed.txt"), "hello").unwrap();
+        std::fs::write(
+            dir.path().join("text/processed.txt.meta"),
+            "(processor: Some(\"uppercase\"), processor_settings: (exclaim: true), settings: (suffix: \"?\"))",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("text/raw.txt"), "hello").unwrap();
+
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+        let processor = UppercaseProcessor::default();
+        let processed_count = processor.0.clone();
+        asset_server.add_processor(processor);
+
+        // without an artifact, the source is loaded
+        assert_eq!(load_text("text/processed.txt", &asset_server), "hello?");
+
+        let artifacts = asset_server.process_folder(".").unwrap();
+        assert_eq!(artifacts.len(), 1);
+        assert!(artifacts[0].starts_with(DEFAULT_PROCESSED_FOLDER));
+        assert_eq!(
+            std::fs::read(dir.path().join(&artifacts[0])).unwrap(),
+            b"HELLO!"
+        );
+        assert_eq!(processed_count.load(Ordering::Relaxed), 1);
+
+        // the artifact is loaded instead of the source, with the settings of the source
+        assert_eq!(load_text("text/processed.txt", &asset_server), "HELLO!?");
+        assert_eq!(load_text("text/raw.txt", &asset_server), "hello");
+
+        // unchanged sources are not processed again, and artifacts are not sources
+        assert_eq!(asset_server.process_folder(".").unwrap(), artifacts);
+        assert_eq!(processed_count.load(Ordering::Relaxed), 1);
+        assert_eq!(asset_server.load_folder(".").unwrap().len(), 2);
+
+        // changing the source changes its artifact
+        std::fs::write(dir.path().join("text/processed.txt"), "bye").unwrap();
+        assert_eq!(load_text("text/processed.txt", &asset_server), "bye?");
+        let new_artifacts = asset_server.process("text/processed.txt");
+        let new_artifacts = futures_lite::future::block_on(new_artifacts).unwrap();
+        assert_ne!(new_artifacts.as_ref(), artifacts.first());
+        assert_eq!(load_text("text/processed.txt", &asset_server), "BYE!?");
+        assert_eq!(processed_count.load(Ordering::Relaxed), 2);
+
+        // changing the settings of the loader keeps the artifact, unlike the processor settings
+        std::fs::write(
+            dir.path().join("text/processed.txt.meta"),
+            "(\n    processor: Some(\"uppercase\"),\n    processor_settings: (exclaim: true),\n    settings: (suffix: \".\"),\n)",
+        )
+        .unwrap();
+        let artifact = asset_server.process("text/processed.txt");
+        assert_eq!(
+            futures_lite::future::block_on(artifact).unwrap(),
+            new_artifacts
+        );
+        assert_eq!(load_text("text/processed.txt", &asset_server), "BYE!.");
+        assert_eq!(processed_count.load(Ordering::Relaxed), 2);
+        std::fs::write(
+            dir.path().join("text/processed.txt.meta"),
+            "(processor: Some(\"uppercase\"), processor_settings: (exclaim: false))",
+        )
+        .unwrap();
+        let artifact = asset_server.process("text/processed.txt");
+        assert_ne!(
+            futures_lite::future::block_on(artifact).unwrap(),
+            new_artifacts
+        );
+        assert_eq!(load_text("text/processed.txt", &asset_server), "BYE");
+        assert_eq!(processed_count.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_load_with_settings() {
+        let dir = create_dir_and_file("file.txt");
+        std::fs::write(
+            dir.path().join("file.txt.meta"),
+            "(settings: (suffix: \"?\"))",
+        )
+        .unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+
+        let handle: Handle<TextAsset> = asset_server.load_with_settings(
+            "file.txt",
+            TextSettings {
+                suffix: "!".to_string(),
+            },
+        );
+        let path = asset_server.get_handle_path(&handle).unwrap();
+        assert_eq!(path.path(), Path::new("file.txt"));
+        assert!(path.settings().is_some());
+        assert_ne!(HandleId::from(&handle), AssetPath::from("file.txt").into());
+        assert_eq!(HandleId::from(path.get_id()), handle.id);
+
+        // the settings replace the settings of the `.meta` file
+        assert_eq!(load_text(path.to_owned(), &asset_server), "!");
+        assert_eq!(load_text("file.txt", &asset_server), "?");
+        let labeled_path =
+            AssetPath::from("file.txt#label").with_settings(path.settings().unwrap());
+        assert_eq!(
+            labeled_path.get_id().source_path_id(),
+            path.get_id().source_path_id()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "are not the settings of the loader")]
+    fn test_load_with_other_settings() {
+        #[derive(Serialize)]
+        struct OtherSettings {
+            suffix: String,
+        }
+
+        let dir = create_dir_and_file("file.txt");
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+        let _: Handle<TextAsset> = asset_server.load_with_settings(
+            "file.txt",
+            OtherSettings {
+                suffix: "!".to_string(),
+            },
+        );
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        let dir = create_dir_and_file("file.txt");
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+
+        let path = AssetPath::from("file.txt").with_settings("(prefix: \"!\")");
+        let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true))
+            .unwrap_err();
+        assert!(matches!(err, AssetServerError::InvalidSettings { .. }));
+        assert_eq!(
+            asset_server.get_load_state(path.get_id()),
+            LoadState::Failed
+        );
+    }
+
+    #[test]
+    fn test_reload_loaded_settings() {
+        let dir = create_dir_and_file("file.txt");
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+        let path = AssetPath::from("file.txt").with_settings("(suffix: \"!\")");
+        assert_eq!(load_text(path.clone(), &asset_server), "!");
+        let loaded_ids = || {
+            let mut ids = asset_server
+                .loaded_source_paths(Path::new("file.txt"))
+                .iter()
+                .map(AssetPath::get_id)
+                .collect::<Vec<_>>();
+            ids.sort();
+            ids
+        };
+
+        // only the settings the source was loaded with are reloaded
+        assert_eq!(loaded_ids(), vec![path.get_id()]);
+
+        assert_eq!(load_text("file.txt", &asset_server), "");
+        let mut ids = vec![path.get_id(), AssetPath::from("file.txt").get_id()];
+        ids.sort();
+        assert_eq!(loaded_ids(), ids);
+    }
+
+    #[test]
+    fn test_missing_processor() {
+        let dir = create_dir_and_file("file.txt");
+        std::fs::write(
+            dir.path().join("file.txt.meta"),
+            "(processor: Some(\"missing\"))",
+        )
+        .unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(TextLoader);
+
+        let err = futures_lite::future::block_on(asset_server.load_async("file.txt".into(), true))
+            .unwrap_err();
+        assert!(matches!(err, AssetServerError::MissingAssetProcessor(name) if name == "missing"));
+        assert!(matches!(
+            asset_server.process_folder("."),
+            Err(AssetServerError::MissingAssetProcessor(_))
+        ));
+    }
+
     #[test]
     fn test_get_handle_path() {
         const PATH: &str = "path/file.png";
@@ -1,6 +1,6 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetLoader, AssetServer, AssetStage, Handle, HandleId,
-    RefChange,
+    update_asset_storage_system, Asset, AssetLoader, AssetProcessor, AssetServer, AssetStage,
+    Handle, HandleId, RefChange,
 };
 use bevy_app::{App, EventWriter, Events};
 use bevy_ecs::{system::ResMut, world::FromWorld};
@@ -201,6 +201,12 @@ pub trait AddAsset {
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+    fn init_asset_processor<T>(&mut self) -> &mut Self
+    where
+        T: AssetProcessor + FromWorld;
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor;
 }
 
 impl AddAsset for App {
@@ -238,4 +244,23 @@ impl AddAsset for App {
             .add_loader(loader);
         self
     }
+
+    fn init_asset_processor<T>(&mut self) -> &mut Self
+    where
+        T: AssetProcessor + FromWorld,
+    {
+        let result = T::from_world(&mut self.world);
+        self.add_asset_processor(result)
+    }
+
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor,
+    {
+        self.world
+            .get_resource_mut::<AssetServer>()
+            .expect("AssetServer does not exist. Consider adding it as a resource.")
+            .add_processor(processor);
+        self
+    }
 }
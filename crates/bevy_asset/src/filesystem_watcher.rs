@@ -24,4 +24,10 @@ impl FilesystemWatcher {
     pub fn watch<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
         self.watcher.watch(path.as_ref(), RecursiveMode::Recursive)
     }
+
+    /// Watches the files of a folder, without its subfolders
+    pub fn watch_folder<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        self.watcher
+            .watch(path.as_ref(), RecursiveMode::NonRecursive)
+    }
 }
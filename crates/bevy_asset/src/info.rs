@@ -20,6 +20,7 @@ pub struct AssetMeta {
 pub struct SourceInfo {
     pub meta: Option<SourceMeta>,
     pub path: PathBuf,
+    pub settings: Option<String>,
     pub asset_types: HashMap<LabelId, Uuid>,
     pub load_state: LoadState,
     pub committed_assets: HashSet<LabelId>,
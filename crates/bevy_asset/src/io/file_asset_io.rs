@@ -1,4 +1,7 @@
-use crate::{filesystem_watcher::FilesystemWatcher, AssetIo, AssetIoError, AssetServer};
+use crate::{
+    filesystem_watcher::FilesystemWatcher, is_meta_path, AssetIo, AssetIoError, AssetServer,
+    LayeredAssetIo,
+};
 use anyhow::Result;
 use bevy_ecs::system::Res;
 use bevy_utils::{BoxedFuture, HashSet};
@@ -81,9 +84,12 @@ impl AssetIo for FileAssetIo {
             let path = self.root_path.join(path);
             let mut watcher = self.filesystem_watcher.write();
             if let Some(ref mut watcher) = *watcher {
-                watcher
-                    .watch(&path)
-                    .map_err(|_error| AssetIoError::PathWatchError(path))?;
+                // files that don't exist yet, like `.meta` files, are watched through their folder
+                let watched = match path.parent() {
+                    Some(folder) if !path.exists() => watcher.watch_folder(folder),
+                    _ => watcher.watch(&path),
+                };
+                watched.map_err(|_error| AssetIoError::PathWatchError(path))?;
             }
         }
 
@@ -102,6 +108,35 @@ impl AssetIo for FileAssetIo {
     fn is_directory(&self, path: &Path) -> bool {
         self.root_path.join(path).is_dir()
     }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(full_path, bytes)?;
+            Ok(())
+        })
+    }
+}
+
+/// Finds the [`FileAssetIo`] in an [`AssetIo`], which may be layered in a [`LayeredAssetIo`]
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+fn find_file_asset_io(asset_io: &dyn AssetIo) -> Option<&FileAssetIo> {
+    if let Some(layered_asset_io) = asset_io.downcast_ref::<LayeredAssetIo>() {
+        find_file_asset_io(&*layered_asset_io.overlay)
+            .or_else(|| find_file_asset_io(&*layered_asset_io.base))
+    } else {
+        asset_io.downcast_ref::<FileAssetIo>()
+    }
 }
 
 #[cfg(all(
@@ -110,12 +145,11 @@ impl AssetIo for FileAssetIo {
 ))]
 pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
     let mut changed = HashSet::default();
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
-        } else {
-            return;
-        };
+    let asset_io = if let Some(asset_io) = find_file_asset_io(&*asset_server.server.asset_io) {
+        asset_io
+    } else {
+        return;
+    };
     let watcher = asset_io.filesystem_watcher.read();
     if let Some(ref watcher) = *watcher {
         loop {
@@ -124,19 +158,64 @@ pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
                 Err(TryRecvError::Empty) => break,
                 Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
             };
-            if let notify::event::Event {
-                kind: notify::event::EventKind::Modify(_),
-                paths,
-                ..
-            } = event
-            {
-                for path in paths.iter() {
-                    if !changed.contains(path) {
-                        let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-                        let _ = asset_server.load_untracked(relative_path.into(), true);
+            let modified = matches!(event.kind, notify::event::EventKind::Modify(_));
+            // adding or removing a `.meta` file changes its source as well
+            let added_or_removed = matches!(
+                event.kind,
+                notify::event::EventKind::Create(_) | notify::event::EventKind::Remove(_)
+            );
+            if !modified && !added_or_removed {
+                continue;
+            }
+            for path in event.paths {
+                let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
+                // a changed `.meta` file reloads its source
+                let meta = is_meta_path(relative_path);
+                if (meta || modified) && !changed.contains(&path) {
+                    if meta {
+                        asset_server.reload_source(&relative_path.with_extension(""));
+                    } else {
+                        asset_server.reload_source(relative_path);
                     }
+                    changed.insert(path);
                 }
-                changed.extend(paths);
+            }
+        }
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+mod tests {
+    use super::*;
+    use crate::meta_path;
+    use bevy_utils::Duration;
+
+    #[test]
+    fn watch_missing_meta_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("file.txt"), "hello").unwrap();
+        let asset_io = FileAssetIo::new(dir.path());
+        asset_io.watch_for_changes().unwrap();
+        asset_io
+            .watch_path_for_changes(&meta_path(Path::new("file.txt")))
+            .unwrap();
+
+        // adding the `.meta` file is seen by the watcher
+        let meta = dir.path().join("file.txt.meta");
+        std::fs::write(&meta, "()").unwrap();
+        let watcher = asset_io.filesystem_watcher.read();
+        let receiver = &watcher.as_ref().unwrap().receiver;
+        loop {
+            let event = receiver
+                .recv_timeout(Duration::from_secs(10))
+                .expect("the `.meta` file was not watched")
+                .unwrap();
+            if event.paths.contains(&meta) {
+                break;
             }
         }
     }
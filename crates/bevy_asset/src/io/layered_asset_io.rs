@@ -0,0 +1,243 @@
+use crate::{AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashSet};
+use std::path::{Path, PathBuf};
+
+/// An [`AssetIo`] reading from an overlay, and from a base for the paths the overlay does not
+/// have.
+///
+/// Layering a [`FileAssetIo`](crate::FileAssetIo) over a [`PackAssetIo`](crate::PackAssetIo)
+/// lets loose files override packed files during development. Changes are watched in the
+/// overlay, and files are written to it.
+pub struct LayeredAssetIo {
+    pub(crate) overlay: Box<dyn AssetIo>,
+    pub(crate) base: Box<dyn AssetIo>,
+}
+
+impl LayeredAssetIo {
+    pub fn new(overlay: Box<dyn AssetIo>, base: Box<dyn AssetIo>) -> Self {
+        LayeredAssetIo { overlay, base }
+    }
+}
+
+impl AssetIo for LayeredAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            match self.overlay.load_path(path).await {
+                Err(AssetIoError::NotFound(_)) => self.base.load_path(path).await,
+                result => result,
+            }
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        if !self.is_directory(path) {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+
+        let mut visited = HashSet::default();
+        let mut children = Vec::new();
+        for asset_io in [&self.overlay, &self.base].iter() {
+            if asset_io.is_directory(path) {
+                children.extend(
+                    asset_io
+                        .read_directory(path)?
+                        .filter(|child| visited.insert(child.clone())),
+                );
+            }
+        }
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.overlay.is_directory(path) || self.base.is_directory(path)
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        // paths only in the base can't be watched in the overlay
+        self.overlay
+            .watch_path_for_changes(path)
+            .or_else(|_| self.base.watch_path_for_changes(path))
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.overlay.watch_for_changes()?;
+        self.base.watch_for_changes()
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        self.overlay.write_path(path, bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PackAssetIo, PackWriter};
+    use futures_lite::future::block_on;
+    use std::io::Cursor;
+
+    fn pack(files: &[(&str, &[u8])]) -> Box<dyn AssetIo> {
+        let mut writer = PackWriter::new(Vec::new()).unwrap();
+        for (path, bytes) in files {
+            writer.add_file(Path::new(path), bytes).unwrap();
+        }
+        Box::new(PackAssetIo::new(Cursor::new(writer.finish().unwrap())).unwrap())
+    }
+
+    #[test]
+    fn overlay_overrides_base() {
+        let layered = LayeredAssetIo::new(
+            pack(&[("icon.png", b"loose"), ("textures/grass.png", b"grass")]),
+            pack(&[("icon.png", b"packed"), ("models/cube.gltf", b"cube")]),
+        );
+        let load = |path: &str| block_on(layered.load_path(Path::new(path)));
+        assert_eq!(load("icon.png").unwrap(), b"loose");
+        assert_eq!(load("textures/grass.png").unwrap(), b"grass");
+        assert_eq!(load("models/cube.gltf").unwrap(), b"cube");
+        assert!(matches!(
+            load("missing.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        let mut root = layered
+            .read_directory(Path::new(""))
+            .unwrap()
+            .collect::<Vec<_>>();
+        root.sort();
+        assert_eq!(
+            root,
+            vec![
+                PathBuf::from("icon.png"),
+                PathBuf::from("models"),
+                PathBuf::from("textures")
+            ]
+        );
+        assert!(layered.is_directory(Path::new("models")));
+        assert!(layered.is_directory(Path::new("textures")));
+        assert!(layered.read_directory(Path::new("sounds")).is_err());
+
+        assert!(matches!(
+            block_on(layered.write_path(Path::new("icon.png"), b"")),
+            Err(AssetIoError::WriteNotSupported(_))
+        ));
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    #[test]
+    fn file_overlay_over_pack() {
+        use crate::FileAssetIo;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("textures")).unwrap();
+        std::fs::write(dir.path().join("icon.png"), "loose").unwrap();
+        std::fs::write(dir.path().join("textures/grass.png"), "grass").unwrap();
+        let layered = LayeredAssetIo::new(
+            Box::new(FileAssetIo::new(dir.path())),
+            pack(&[
+                ("icon.png", b"packed"),
+                ("textures/dirt.png", b"dirt"),
+                ("models/cube.gltf", b"cube"),
+            ]),
+        );
+        let load = |path: &str| block_on(layered.load_path(Path::new(path)));
+        assert_eq!(load("icon.png").unwrap(), b"loose");
+        assert_eq!(load("./textures/dirt.png").unwrap(), b"dirt");
+
+        // paths read from the folder with `./` are the same as the packed paths
+        let read_directory = |path: &str| {
+            let mut children = layered
+                .read_directory(Path::new(path))
+                .unwrap()
+                .collect::<Vec<_>>();
+            children.sort();
+            children
+        };
+        let root = vec![
+            PathBuf::from("icon.png"),
+            PathBuf::from("models"),
+            PathBuf::from("textures"),
+        ];
+        assert_eq!(read_directory("."), root);
+        assert_eq!(read_directory(""), root);
+        assert_eq!(
+            read_directory("./textures"),
+            vec![
+                PathBuf::from("textures/dirt.png"),
+                PathBuf::from("textures/grass.png")
+            ]
+        );
+    }
+
+    #[cfg(all(
+        feature = "filesystem_watcher",
+        all(not(target_arch = "wasm32"), not(target_os = "android"))
+    ))]
+    #[test]
+    fn watch_file_overlay() {
+        use crate::{
+            filesystem_watcher_system, AssetLoader, AssetServer, FileAssetIo, LoadContext,
+        };
+        use bevy_ecs::{
+            system::{IntoSystem, System},
+            world::World,
+        };
+        use bevy_tasks::TaskPool;
+        use bevy_utils::{BoxedFuture, Duration, Instant};
+
+        struct EmptyLoader;
+        impl AssetLoader for EmptyLoader {
+            type Settings = ();
+
+            fn load<'a>(
+                &'a self,
+                _: &'a [u8],
+                _: &'a mut LoadContext,
+            ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+                Box::pin(async move { Ok(()) })
+            }
+
+            fn extensions(&self) -> &[&str] {
+                &["txt"]
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("file.txt"), "loose").unwrap();
+        let asset_server = AssetServer::new(
+            LayeredAssetIo::new(
+                Box::new(FileAssetIo::new(dir.path())),
+                pack(&[("file.txt", b"packed")]),
+            ),
+            TaskPool::new(),
+        );
+        asset_server.add_loader(EmptyLoader);
+        asset_server.watch_for_changes().unwrap();
+        block_on(asset_server.load_async("file.txt".into(), false)).unwrap();
+        let version = || {
+            let asset_sources = asset_server.server.asset_sources.read();
+            asset_sources.values().next().unwrap().version
+        };
+        assert_eq!(version(), 1);
+
+        // the watcher of the overlay is found in the layers, and reloads changed files
+        let mut world = World::new();
+        world.insert_resource(asset_server.clone());
+        let mut watcher_system = filesystem_watcher_system.system();
+        watcher_system.initialize(&mut world);
+        std::fs::write(dir.path().join("file.txt"), "changed").unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while version() == 1 {
+            assert!(Instant::now() < deadline, "the change was not seen");
+            watcher_system.run((), &mut world);
+            std::thread::yield_now();
+        }
+    }
+}
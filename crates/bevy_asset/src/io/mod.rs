@@ -2,6 +2,8 @@
 mod android_asset_io;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 mod file_asset_io;
+mod layered_asset_io;
+mod pack_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
@@ -9,6 +11,8 @@ mod wasm_asset_io;
 pub use android_asset_io::*;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 pub use file_asset_io::*;
+pub use layered_asset_io::*;
+pub use pack_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
@@ -30,6 +34,8 @@ pub enum AssetIoError {
     Io(#[from] io::Error),
     #[error("failed to watch path: {0}")]
     PathWatchError(PathBuf),
+    #[error("writing is not supported: {0}")]
+    WriteNotSupported(PathBuf),
 }
 
 /// Handles load requests from an AssetServer
@@ -42,6 +48,16 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     fn is_directory(&self, path: &Path) -> bool;
     fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError>;
     fn watch_for_changes(&self) -> Result<(), AssetIoError>;
+
+    /// Writes `bytes` to `path`, creating its parent directories. This is used to cache
+    /// processed artifacts, see [`AssetProcessor`](crate::AssetProcessor).
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        _bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::WriteNotSupported(path.to_owned())) })
+    }
 }
 
 impl_downcast!(AssetIo);
@@ -0,0 +1,344 @@
+use crate::{AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use parking_lot::Mutex;
+use std::{
+    convert::TryFrom,
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+};
+
+const MAGIC: &[u8; 8] = b"BEVYPACK";
+const VERSION: u32 = 1;
+
+trait PackReader: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> PackReader for T {}
+
+/// An [`AssetIo`] reading assets from a pack archive, which is written by [`PackWriter`].
+///
+/// Packs are read only and are not watched for changes. Layer a [`FileAssetIo`] over a pack with
+/// a [`LayeredAssetIo`] to override packed files with loose files during development.
+///
+/// [`FileAssetIo`]: crate::FileAssetIo
+/// [`LayeredAssetIo`]: crate::LayeredAssetIo
+pub struct PackAssetIo {
+    reader: Mutex<Box<dyn PackReader>>,
+    /// The offset and length of every file
+    files: HashMap<PathBuf, (u64, u64)>,
+    /// The files and directories in every directory, the root being the empty path
+    directories: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl PackAssetIo {
+    /// Reads the index of the pack in `reader`
+    pub fn new<R: Read + Seek + Send + 'static>(mut reader: R) -> Result<Self, AssetIoError> {
+        let files = read_index(&mut reader)?;
+
+        let mut directories: HashMap<PathBuf, HashSet<PathBuf>> = HashMap::default();
+        directories.insert(PathBuf::new(), HashSet::default());
+        for path in files.keys() {
+            let mut child = path.as_path();
+            while let Some(parent) = child.parent() {
+                directories
+                    .entry(parent.to_owned())
+                    .or_default()
+                    .insert(child.to_owned());
+                child = parent;
+            }
+        }
+        let directories = directories
+            .into_iter()
+            .map(|(path, children)| {
+                let mut children = children.into_iter().collect::<Vec<_>>();
+                children.sort();
+                (path, children)
+            })
+            .collect();
+
+        Ok(PackAssetIo {
+            reader: Mutex::new(Box::new(reader)),
+            files,
+            directories,
+        })
+    }
+
+    /// Opens the pack at `path`, relative to the root path of [`FileAssetIo`].
+    ///
+    /// [`FileAssetIo`]: crate::FileAssetIo
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, AssetIoError> {
+        let full_path = crate::FileAssetIo::get_root_path().join(path.as_ref());
+        match std::fs::File::open(&full_path) {
+            Ok(file) => Self::new(io::BufReader::new(file)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(AssetIoError::NotFound(full_path)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns the paths of the files in the pack
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(|path| path.as_path())
+    }
+}
+
+impl AssetIo for PackAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let &(offset, len) = self
+                .files
+                .get(&normalize(path))
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+            let mut bytes = vec![0; len as usize];
+            let mut reader = self.reader.lock();
+            reader.seek(SeekFrom::Start(offset))?;
+            reader.read_exact(&mut bytes)?;
+            Ok(bytes)
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let children = self
+            .directories
+            .get(&normalize(path))
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+        Ok(Box::new(children.clone().into_iter()))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.directories.contains_key(&normalize(path))
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
+
+/// Writes a pack archive read by [`PackAssetIo`].
+///
+/// Files are written as they are added, and the index of the pack is written by
+/// [`PackWriter::finish`]. A pack is laid out as:
+/// - the `BEVYPACK` magic bytes and the format version as a little endian `u32`
+/// - the bytes of every file
+/// - the index: the number of files, then for each file the length and bytes of its path with `/`
+///   separators, and the offset and length of its bytes
+/// - the offset of the index
+///
+/// Numbers in the index and the offset of the index are little endian `u64`s.
+pub struct PackWriter<W: Write> {
+    writer: W,
+    offset: u64,
+    index: Vec<(String, u64, u64)>,
+}
+
+impl<W: Write> PackWriter<W> {
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        Ok(PackWriter {
+            writer,
+            offset: (MAGIC.len() + 4) as u64,
+            index: Vec::new(),
+        })
+    }
+
+    /// Adds a file to the pack at `path`, which is relative to the asset folder
+    pub fn add_file(&mut self, path: &Path, bytes: &[u8]) -> io::Result<()> {
+        let mut pack_path = String::new();
+        for component in normalize(path).components() {
+            let component = match component {
+                Component::Normal(component) => component.to_str(),
+                _ => None,
+            }
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid pack path: {}", path.display()),
+                )
+            })?;
+            if !pack_path.is_empty() {
+                pack_path.push('/');
+            }
+            pack_path.push_str(component);
+        }
+
+        self.writer.write_all(bytes)?;
+        self.index
+            .push((pack_path, self.offset, bytes.len() as u64));
+        self.offset += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// Writes the index of the pack, and returns the underlying writer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer
+            .write_all(&(self.index.len() as u64).to_le_bytes())?;
+        for (path, offset, len) in self.index.iter() {
+            self.writer.write_all(&(path.len() as u64).to_le_bytes())?;
+            self.writer.write_all(path.as_bytes())?;
+            self.writer.write_all(&offset.to_le_bytes())?;
+            self.writer.write_all(&len.to_le_bytes())?;
+        }
+        self.writer.write_all(&self.offset.to_le_bytes())?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Removes the `.` components of a path, to match the paths of the pack
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| *component != Component::CurDir)
+        .collect()
+}
+
+fn invalid_pack(message: &str) -> AssetIoError {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid pack: {}", message),
+    )
+    .into()
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_index<R: Read + Seek>(
+    reader: &mut R,
+) -> Result<HashMap<PathBuf, (u64, u64)>, AssetIoError> {
+    let mut header = [0; 12];
+    reader.seek(SeekFrom::Start(0))?;
+    reader.read_exact(&mut header)?;
+    if &header[..8] != MAGIC {
+        return Err(invalid_pack("missing magic bytes"));
+    }
+    if header[8..] != VERSION.to_le_bytes() {
+        return Err(invalid_pack("unsupported version"));
+    }
+
+    let end = reader.seek(SeekFrom::End(-8))?;
+    let index_offset = read_u64(reader)?;
+    if index_offset > end {
+        return Err(invalid_pack("index out of bounds"));
+    }
+    reader.seek(SeekFrom::Start(index_offset))?;
+    let mut reader = reader.take(end - index_offset);
+
+    let count = read_u64(&mut reader)?;
+    let mut files = HashMap::default();
+    for _ in 0..count {
+        let path_len = read_u64(&mut reader)?;
+        let mut path = Vec::new();
+        (&mut reader).take(path_len).read_to_end(&mut path)?;
+        let path = String::from_utf8(path).map_err(|_| invalid_pack("path is not UTF-8"))?;
+        let offset = read_u64(&mut reader)?;
+        let len = read_u64(&mut reader)?;
+        match offset.checked_add(len) {
+            Some(file_end) if file_end <= index_offset => {}
+            _ => return Err(invalid_pack("file out of bounds")),
+        }
+        if usize::try_from(len).is_err() {
+            return Err(invalid_pack("file too large"));
+        }
+        files.insert(PathBuf::from(path), (offset, len));
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::future::block_on;
+    use std::io::Cursor;
+
+    fn pack(files: &[(&str, &[u8])]) -> PackAssetIo {
+        let mut writer = PackWriter::new(Vec::new()).unwrap();
+        for (path, bytes) in files {
+            writer.add_file(Path::new(path), bytes).unwrap();
+        }
+        PackAssetIo::new(Cursor::new(writer.finish().unwrap())).unwrap()
+    }
+
+    #[test]
+    fn load_path() {
+        let pack = pack(&[
+            ("icon.png", b"icon"),
+            ("models/cube/cube.gltf", b"cube"),
+            ("./models/empty.gltf", b""),
+        ]);
+        assert_eq!(
+            block_on(pack.load_path(Path::new("icon.png"))).unwrap(),
+            b"icon"
+        );
+        assert_eq!(
+            block_on(pack.load_path(Path::new("./models/cube/cube.gltf"))).unwrap(),
+            b"cube"
+        );
+        assert!(block_on(pack.load_path(Path::new("models/empty.gltf")))
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            block_on(pack.load_path(Path::new("missing.png"))),
+            Err(AssetIoError::NotFound(_))
+        ));
+        assert!(matches!(
+            block_on(pack.load_path(Path::new("models"))),
+            Err(AssetIoError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn read_directory() {
+        let pack = pack(&[
+            ("icon.png", b"icon"),
+            ("models/cube/cube.gltf", b"cube"),
+            ("models/cube/cube.bin", b"bin"),
+            ("models/sphere.gltf", b"sphere"),
+        ]);
+        let read_directory = |path: &str| pack.read_directory(Path::new(path)).unwrap();
+        assert_eq!(
+            read_directory(".").collect::<Vec<_>>(),
+            vec![PathBuf::from("icon.png"), PathBuf::from("models")]
+        );
+        assert_eq!(
+            read_directory("models").collect::<Vec<_>>(),
+            vec![
+                PathBuf::from("models/cube"),
+                PathBuf::from("models/sphere.gltf")
+            ]
+        );
+        assert_eq!(read_directory("models/cube").count(), 2);
+        assert!(pack.read_directory(Path::new("icon.png")).is_err());
+
+        assert!(pack.is_directory(Path::new("")));
+        assert!(pack.is_directory(Path::new("models/cube")));
+        assert!(!pack.is_directory(Path::new("models/sphere.gltf")));
+        assert!(!pack.is_directory(Path::new("textures")));
+    }
+
+    #[test]
+    fn invalid_packs() {
+        let is_invalid = |bytes: Vec<u8>| PackAssetIo::new(Cursor::new(bytes)).is_err();
+        assert!(is_invalid(Vec::new()));
+        assert!(is_invalid(b"NOTAPACK".to_vec()));
+
+        let mut bytes = PackWriter::new(Vec::new()).unwrap().finish().unwrap();
+        assert!(!is_invalid(bytes.clone()));
+        bytes.pop();
+        assert!(is_invalid(bytes));
+
+        let mut writer = PackWriter::new(Vec::new()).unwrap();
+        assert!(writer.add_file(Path::new("../icon.png"), b"").is_err());
+        assert!(writer.add_file(Path::new("/icon.png"), b"").is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-    RefChangeChannel,
+    RefChangeChannel, SettingsMeta,
 };
 use anyhow::Result;
 use bevy_ecs::{
@@ -12,10 +12,19 @@ use bevy_tasks::TaskPool;
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
-use std::path::Path;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{any::TypeId, path::Path};
 
 /// A loader for an asset source
 pub trait AssetLoader: Send + Sync + 'static {
+    /// The settings of the loader, read with [`LoadContext::settings`]. Loaders without settings
+    /// use `()`.
+    ///
+    /// Settings should implement `Default` with `#[serde(default)]`, so that settings only need to
+    /// list the fields they change, and `#[serde(deny_unknown_fields)]`, so that settings meant
+    /// for another loader fail to load instead of silently using the default values.
+    type Settings: Serialize + DeserializeOwned + Default + Send + Sync + 'static;
+
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
@@ -24,6 +33,46 @@ pub trait AssetLoader: Send + Sync + 'static {
     fn extensions(&self) -> &[&str];
 }
 
+/// The object safe part of [`AssetLoader`], used to store loaders of any settings type
+pub(crate) trait AssetLoaderDynamic: Send + Sync + 'static {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>>;
+    fn extensions(&self) -> &[&str];
+    fn settings_type_id(&self) -> TypeId;
+    fn settings_type_name(&self) -> &'static str;
+    /// Checks that `settings` are valid settings of this loader
+    fn check_settings(&self, settings: &str) -> Result<(), ron::Error>;
+}
+
+impl<T: AssetLoader> AssetLoaderDynamic for T {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        AssetLoader::load(self, bytes, load_context)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        AssetLoader::extensions(self)
+    }
+
+    fn settings_type_id(&self) -> TypeId {
+        TypeId::of::<T::Settings>()
+    }
+
+    fn settings_type_name(&self) -> &'static str {
+        std::any::type_name::<T::Settings>()
+    }
+
+    fn check_settings(&self, settings: &str) -> Result<(), ron::Error> {
+        ron::de::from_str::<T::Settings>(settings).map(|_| ())
+    }
+}
+
 pub trait Asset: TypeUuid + AssetDynamic {}
 
 pub trait AssetDynamic: Downcast + TypeUuidDynamic + Send + Sync + 'static {}
@@ -78,6 +127,8 @@ pub struct LoadContext<'a> {
     pub(crate) asset_io: &'a dyn AssetIo,
     pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
     pub(crate) path: &'a Path,
+    pub(crate) settings: Option<&'a str>,
+    pub(crate) meta: Option<&'a [u8]>,
     pub(crate) version: usize,
     pub(crate) task_pool: &'a TaskPool,
 }
@@ -85,6 +136,8 @@ pub struct LoadContext<'a> {
 impl<'a> LoadContext<'a> {
     pub(crate) fn new(
         path: &'a Path,
+        settings: Option<&'a str>,
+        meta: Option<&'a [u8]>,
         ref_change_channel: &'a RefChangeChannel,
         asset_io: &'a dyn AssetIo,
         version: usize,
@@ -96,6 +149,8 @@ impl<'a> LoadContext<'a> {
             labeled_assets: Default::default(),
             version,
             path,
+            settings,
+            meta,
             task_pool,
         }
     }
@@ -104,6 +159,25 @@ impl<'a> LoadContext<'a> {
         self.path
     }
 
+    /// Reads the loader settings of the asset, which are the settings it is loaded with (see
+    /// [`AssetServer::load_with_settings`]), the `settings` of its `.meta` file (see
+    /// [`meta_path`](crate::meta_path)), or their default value if there are none.
+    ///
+    /// `S` is the [`AssetLoader::Settings`] of the loader.
+    pub fn settings<S: DeserializeOwned + Default>(&self) -> Result<S, ron::Error> {
+        match (self.settings, self.meta) {
+            (Some(settings), _) => ron::de::from_str(settings),
+            (None, Some(meta)) => Ok(ron::de::from_bytes::<SettingsMeta<S>>(meta)?.settings),
+            (None, None) => Ok(S::default()),
+        }
+    }
+
+    /// Returns the path of a labeled asset of the source being loaded, with the settings it is
+    /// loaded with.
+    pub fn get_labeled_asset_path<'b>(&'b self, label: &'b str) -> AssetPath<'b> {
+        AssetPath::new_source_ref(self.path, Some(label), self.settings)
+    }
+
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
     }
@@ -116,7 +190,7 @@ impl<'a> LoadContext<'a> {
         assert!(!label.is_empty());
         self.labeled_assets
             .insert(Some(label.to_string()), asset.into());
-        self.get_handle(AssetPath::new_ref(self.path(), Some(label)))
+        self.get_handle(self.get_labeled_asset_path(label))
     }
 
     pub fn get_handle<I: Into<HandleId>, T: Asset>(&self, id: I) -> Handle<T> {
@@ -11,6 +11,8 @@ use std::{
 pub struct AssetPath<'a> {
     path: Cow<'a, Path>,
     label: Option<Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    settings: Option<Cow<'a, str>>,
 }
 
 impl<'a> AssetPath<'a> {
@@ -19,6 +21,21 @@ impl<'a> AssetPath<'a> {
         AssetPath {
             path: Cow::Borrowed(path),
             label: label.map(|val| Cow::Borrowed(val)),
+            settings: None,
+        }
+    }
+
+    /// Returns the path of an asset of a source loaded with `settings`
+    #[inline]
+    pub(crate) fn new_source_ref(
+        path: &'a Path,
+        label: Option<&'a str>,
+        settings: Option<&'a str>,
+    ) -> AssetPath<'a> {
+        AssetPath {
+            path: Cow::Borrowed(path),
+            label: label.map(Cow::Borrowed),
+            settings: settings.map(Cow::Borrowed),
         }
     }
 
@@ -27,9 +44,21 @@ impl<'a> AssetPath<'a> {
         AssetPath {
             path: Cow::Owned(path),
             label: label.map(Cow::Owned),
+            settings: None,
         }
     }
 
+    /// Sets the loader settings of the asset, serialized as
+    /// [RON](https://github.com/ron-rs/ron). They replace the settings of its `.meta` file, see
+    /// [`LoadContext::settings`](crate::LoadContext::settings).
+    ///
+    /// Loading the same source with different settings loads different assets.
+    #[inline]
+    pub fn with_settings<S: Into<Cow<'a, str>>>(mut self, settings: S) -> AssetPath<'a> {
+        self.settings = Some(settings.into());
+        self
+    }
+
     #[inline]
     pub fn get_id(&self) -> AssetPathId {
         AssetPathId::from(self)
@@ -45,6 +74,11 @@ impl<'a> AssetPath<'a> {
         &self.path
     }
 
+    #[inline]
+    pub fn settings(&self) -> Option<&str> {
+        self.settings.as_ref().map(|settings| settings.as_ref())
+    }
+
     #[inline]
     pub fn to_owned(&self) -> AssetPath<'static> {
         AssetPath {
@@ -53,6 +87,10 @@ impl<'a> AssetPath<'a> {
                 .label
                 .as_ref()
                 .map(|value| Cow::Owned(value.to_string())),
+            settings: self
+                .settings
+                .as_ref()
+                .map(|value| Cow::Owned(value.to_string())),
         }
     }
 }
@@ -77,8 +115,18 @@ pub struct LabelId(u64);
 
 impl<'a> From<&'a Path> for SourcePathId {
     fn from(value: &'a Path) -> Self {
+        SourcePathId::new(value, None)
+    }
+}
+
+impl SourcePathId {
+    fn new(path: &Path, settings: Option<&str>) -> Self {
         let mut hasher = get_hasher();
-        value.hash(&mut hasher);
+        path.hash(&mut hasher);
+        // sources without settings keep the id of their path
+        if let Some(settings) = settings {
+            settings.hash(&mut hasher);
+        }
         SourcePathId(hasher.finish())
     }
 }
@@ -125,7 +173,7 @@ where
     fn from(value: T) -> Self {
         let asset_path: AssetPath = value.into();
         AssetPathId(
-            SourcePathId::from(asset_path.path()),
+            SourcePathId::new(asset_path.path(), asset_path.settings()),
             LabelId::from(asset_path.label()),
         )
     }
@@ -134,7 +182,7 @@ where
 impl<'a, 'b> From<&'a AssetPath<'b>> for AssetPathId {
     fn from(asset_path: &'a AssetPath<'b>) -> Self {
         AssetPathId(
-            SourcePathId::from(asset_path.path()),
+            SourcePathId::new(asset_path.path(), asset_path.settings()),
             LabelId::from(asset_path.label()),
         )
     }
@@ -148,6 +196,7 @@ impl<'a> From<&'a str> for AssetPath<'a> {
         AssetPath {
             path: Cow::Borrowed(path),
             label: label.map(|label| Cow::Borrowed(label)),
+            settings: None,
         }
     }
 }
@@ -157,6 +206,7 @@ impl<'a> From<&'a Path> for AssetPath<'a> {
         AssetPath {
             path: Cow::Borrowed(path),
             label: None,
+            settings: None,
         }
     }
 }
@@ -166,6 +216,7 @@ impl<'a> From<PathBuf> for AssetPath<'a> {
         AssetPath {
             path: Cow::Owned(path),
             label: None,
+            settings: None,
         }
     }
 }
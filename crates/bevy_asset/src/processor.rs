@@ -0,0 +1,145 @@
+use crate::{AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_utils::BoxedFuture;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    ffi::{OsStr, OsString},
+    hash::Hasher,
+    path::{Path, PathBuf},
+};
+
+/// The default folder processed artifacts are written to, relative to the asset folder
+pub const DEFAULT_PROCESSED_FOLDER: &str = ".processed";
+
+/// Transforms asset sources into optimized artifacts ahead of loading them, such as compressed
+/// textures or baked meshes.
+///
+/// A source is processed when its `.meta` file selects a processor by [name](Self::name), see
+/// [`meta_path`]. The artifacts are cached by [`AssetServer::process`](crate::AssetServer::process)
+/// and loaded instead of the source when present.
+pub trait AssetProcessor: Send + Sync + 'static {
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        process_context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>>;
+
+    /// The name `.meta` files select this processor with
+    fn name(&self) -> &str;
+
+    /// The extension of the artifacts, which selects the [`AssetLoader`](crate::AssetLoader)
+    /// loading them
+    fn extension(&self) -> &str;
+
+    /// The version of the processor. Changing it invalidates the artifacts of earlier versions.
+    fn version(&self) -> u32 {
+        0
+    }
+}
+
+pub struct ProcessContext<'a> {
+    pub(crate) path: &'a Path,
+    pub(crate) meta: &'a [u8],
+    pub(crate) asset_io: &'a dyn AssetIo,
+}
+
+impl<'a> ProcessContext<'a> {
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+
+    /// Reads the `processor_settings` of the `.meta` file, or their default value if there are
+    /// none.
+    pub fn processor_settings<S: DeserializeOwned + Default>(&self) -> Result<S, ron::Error> {
+        Ok(ron::de::from_bytes::<ProcessorSettingsMeta<S>>(self.meta)?.processor_settings)
+    }
+
+    pub async fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
+        self.asset_io.load_path(path.as_ref()).await
+    }
+}
+
+/// Returns the path of the `.meta` file of an asset source, which is the path of the source with
+/// `.meta` appended, such as `textures/grass.png.meta`.
+///
+/// `.meta` files are [RON](https://github.com/ron-rs/ron) structs, where every field is optional:
+///
+/// ```ron
+/// (
+///     // the name of the `AssetProcessor` transforming the source
+///     processor: Some("compress_texture"),
+///     // the settings of the processor, see `ProcessContext::processor_settings`
+///     processor_settings: (quality: 80),
+///     // the settings of the loader, see `LoadContext::settings`
+///     settings: (srgb: false),
+/// )
+/// ```
+pub fn meta_path(path: &Path) -> PathBuf {
+    let mut meta_path = OsString::from(path.as_os_str());
+    meta_path.push(".meta");
+    meta_path.into()
+}
+
+pub(crate) fn is_meta_path(path: &Path) -> bool {
+    path.extension() == Some(OsStr::new("meta"))
+}
+
+/// Returns the path of the artifact of an asset source in `processed_folder`, which is keyed by
+/// the hash of everything the artifact depends on.
+///
+/// Only the processing part of the `.meta` file is hashed, so changing the `settings` of the
+/// loader doesn't invalidate the artifact.
+pub(crate) fn processed_path(
+    processed_folder: &Path,
+    processor: &dyn AssetProcessor,
+    meta: &[u8],
+    bytes: &[u8],
+) -> Result<PathBuf, ron::Error> {
+    // reserialized, so that formatting the `.meta` file doesn't invalidate the artifact either
+    let meta = ron::ser::to_string(&ron::de::from_bytes::<ProcessingMeta>(meta)?)?;
+
+    // FNV is stable across builds and platforms, so artifacts processed by a tool are found by
+    // the game. Lengths are hashed as little endian u64 for the same reason.
+    let mut hasher = fnv::FnvHasher::default();
+    for part in [processor.name().as_bytes(), meta.as_bytes(), bytes].iter() {
+        hasher.write(&(part.len() as u64).to_le_bytes());
+        hasher.write(part);
+    }
+    hasher.write(&processor.version().to_le_bytes());
+    Ok(processed_folder.join(format!(
+        "{:016x}.{}",
+        hasher.finish(),
+        processor.extension()
+    )))
+}
+
+/// The fields of a `.meta` file that processing depends on
+#[derive(Deserialize, Serialize)]
+struct ProcessingMeta {
+    #[serde(default)]
+    processor: Option<String>,
+    #[serde(default = "no_processor_settings")]
+    processor_settings: ron::Value,
+}
+
+fn no_processor_settings() -> ron::Value {
+    ron::Value::Unit
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ProcessorMeta {
+    #[serde(default)]
+    pub(crate) processor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ProcessorSettingsMeta<S> {
+    #[serde(default)]
+    processor_settings: S,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SettingsMeta<S> {
+    #[serde(default)]
+    pub(crate) settings: S,
+}
@@ -1,5 +1,6 @@
-use crate::{AudioSource, Decodable};
-use bevy_asset::{Asset, Handle};
+use crate::{AudioSink, AudioSource, Decodable};
+use bevy_asset::{Asset, Handle, HandleId};
+use bevy_ecs::entity::Entity;
 use parking_lot::RwLock;
 use std::{collections::VecDeque, fmt};
 
@@ -8,7 +9,7 @@ pub struct Audio<P = AudioSource>
 where
     P: Asset + Decodable,
 {
-    pub queue: RwLock<VecDeque<Handle<P>>>,
+    pub queue: RwLock<VecDeque<AudioToPlay<P>>>,
 }
 
 impl<P: Asset> fmt::Debug for Audio<P>
@@ -37,7 +38,122 @@ where
     <P as Decodable>::Decoder: rodio::Source + Send + Sync,
     <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync,
 {
-    pub fn play(&self, audio_source: Handle<P>) {
-        self.queue.write().push_front(audio_source);
+    /// Plays a sound once, at its original volume and speed.
+    ///
+    /// Returns a weak handle to the [`AudioSink`] controlling the sound once it starts playing.
+    pub fn play(&self, audio_source: Handle<P>) -> Handle<AudioSink> {
+        self.play_with_settings(audio_source, PlaybackSettings::ONCE)
+    }
+
+    /// Plays a sound with the given settings, see [`Audio::play`]
+    pub fn play_with_settings(
+        &self,
+        audio_source: Handle<P>,
+        settings: PlaybackSettings,
+    ) -> Handle<AudioSink> {
+        self.queue(audio_source, settings, None)
+    }
+
+    /// Plays a sound from `emitter`, an entity with an [`AudioEmitter`](crate::AudioEmitter)
+    /// component. The sound is panned and attenuated depending on where the emitter is relative
+    /// to the [`AudioListener`](crate::AudioListener), and stops once the emitter is despawned.
+    pub fn play_spatial(&self, audio_source: Handle<P>, emitter: Entity) -> Handle<AudioSink> {
+        self.play_spatial_with_settings(audio_source, PlaybackSettings::ONCE, emitter)
+    }
+
+    /// Plays a sound from `emitter` with the given settings, see [`Audio::play_spatial`]
+    pub fn play_spatial_with_settings(
+        &self,
+        audio_source: Handle<P>,
+        settings: PlaybackSettings,
+        emitter: Entity,
+    ) -> Handle<AudioSink> {
+        self.queue(audio_source, settings, Some(emitter))
+    }
+
+    fn queue(
+        &self,
+        audio_source: Handle<P>,
+        settings: PlaybackSettings,
+        emitter: Option<Entity>,
+    ) -> Handle<AudioSink> {
+        let sink_handle = Handle::<AudioSink>::weak(HandleId::random::<AudioSink>());
+        self.queue.write().push_front(AudioToPlay {
+            source_handle: audio_source,
+            sink_handle: sink_handle.clone_weak(),
+            settings,
+            emitter,
+        });
+        sink_handle
+    }
+}
+
+/// How a sound is played
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackSettings {
+    /// Plays the sound again from the start once it ends, until it is stopped
+    pub repeat: bool,
+    /// Volume of the sound, `1.0` being its original volume
+    pub volume: f32,
+    /// Playback speed of the sound, `1.0` being its original speed
+    pub speed: f32,
+}
+
+impl Default for PlaybackSettings {
+    fn default() -> Self {
+        Self::ONCE
+    }
+}
+
+impl PlaybackSettings {
+    /// Plays the sound once
+    pub const ONCE: PlaybackSettings = PlaybackSettings {
+        repeat: false,
+        volume: 1.0,
+        speed: 1.0,
+    };
+
+    /// Plays the sound in a loop
+    pub const LOOP: PlaybackSettings = PlaybackSettings {
+        repeat: true,
+        volume: 1.0,
+        speed: 1.0,
+    };
+
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+}
+
+/// A sound queued in [`Audio`], waiting for its source to load
+pub struct AudioToPlay<P>
+where
+    P: Asset + Decodable,
+{
+    pub source_handle: Handle<P>,
+    /// Where the [`AudioSink`] of the sound is added once it plays
+    pub sink_handle: Handle<AudioSink>,
+    pub settings: PlaybackSettings,
+    /// The entity the sound is emitted from, for spatial sounds
+    pub emitter: Option<Entity>,
+}
+
+impl<P> fmt::Debug for AudioToPlay<P>
+where
+    P: Asset + Decodable,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AudioToPlay")
+            .field("source_handle", &self.source_handle)
+            .field("sink_handle", &self.sink_handle)
+            .field("settings", &self.settings)
+            .field("emitter", &self.emitter)
+            .finish()
     }
 }
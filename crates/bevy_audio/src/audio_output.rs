@@ -1,9 +1,11 @@
-use crate::{Audio, AudioSource, Decodable};
+use crate::{
+    Audio, AudioSink, AudioSource, AudioToPlay, ControlledSource, Decodable, PlaybackControls,
+};
 use bevy_asset::{Asset, Assets};
 use bevy_ecs::world::World;
 use bevy_utils::tracing::warn;
-use rodio::{OutputStream, OutputStreamHandle, Sink};
-use std::marker::PhantomData;
+use rodio::{OutputStream, OutputStreamHandle, Sink, Source};
+use std::{marker::PhantomData, sync::Arc};
 
 /// Used internally to play audio on the current "audio device"
 pub struct AudioOutput<P = AudioSource>
@@ -43,32 +45,55 @@ where
     <P as Decodable>::Decoder: rodio::Source + Send + Sync,
     <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync,
 {
-    fn play_source(&self, audio_source: &P) {
-        if let Some(stream_handle) = &self.stream_handle {
-            let sink = Sink::try_new(stream_handle).unwrap();
-            sink.append(audio_source.decoder());
-            sink.detach();
+    fn play_source(&self, audio_source: &P, to_play: &AudioToPlay<P>) -> Option<AudioSink> {
+        let stream_handle = self.stream_handle.as_ref()?;
+        let sink = Sink::try_new(stream_handle).unwrap();
+        let settings = &to_play.settings;
+        let spatial = to_play.emitter.is_some();
+        let controls = Arc::new(PlaybackControls::new(settings.speed, spatial));
+        sink.set_volume(settings.volume);
+        if settings.repeat {
+            sink.append(ControlledSource::new(
+                audio_source.decoder().repeat_infinite(),
+                controls.clone(),
+                spatial,
+            ));
+        } else {
+            sink.append(ControlledSource::new(
+                audio_source.decoder(),
+                controls.clone(),
+                spatial,
+            ));
         }
+        Some(AudioSink::new(sink, controls, to_play.emitter))
     }
 
-    fn try_play_queued(&self, audio_sources: &Assets<P>, audio: &mut Audio<P>) {
+    fn try_play_queued(
+        &self,
+        audio_sources: &Assets<P>,
+        audio: &mut Audio<P>,
+        sinks: &mut Assets<AudioSink>,
+    ) {
         let mut queue = audio.queue.write();
         let len = queue.len();
         let mut i = 0;
         while i < len {
-            let audio_source_handle = queue.pop_back().unwrap();
-            if let Some(audio_source) = audio_sources.get(&audio_source_handle) {
-                self.play_source(audio_source);
+            let to_play = queue.pop_back().unwrap();
+            if let Some(audio_source) = audio_sources.get(&to_play.source_handle) {
+                if let Some(sink) = self.play_source(audio_source, &to_play) {
+                    sinks.set_untracked(&to_play.sink_handle, sink);
+                }
             } else {
                 // audio source hasn't loaded yet. add it back to the queue
-                queue.push_front(audio_source_handle);
+                queue.push_front(to_play);
             }
             i += 1;
         }
     }
 }
 
-/// Plays audio currently queued in the [Audio] resource through the [AudioOutput] resource
+/// Plays audio currently queued in the [Audio] resource through the [AudioOutput] resource, and
+/// removes the [AudioSink]s of the sounds that ended
 pub fn play_queued_audio_system<P: Asset>(world: &mut World)
 where
     P: Decodable,
@@ -78,8 +103,18 @@ where
     let world = world.cell();
     let audio_output = world.get_non_send::<AudioOutput<P>>().unwrap();
     let mut audio = world.get_resource_mut::<Audio<P>>().unwrap();
+    let mut sinks = world.get_resource_mut::<Assets<AudioSink>>().unwrap();
+
+    let ended = sinks
+        .iter()
+        .filter(|(_, sink)| sink.empty())
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+    for id in ended {
+        sinks.remove(id);
+    }
 
     if let Some(audio_sources) = world.get_resource::<Assets<P>>() {
-        audio_output.try_play_queued(&*audio_sources, &mut *audio);
+        audio_output.try_play_queued(&*audio_sources, &mut *audio, &mut sinks);
     };
 }
@@ -0,0 +1,379 @@
+use bevy_ecs::entity::Entity;
+use bevy_reflect::TypeUuid;
+use rodio::{Sample, Sink, Source};
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Controls a sound played by [`Audio`](crate::Audio).
+///
+/// Sinks are added to [`Assets<AudioSink>`](bevy_asset::Assets) once their sound starts playing,
+/// under the handle returned when it was queued, and removed once it ended.
+#[derive(TypeUuid)]
+#[uuid = "8bec2bb8-4a6e-4c9a-9c8f-0ae6ba6f0f7e"]
+pub struct AudioSink {
+    sink: Sink,
+    controls: Arc<PlaybackControls>,
+    emitter: Option<Entity>,
+}
+
+impl AudioSink {
+    pub(crate) fn new(
+        sink: Sink,
+        controls: Arc<PlaybackControls>,
+        emitter: Option<Entity>,
+    ) -> Self {
+        Self {
+            sink,
+            controls,
+            emitter,
+        }
+    }
+
+    /// The volume of the sound, `1.0` being its original volume
+    pub fn volume(&self) -> f32 {
+        self.sink.volume()
+    }
+
+    /// Changes the volume of the sound. Values above `1.0` amplify it.
+    pub fn set_volume(&self, volume: f32) {
+        self.sink.set_volume(volume);
+    }
+
+    /// The playback speed of the sound, `1.0` being its original speed
+    pub fn speed(&self) -> f32 {
+        self.controls.speed()
+    }
+
+    /// Changes the playback speed of the sound, which also changes its pitch
+    pub fn set_speed(&self, speed: f32) {
+        self.controls.set_speed(speed);
+    }
+
+    /// Resumes the sound if it was paused
+    pub fn play(&self) {
+        self.sink.play();
+    }
+
+    /// Pauses the sound, until [`AudioSink::play`] is called
+    pub fn pause(&self) {
+        self.sink.pause();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.sink.is_paused()
+    }
+
+    /// Pauses the sound if it is playing, resumes it otherwise
+    pub fn toggle(&self) {
+        if self.is_paused() {
+            self.play();
+        } else {
+            self.pause();
+        }
+    }
+
+    /// Stops the sound for good. Stopped sounds can't be resumed.
+    pub fn stop(&self) {
+        self.sink.stop();
+    }
+
+    /// Whether the sound is over, or was stopped
+    pub fn empty(&self) -> bool {
+        self.sink.empty()
+    }
+
+    /// The entity the sound is emitted from, for spatial sounds
+    pub fn emitter(&self) -> Option<Entity> {
+        self.emitter
+    }
+
+    pub(crate) fn set_spatial_gains(&self, gains: [f32; 2]) {
+        self.controls.set_gains(gains);
+    }
+}
+
+/// Playback parameters shared between an [`AudioSink`] and the audio thread
+#[derive(Debug)]
+pub(crate) struct PlaybackControls {
+    speed: AtomicU32,
+    gains: [AtomicU32; 2],
+}
+
+impl PlaybackControls {
+    /// Spatial sounds start silent, until their gains are computed
+    pub(crate) fn new(speed: f32, spatial: bool) -> Self {
+        let gain = if spatial { 0f32 } else { 1f32 };
+        Self {
+            speed: AtomicU32::new(speed.to_bits()),
+            gains: [
+                AtomicU32::new(gain.to_bits()),
+                AtomicU32::new(gain.to_bits()),
+            ],
+        }
+    }
+
+    pub(crate) fn speed(&self) -> f32 {
+        f32::from_bits(self.speed.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn set_speed(&self, speed: f32) {
+        self.speed.store(speed.to_bits(), Ordering::Relaxed);
+    }
+
+    pub(crate) fn gains(&self) -> [f32; 2] {
+        [
+            f32::from_bits(self.gains[0].load(Ordering::Relaxed)),
+            f32::from_bits(self.gains[1].load(Ordering::Relaxed)),
+        ]
+    }
+
+    pub(crate) fn set_gains(&self, gains: [f32; 2]) {
+        self.gains[0].store(gains[0].to_bits(), Ordering::Relaxed);
+        self.gains[1].store(gains[1].to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Number of frames played between two reads of the [`PlaybackControls`]
+const CONTROL_PERIOD: usize = 512;
+
+/// Applies the [`PlaybackControls`] to a source.
+///
+/// Spatial sources are mixed down to mono, then played in stereo with the gain of each ear.
+pub(crate) struct ControlledSource<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    input: I,
+    controls: Arc<PlaybackControls>,
+    spatial: bool,
+    speed: f32,
+    gains: [f32; 2],
+    /// Samples to play before reading the controls again
+    remaining: usize,
+    /// For spatial sources, the sample left to play on the right channel
+    right: Option<I::Item>,
+}
+
+impl<I> ControlledSource<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    pub(crate) fn new(input: I, controls: Arc<PlaybackControls>, spatial: bool) -> Self {
+        let mut source = Self {
+            input,
+            controls,
+            spatial,
+            speed: 1.0,
+            gains: [1.0, 1.0],
+            remaining: 0,
+            right: None,
+        };
+        source.read_controls();
+        source
+    }
+
+    fn read_controls(&mut self) {
+        self.speed = self.controls.speed();
+        if self.spatial {
+            self.gains = self.controls.gains();
+        }
+        // a source may report 0 channels, which would never read the controls again
+        self.remaining = CONTROL_PERIOD * self.channels().max(1) as usize;
+    }
+
+    fn played(&mut self) {
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            self.read_controls();
+        }
+    }
+}
+
+impl<I> Iterator for ControlledSource<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if !self.spatial {
+            let sample = self.input.next()?;
+            self.played();
+            return Some(sample);
+        }
+
+        let sample = if let Some(right) = self.right.take() {
+            right.amplify(self.gains[1])
+        } else {
+            let channels = self.input.channels().max(1);
+            let mut mono: Option<I::Item> = None;
+            for _ in 0..channels {
+                let sample = self.input.next()?.amplify(1.0 / channels as f32);
+                mono = Some(match mono {
+                    Some(mono) => mono.saturating_add(sample),
+                    None => sample,
+                });
+            }
+            let mono = mono?;
+            self.right = Some(mono);
+            mono.amplify(self.gains[0])
+        };
+        self.played();
+        Some(sample)
+    }
+}
+
+impl<I> Source for ControlledSource<I>
+where
+    I: Source,
+    I::Item: Sample,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        let input = if self.spatial {
+            let channels = self.input.channels().max(1) as usize;
+            self.input
+                .current_frame_len()
+                .map(|len| len / channels * 2 + self.right.is_some() as usize)
+        } else {
+            self.input.current_frame_len()
+        };
+        Some(match input {
+            Some(input) => input.min(self.remaining),
+            None => self.remaining,
+        })
+    }
+
+    fn channels(&self) -> u16 {
+        if self.spatial {
+            2
+        } else {
+            self.input.channels()
+        }
+    }
+
+    fn sample_rate(&self) -> u32 {
+        (self.input.sample_rate() as f32 * self.speed) as u32
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    #[test]
+    fn spatial_source_pans_mono_down_mix() {
+        let controls = Arc::new(PlaybackControls::new(2.0, true));
+        controls.set_gains([0.5, 0.25]);
+        // stereo input, mixed down to mono before being panned
+        let input = SamplesBuffer::new(2, 44100, vec![1.0f32, 0.0, 0.5, 0.5]);
+        let source = ControlledSource::new(input, controls.clone(), true);
+        assert_eq!(source.channels(), 2);
+        assert_eq!(source.sample_rate(), 88200);
+        assert_eq!(source.current_frame_len(), Some(CONTROL_PERIOD * 2));
+        assert_eq!(source.collect::<Vec<_>>(), vec![0.25, 0.125, 0.25, 0.125]);
+    }
+
+    #[test]
+    fn non_spatial_source_ignores_gains() {
+        let controls = Arc::new(PlaybackControls::new(1.0, false));
+        controls.set_gains([0.0, 0.0]);
+        let input = SamplesBuffer::new(1, 44100, vec![0.5f32, -0.5]);
+        let source = ControlledSource::new(input, controls, false);
+        assert_eq!(source.channels(), 1);
+        assert_eq!(source.collect::<Vec<_>>(), vec![0.5, -0.5]);
+    }
+
+    #[test]
+    fn controls_are_read_periodically() {
+        let controls = Arc::new(PlaybackControls::new(1.0, false));
+        let input = SamplesBuffer::new(1, 1000, vec![1.0f32; CONTROL_PERIOD * 2]);
+        let mut source = ControlledSource::new(input, controls.clone(), false);
+        controls.set_speed(0.5);
+        assert_eq!(source.sample_rate(), 1000);
+        for _ in 0..CONTROL_PERIOD - 1 {
+            source.next();
+        }
+        assert_eq!(source.current_frame_len(), Some(1));
+        source.next();
+        assert_eq!(source.sample_rate(), 500);
+    }
+
+    #[test]
+    fn sink_controls() {
+        let (sink, mut output) = Sink::new_idle();
+        let controls = Arc::new(PlaybackControls::new(1.0, false));
+        sink.append(ControlledSource::new(
+            SamplesBuffer::new(1, 44100, vec![0.5f32; 44100]),
+            controls.clone(),
+            false,
+        ));
+        let audio_sink = AudioSink::new(sink, controls, None);
+        audio_sink.set_volume(0.5);
+        audio_sink.set_speed(1.5);
+        assert_eq!(audio_sink.volume(), 0.5);
+        assert_eq!(audio_sink.speed(), 1.5);
+        assert!(!audio_sink.empty());
+
+        audio_sink.toggle();
+        assert!(audio_sink.is_paused());
+        audio_sink.toggle();
+        assert!(!audio_sink.is_paused());
+        assert!(output.next().is_some());
+
+        audio_sink.stop();
+        // the sink notices it was stopped within a few milliseconds of samples
+        output.by_ref().take(4410).for_each(drop);
+        assert!(audio_sink.empty());
+    }
+
+    /// A source reporting 0 channels
+    struct NoChannels(std::vec::IntoIter<f32>);
+
+    impl Iterator for NoChannels {
+        type Item = f32;
+
+        fn next(&mut self) -> Option<f32> {
+            self.0.next()
+        }
+    }
+
+    impl Source for NoChannels {
+        fn current_frame_len(&self) -> Option<usize> {
+            None
+        }
+
+        fn channels(&self) -> u16 {
+            0
+        }
+
+        fn sample_rate(&self) -> u32 {
+            44100
+        }
+
+        fn total_duration(&self) -> Option<Duration> {
+            None
+        }
+    }
+
+    #[test]
+    fn source_without_channels_plays() {
+        let controls = Arc::new(PlaybackControls::new(1.0, false));
+        let samples = vec![0.5f32; CONTROL_PERIOD * 2];
+        let source =
+            ControlledSource::new(NoChannels(samples.clone().into_iter()), controls, false);
+        assert_eq!(source.collect::<Vec<_>>(), samples);
+    }
+}
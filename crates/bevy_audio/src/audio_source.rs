@@ -22,6 +22,8 @@ impl AsRef<[u8]> for AudioSource {
 pub struct Mp3Loader;
 
 impl AssetLoader for Mp3Loader {
+    type Settings = ();
+
     fn load(&self, bytes: &[u8], load_context: &mut LoadContext) -> BoxedFuture<Result<()>> {
         load_context.set_default_asset(LoadedAsset::new(AudioSource {
             bytes: bytes.into(),
@@ -1,19 +1,29 @@
 mod audio;
 mod audio_output;
+mod audio_sink;
 mod audio_source;
+mod spatial;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Audio, AudioOutput, AudioSource, Decodable};
+    pub use crate::{
+        Audio, AudioEmitter, AudioListener, AudioOutput, AudioSink, AudioSource, Decodable,
+        PlaybackSettings,
+    };
 }
 
 pub use audio::*;
 pub use audio_output::*;
+pub use audio_sink::AudioSink;
+pub(crate) use audio_sink::{ControlledSource, PlaybackControls};
 pub use audio_source::*;
+pub use spatial::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::AddAsset;
+use bevy_ecs::schedule::ParallelSystemDescriptorCoercion;
 use bevy_ecs::system::IntoExclusiveSystem;
+use bevy_transform::TransformSystem;
 
 /// Adds support for audio playback to an App
 #[derive(Default)]
@@ -23,10 +33,17 @@ impl Plugin for AudioPlugin {
     fn build(&self, app: &mut App) {
         app.init_non_send_resource::<AudioOutput<AudioSource>>()
             .add_asset::<AudioSource>()
+            .add_asset::<AudioSink>()
             .init_resource::<Audio<AudioSource>>()
+            .register_type::<AudioEmitter>()
+            .register_type::<AudioListener>()
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 play_queued_audio_system::<AudioSource>.exclusive_system(),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_spatial_audio_system.after(TransformSystem::TransformPropagate),
             );
 
         #[cfg(any(feature = "mp3", feature = "flac", feature = "wav", feature = "vorbis"))]
@@ -0,0 +1,159 @@
+use crate::AudioSink;
+use bevy_asset::Assets;
+use bevy_ecs::{
+    query::With,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_math::Vec3;
+use bevy_reflect::Reflect;
+use bevy_transform::components::GlobalTransform;
+
+/// Marks the entity hearing spatial sounds, usually the camera.
+///
+/// Its right is along its local `X` axis. Only the first listener found is used.
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct AudioListener;
+
+/// Makes an entity the source of the spatial sounds played from it with
+/// [`Audio::play_spatial`](crate::Audio::play_spatial)
+#[derive(Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct AudioEmitter {
+    /// Distance under which the sound plays at full volume
+    pub reference_distance: f32,
+    /// How fast the volume falls off past the reference distance. `0.0` disables attenuation.
+    pub rolloff: f32,
+}
+
+impl Default for AudioEmitter {
+    fn default() -> Self {
+        Self {
+            reference_distance: 1.0,
+            rolloff: 1.0,
+        }
+    }
+}
+
+impl AudioEmitter {
+    /// The volume of the sound at `distance`, following an inverse distance model
+    pub fn attenuation(&self, distance: f32) -> f32 {
+        if distance <= self.reference_distance {
+            return 1.0;
+        }
+        let reference_distance = self.reference_distance.max(f32::EPSILON);
+        reference_distance
+            / (reference_distance + self.rolloff.max(0.0) * (distance - reference_distance))
+    }
+}
+
+/// The gains of the left and right channels of a sound emitted at `emitter_position`, as heard
+/// by `listener`.
+///
+/// Sounds are panned with a constant power law, so that their loudness doesn't change as they
+/// move around the listener.
+pub fn spatial_gains(
+    emitter: &AudioEmitter,
+    emitter_position: Vec3,
+    listener: &GlobalTransform,
+) -> [f32; 2] {
+    let offset = emitter_position - listener.translation;
+    let distance = offset.length();
+    let attenuation = emitter.attenuation(distance);
+    let pan = if distance > f32::EPSILON {
+        (listener.rotation.inverse() * offset).x / distance
+    } else {
+        0.0
+    };
+    let angle = (pan.clamp(-1.0, 1.0) + 1.0) * std::f32::consts::FRAC_PI_4;
+    [angle.cos() * attenuation, angle.sin() * attenuation]
+}
+
+/// Updates the panning and attenuation of spatial sounds from the [`GlobalTransform`] of their
+/// [`AudioEmitter`] and of the [`AudioListener`]
+pub fn update_spatial_audio_system(
+    audio_sinks: Option<Res<Assets<AudioSink>>>,
+    listeners: Query<&GlobalTransform, With<AudioListener>>,
+    emitters: Query<(&AudioEmitter, &GlobalTransform)>,
+) {
+    let audio_sinks = match audio_sinks {
+        Some(audio_sinks) => audio_sinks,
+        None => return,
+    };
+    let listener = listeners.iter().next();
+    for (_, sink) in audio_sinks.iter() {
+        let emitter = match sink.emitter() {
+            Some(emitter) => emitter,
+            None => continue,
+        };
+        match (listener, emitters.get(emitter)) {
+            (Some(listener), Ok((emitter, transform))) => {
+                sink.set_spatial_gains(spatial_gains(emitter, transform.translation, listener));
+            }
+            // without listener, sounds are heard as if from the listener position
+            (None, Ok(_)) => sink.set_spatial_gains([std::f32::consts::FRAC_1_SQRT_2; 2]),
+            // the emitter is gone, and its sounds with it
+            (_, Err(_)) => sink.stop(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Quat;
+
+    fn assert_gains(gains: [f32; 2], expected: [f32; 2]) {
+        assert!(
+            (gains[0] - expected[0]).abs() < 1e-5 && (gains[1] - expected[1]).abs() < 1e-5,
+            "{:?} != {:?}",
+            gains,
+            expected
+        );
+    }
+
+    #[test]
+    fn pans_with_listener_orientation() {
+        let emitter = AudioEmitter::default();
+        let listener = GlobalTransform::identity();
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        assert_gains(spatial_gains(&emitter, Vec3::ZERO, &listener), [half, half]);
+        assert_gains(spatial_gains(&emitter, -Vec3::Z, &listener), [half, half]);
+        assert_gains(spatial_gains(&emitter, Vec3::X, &listener), [0.0, 1.0]);
+        assert_gains(spatial_gains(&emitter, -Vec3::X, &listener), [1.0, 0.0]);
+
+        // turned around, the listener hears the sound on its other ear
+        let turned = GlobalTransform::from_rotation(Quat::from_rotation_y(std::f32::consts::PI));
+        assert_gains(spatial_gains(&emitter, Vec3::X, &turned), [1.0, 0.0]);
+
+        // constant power panning
+        let gains = spatial_gains(&emitter, Vec3::new(0.3, 0.0, -0.4), &listener);
+        assert!((gains[0].powi(2) + gains[1].powi(2) - 1.0).abs() < 1e-5);
+        assert!(gains[1] > gains[0]);
+    }
+
+    #[test]
+    fn attenuates_with_distance() {
+        let emitter = AudioEmitter {
+            reference_distance: 2.0,
+            rolloff: 1.0,
+        };
+        assert_eq!(emitter.attenuation(1.0), 1.0);
+        assert_eq!(emitter.attenuation(2.0), 1.0);
+        assert_eq!(emitter.attenuation(4.0), 0.5);
+        assert_eq!(emitter.attenuation(8.0), 0.25);
+        let gains = spatial_gains(
+            &emitter,
+            Vec3::new(0.0, 0.0, -8.0),
+            &GlobalTransform::identity(),
+        );
+        assert_gains(gains, [0.25 * std::f32::consts::FRAC_1_SQRT_2; 2]);
+
+        let constant = AudioEmitter {
+            rolloff: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(constant.attenuation(100.0), 1.0);
+    }
+}
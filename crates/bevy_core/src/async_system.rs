@@ -0,0 +1,522 @@
+use crate::Time;
+use bevy_app::App;
+use bevy_ecs::{component::Component, event::Events, world::World};
+use bevy_tasks::AsyncComputeTaskPool;
+use bevy_utils::{tracing::warn, Duration};
+use event_listener::Event;
+use futures_lite::future;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Accesses the world on behalf of an async system, returns `true` once it is done
+type WorldRequest = Box<dyn FnMut(&mut World) -> bool + Send>;
+
+/// Starts an async system, given its handle to the world
+type AsyncSystemStarter =
+    Box<dyn FnOnce(AsyncWorld) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send>;
+
+/// Counts the frames seen by async systems
+#[derive(Default)]
+struct FrameSignal {
+    frame: AtomicU64,
+    event: Event,
+}
+
+/// The async systems of a world, driven by [`async_systems_system`].
+///
+/// Async systems are spawned on the [`AsyncComputeTaskPool`] the next time the driver runs, and
+/// access the world through their [`AsyncWorld`]. They stop when they complete, or when this
+/// resource is dropped.
+pub struct AsyncSystems {
+    sender: async_channel::Sender<WorldRequest>,
+    receiver: async_channel::Receiver<WorldRequest>,
+    /// Requests that take several frames to complete
+    waiting: Mutex<Vec<WorldRequest>>,
+    /// Async systems that haven't been spawned yet
+    pending: Mutex<Vec<AsyncSystemStarter>>,
+    frame: Arc<FrameSignal>,
+    /// Closed when the resource is dropped, to stop the async systems
+    _running: async_channel::Sender<()>,
+    stopped: async_channel::Receiver<()>,
+}
+
+impl Default for AsyncSystems {
+    fn default() -> Self {
+        let (sender, receiver) = async_channel::unbounded();
+        let (running, stopped) = async_channel::bounded(1);
+        AsyncSystems {
+            sender,
+            receiver,
+            waiting: Default::default(),
+            pending: Default::default(),
+            frame: Default::default(),
+            _running: running,
+            stopped,
+        }
+    }
+}
+
+impl AsyncSystems {
+    /// Adds an async system, usually an `async fn` taking an [`AsyncWorld`]. It is spawned the
+    /// next time [`async_systems_system`] runs, and runs until it completes.
+    pub fn add<F, Fut>(&mut self, system: F)
+    where
+        F: FnOnce(AsyncWorld) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.pending
+            .get_mut()
+            .unwrap()
+            .push(Box::new(move |world| Box::pin(system(world))));
+    }
+
+    fn world(&self) -> AsyncWorld {
+        AsyncWorld {
+            sender: self.sender.clone(),
+            frame: self.frame.clone(),
+        }
+    }
+}
+
+/// Spawns the new async systems, wakes the ones waiting for the next frame, and runs the world
+/// accesses they requested.
+///
+/// Requests that arrive while this system runs are handled the next time it runs.
+pub fn async_systems_system(world: &mut World) {
+    let mut systems = world
+        .get_resource_mut::<AsyncSystems>()
+        .expect("AsyncSystems resource does not exist");
+    let pending = std::mem::take(systems.pending.get_mut().unwrap());
+    let mut waiting = std::mem::take(systems.waiting.get_mut().unwrap());
+    waiting.extend(std::iter::from_fn(|| systems.receiver.try_recv().ok()));
+    if !pending.is_empty() {
+        let stopped = systems.stopped.clone();
+        let async_world = systems.world();
+        let task_pool = world
+            .get_resource::<AsyncComputeTaskPool>()
+            .expect("AsyncComputeTaskPool resource does not exist");
+        for system in pending {
+            let stopped = stopped.clone();
+            let running = system(async_world.clone());
+            task_pool
+                .spawn(future::or(running, async move {
+                    let _ = stopped.recv().await;
+                }))
+                .detach();
+        }
+    }
+
+    let systems = world.get_resource::<AsyncSystems>().unwrap();
+    systems.frame.frame.fetch_add(1, Ordering::AcqRel);
+    systems.frame.event.notify(usize::MAX);
+
+    let mut waiting = waiting
+        .into_iter()
+        .filter_map(|mut request| if request(world) { None } else { Some(request) })
+        .collect();
+    let mut systems = world.get_resource_mut::<AsyncSystems>().unwrap();
+    systems.waiting.get_mut().unwrap().append(&mut waiting);
+}
+
+/// The handle of an async system to the world.
+///
+/// World accesses are queued and run by [`async_systems_system`], the next time it runs, so each
+/// access usually takes a frame: group the accesses that don't need to await in between in a
+/// single [`AsyncWorld::with`].
+///
+/// ```no_run
+/// use bevy_core::{AddAsyncSystem, AsyncWorld};
+/// use bevy_app::App;
+/// use bevy_utils::Duration;
+///
+/// struct Dialogue(&'static str);
+///
+/// async fn cutscene(world: AsyncWorld) {
+///     world.with(|world| world.insert_resource(Dialogue("Where am I?"))).await;
+///     world.sleep(Duration::from_secs(2)).await;
+///     world.with(|world| world.insert_resource(Dialogue("..."))).await;
+/// }
+///
+/// App::new().add_async_system(cutscene);
+/// ```
+#[derive(Clone)]
+pub struct AsyncWorld {
+    sender: async_channel::Sender<WorldRequest>,
+    frame: Arc<FrameSignal>,
+}
+
+impl AsyncWorld {
+    /// Runs `f` with the world, and returns its result.
+    pub async fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut World) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let mut f = Some(f);
+        self.until(move |world| f.take().map(|f| f(world))).await
+    }
+
+    /// Runs `f` with the world once per frame, until it returns `Some`, and returns its result.
+    pub async fn until<F, R>(&self, f: F) -> R
+    where
+        F: FnMut(&mut World) -> Option<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        self.queue_until(f).await
+    }
+
+    /// Queues the request of [`AsyncWorld::until`] right away, rather than when the returned
+    /// future is first polled.
+    fn queue_until<F, R>(&self, mut f: F) -> impl Future<Output = R> + Send + 'static
+    where
+        F: FnMut(&mut World) -> Option<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (sender, receiver) = async_channel::bounded(1);
+        let request: WorldRequest = Box::new(move |world| match f(world) {
+            Some(result) => {
+                let _ = sender.try_send(result);
+                true
+            }
+            None => sender.is_closed(),
+        });
+        // the channel is unbounded, so this only fails once the world is gone
+        let _ = self.sender.try_send(request);
+        async move {
+            if let Ok(result) = receiver.recv().await {
+                return result;
+            }
+            // the world is gone, and the async system is about to be stopped
+            future::pending().await
+        }
+    }
+
+    /// Waits until the next time [`async_systems_system`] runs.
+    pub async fn next_frame(&self) {
+        let frame = self.frame.frame.load(Ordering::Acquire);
+        loop {
+            let listener = self.frame.event.listen();
+            if self.frame.frame.load(Ordering::Acquire) != frame {
+                return;
+            }
+            listener.await;
+        }
+    }
+
+    /// Waits until `duration` has elapsed, as measured by the [`Time`] resource.
+    ///
+    /// Never completes if there is no [`Time`] resource.
+    pub async fn sleep(&self, duration: Duration) {
+        let mut end = None;
+        let has_time = self
+            .until(move |world| {
+                let time = match world.get_resource::<Time>() {
+                    Some(time) => time,
+                    None => return Some(false),
+                };
+                let now = Duration::from_secs_f64(time.seconds_since_startup());
+                if now >= *end.get_or_insert(now + duration) {
+                    Some(true)
+                } else {
+                    None
+                }
+            })
+            .await;
+        if !has_time {
+            warn!("Time resource does not exist, the async system sleeps forever");
+            future::pending().await
+        }
+    }
+
+    /// Waits for the next event of type `T` sent after this is called, and returns a clone of it.
+    ///
+    /// Events are read from the next time [`async_systems_system`] runs after the call, even if
+    /// the returned future is awaited later, so it can be created before causing the event:
+    ///
+    /// ```no_run
+    /// # use bevy_core::AsyncWorld;
+    /// # use bevy_ecs::event::Events;
+    /// #[derive(Clone)]
+    /// struct Pong;
+    /// struct Ping;
+    ///
+    /// async fn ping(world: AsyncWorld) {
+    ///     let pong = world.next_event::<Pong>();
+    ///     world.with(|world| world.get_resource_mut::<Events<Ping>>().unwrap().send(Ping)).await;
+    ///     let Pong = pong.await;
+    /// }
+    /// ```
+    ///
+    /// Never completes if there is no [`Events<T>`] resource.
+    pub fn next_event<T: Component + Clone>(&self) -> impl Future<Output = T> + Send + 'static {
+        let reader = self.queue_until(|world| {
+            Some(
+                world
+                    .get_resource::<Events<T>>()
+                    .map(|events| events.get_reader_current()),
+            )
+        });
+        let world = self.clone();
+        async move {
+            let mut reader = match reader.await {
+                Some(reader) => reader,
+                None => {
+                    warn!(
+                        "Events<{}> resource does not exist, the async system waits forever",
+                        std::any::type_name::<T>()
+                    );
+                    return future::pending().await;
+                }
+            };
+            world
+                .until(move |world| {
+                    reader
+                        .iter(world.get_resource::<Events<T>>()?)
+                        .next()
+                        .cloned()
+                })
+                .await
+        }
+    }
+}
+
+/// Adds async systems to an [`App`].
+pub trait AddAsyncSystem {
+    /// Adds an async system, usually an `async fn` taking an [`AsyncWorld`], that starts during
+    /// the first update of the app and runs until it completes.
+    ///
+    /// Async systems are driven by [`async_systems_system`], which the
+    /// [`CorePlugin`](crate::CorePlugin) adds to [`CoreStage::Update`](bevy_app::CoreStage::Update).
+    fn add_async_system<F, Fut>(&mut self, system: F) -> &mut Self
+    where
+        F: FnOnce(AsyncWorld) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static;
+}
+
+impl AddAsyncSystem for App {
+    fn add_async_system<F, Fut>(&mut self, system: F) -> &mut Self
+    where
+        F: FnOnce(AsyncWorld) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.world
+            .get_resource_or_insert_with(AsyncSystems::default)
+            .add(system);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        schedule::ExclusiveSystemDescriptorCoercion,
+        schedule::{Stage, SystemStage},
+        system::{IntoExclusiveSystem, IntoSystem, Local, ResMut},
+    };
+    use bevy_tasks::TaskPool;
+
+    #[derive(Default)]
+    struct Frame(u64);
+
+    #[derive(Default)]
+    struct Log(Vec<String>);
+
+    #[derive(Clone)]
+    struct Ping(u32);
+
+    fn count_frames(mut frame: ResMut<Frame>) {
+        frame.0 += 1;
+    }
+
+    fn setup() -> (World, SystemStage) {
+        let mut world = World::new();
+        world.insert_resource(AsyncComputeTaskPool(TaskPool::new()));
+        world.insert_resource(AsyncSystems::default());
+        world.insert_resource(Frame::default());
+        world.insert_resource(Log::default());
+        world.insert_resource(Events::<Ping>::default());
+        let stage = SystemStage::single_threaded()
+            .with_system(async_systems_system.exclusive_system().at_start())
+            .with_system(count_frames.system())
+            .with_system(Events::<Ping>::update_system.system());
+        (world, stage)
+    }
+
+    /// Runs the stage until `done` returns `true`. Async systems run on other threads, so frames
+    /// are run until a generous deadline rather than for a number of frames.
+    fn run_until(
+        world: &mut World,
+        stage: &mut SystemStage,
+        mut done: impl FnMut(&mut World) -> bool,
+    ) {
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while std::time::Instant::now() < deadline {
+            stage.run(world);
+            if done(world) {
+                return;
+            }
+            std::thread::yield_now();
+        }
+        panic!("async system did not complete");
+    }
+
+    /// Runs the stage until the log has `len` entries
+    fn run_until_logged(world: &mut World, stage: &mut SystemStage, len: usize) {
+        run_until(world, stage, |world| {
+            world.get_resource::<Log>().unwrap().0.len() >= len
+        });
+    }
+
+    fn log(world: &mut World, message: String) {
+        world.get_resource_mut::<Log>().unwrap().0.push(message);
+    }
+
+    #[test]
+    fn async_system_frames() {
+        async fn wait_frames(world: AsyncWorld) {
+            let start = world
+                .with(|world| world.get_resource::<Frame>().unwrap().0)
+                .await;
+            world.next_frame().await;
+            world.next_frame().await;
+            world
+                .with(move |world| {
+                    let frames = world.get_resource::<Frame>().unwrap().0 - start;
+                    log(world, format!("{}", frames >= 2));
+                })
+                .await;
+        }
+
+        let (mut world, mut stage) = setup();
+        world
+            .get_resource_mut::<AsyncSystems>()
+            .unwrap()
+            .add(wait_frames);
+        run_until_logged(&mut world, &mut stage, 1);
+        assert_eq!(world.get_resource::<Log>().unwrap().0, vec!["true"]);
+    }
+
+    #[test]
+    fn async_system_events() {
+        async fn wait_ping(world: AsyncWorld) {
+            world.with(|world| log(world, "waiting".to_string())).await;
+            let Ping(value) = world.next_event::<Ping>().await;
+            world
+                .with(move |world| log(world, format!("ping {}", value)))
+                .await;
+        }
+
+        let (mut world, mut stage) = setup();
+        world
+            .get_resource_mut::<AsyncSystems>()
+            .unwrap()
+            .add(wait_ping);
+        run_until_logged(&mut world, &mut stage, 1);
+        // events sent before the async system waits are ignored
+        world
+            .get_resource_mut::<Events<Ping>>()
+            .unwrap()
+            .send(Ping(0));
+        // wait for the event reader to be created
+        run_until(&mut world, &mut stage, |world| {
+            !world
+                .get_resource_mut::<AsyncSystems>()
+                .unwrap()
+                .waiting
+                .get_mut()
+                .unwrap()
+                .is_empty()
+        });
+        world
+            .get_resource_mut::<Events<Ping>>()
+            .unwrap()
+            .send(Ping(1));
+        run_until_logged(&mut world, &mut stage, 2);
+        assert_eq!(
+            world.get_resource::<Log>().unwrap().0,
+            vec!["waiting", "ping 1"]
+        );
+    }
+
+    #[test]
+    fn async_system_event_sent_before_await() {
+        async fn ping_self(world: AsyncWorld) {
+            let ping = world.next_event::<Ping>();
+            world
+                .with(|world| {
+                    world
+                        .get_resource_mut::<Events<Ping>>()
+                        .unwrap()
+                        .send(Ping(2))
+                })
+                .await;
+            let Ping(value) = ping.await;
+            world
+                .with(move |world| log(world, format!("ping {}", value)))
+                .await;
+        }
+
+        let (mut world, mut stage) = setup();
+        world
+            .get_resource_mut::<AsyncSystems>()
+            .unwrap()
+            .add(ping_self);
+        run_until_logged(&mut world, &mut stage, 1);
+        assert_eq!(world.get_resource::<Log>().unwrap().0, vec!["ping 2"]);
+    }
+
+    #[test]
+    fn async_system_missing_resources() {
+        #[derive(Clone)]
+        struct Missing;
+
+        async fn wait_missing(world: AsyncWorld) {
+            world.next_event::<Missing>().await;
+        }
+
+        async fn sleep_without_time(world: AsyncWorld) {
+            world.sleep(Duration::from_millis(1)).await;
+        }
+
+        let (mut world, mut stage) = setup();
+        let mut systems = world.get_resource_mut::<AsyncSystems>().unwrap();
+        systems.add(wait_missing);
+        systems.add(sleep_without_time);
+        for _ in 0..10 {
+            stage.run(&mut world);
+        }
+    }
+
+    #[test]
+    fn async_system_sleep() {
+        async fn sleeper(world: AsyncWorld) {
+            world.sleep(Duration::from_millis(20)).await;
+            world.with(|world| log(world, "awake".to_string())).await;
+        }
+
+        // each frame advances the time by 5ms, regardless of the time it actually takes
+        fn advance_time(mut time: ResMut<Time>, mut frame: Local<u64>) {
+            *frame += 1;
+            let now = time.startup() + Duration::from_millis(5 * *frame);
+            time.update_with_instant(now);
+        }
+
+        let (mut world, mut stage) = setup();
+        world.insert_resource(Time::default());
+        stage.add_system(advance_time.system());
+        world
+            .get_resource_mut::<AsyncSystems>()
+            .unwrap()
+            .add(sleeper);
+        run_until_logged(&mut world, &mut stage, 1);
+        let time = world.get_resource::<Time>().unwrap();
+        assert!(time.seconds_since_startup() >= 0.02);
+    }
+}
@@ -1,3 +1,4 @@
+mod async_system;
 mod bytes;
 mod float_ord;
 mod label;
@@ -5,6 +6,7 @@ mod name;
 mod task_pool_options;
 mod time;
 
+pub use async_system::*;
 pub use bytes::*;
 pub use float_ord::*;
 pub use label::*;
@@ -14,7 +16,9 @@ pub use time::*;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{DefaultTaskPoolOptions, EntityLabels, Labels, Name, Time, Timer};
+    pub use crate::{
+        AddAsyncSystem, AsyncWorld, DefaultTaskPoolOptions, EntityLabels, Labels, Name, Time, Timer,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -35,6 +39,8 @@ pub enum CoreSystem {
     /// Updates the elapsed time. Any system that interacts with [Time] component should run after
     /// this.
     Time,
+    /// Runs the world accesses of async systems, see [AsyncWorld].
+    AsyncSystems,
 }
 
 impl Plugin for CorePlugin {
@@ -47,6 +53,7 @@ impl Plugin for CorePlugin {
             .create_default_pools(&mut app.world);
 
         app.init_resource::<Time>()
+            .init_resource::<AsyncSystems>()
             .init_resource::<EntityLabels>()
             .init_resource::<FixedTimesteps>()
             .register_type::<HashSet<String>>()
@@ -62,6 +69,13 @@ impl Plugin for CorePlugin {
                 CoreStage::First,
                 time_system.exclusive_system().label(CoreSystem::Time),
             )
+            .add_system_to_stage(
+                CoreStage::Update,
+                async_systems_system
+                    .exclusive_system()
+                    .at_start()
+                    .label(CoreSystem::AsyncSystems),
+            )
             .add_startup_system_to_stage(StartupStage::PostStartup, entity_labels_system)
             .add_system_to_stage(CoreStage::PostUpdate, entity_labels_system);
 
@@ -140,30 +140,60 @@ impl BundleInfo {
         // bundle_info.component_ids are also in "bundle order"
         let mut bundle_component = 0;
         bundle.get_components(|component_ptr| {
-            let component_id = *self.component_ids.get_unchecked(bundle_component);
-            match self.storage_types[bundle_component] {
-                StorageType::Table => {
-                    let column = table.get_column_mut(component_id).unwrap();
-                    match bundle_status.get_unchecked(bundle_component) {
-                        ComponentStatus::Added => {
-                            column.initialize(
-                                table_row,
-                                component_ptr,
-                                ComponentTicks::new(change_tick),
-                            );
-                        }
-                        ComponentStatus::Mutated => {
-                            column.replace(table_row, component_ptr, change_tick);
-                        }
+            self.write_component(
+                sparse_sets,
+                entity,
+                table,
+                table_row,
+                bundle_status,
+                bundle_component,
+                component_ptr,
+                change_tick,
+            );
+            bundle_component += 1;
+        });
+    }
+
+    /// Writes the component at `bundle_component` in "bundle order"
+    ///
+    /// # Safety
+    /// table row must exist, entity must be valid, `component_ptr` must point to a valid value of
+    /// the component, which is moved into its storage
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub(crate) unsafe fn write_component(
+        &self,
+        sparse_sets: &mut SparseSets,
+        entity: Entity,
+        table: &mut Table,
+        table_row: usize,
+        bundle_status: &[ComponentStatus],
+        bundle_component: usize,
+        component_ptr: *mut u8,
+        change_tick: u32,
+    ) {
+        let component_id = *self.component_ids.get_unchecked(bundle_component);
+        match self.storage_types[bundle_component] {
+            StorageType::Table => {
+                let column = table.get_column_mut(component_id).unwrap();
+                match bundle_status.get_unchecked(bundle_component) {
+                    ComponentStatus::Added => {
+                        column.initialize(
+                            table_row,
+                            component_ptr,
+                            ComponentTicks::new(change_tick),
+                        );
+                    }
+                    ComponentStatus::Mutated => {
+                        column.replace(table_row, component_ptr, change_tick);
                     }
-                }
-                StorageType::SparseSet => {
-                    let sparse_set = sparse_sets.get_mut(component_id).unwrap();
-                    sparse_set.insert(entity, component_ptr, change_tick);
                 }
             }
-            bundle_component += 1;
-        });
+            StorageType::SparseSet => {
+                let sparse_set = sparse_sets.get_mut(component_id).unwrap();
+                sparse_set.insert(entity, component_ptr, change_tick);
+            }
+        }
     }
 
     #[inline]
@@ -186,6 +216,8 @@ impl BundleInfo {
 pub struct Bundles {
     bundle_infos: Vec<BundleInfo>,
     bundle_ids: HashMap<TypeId, BundleId>,
+    /// Bundles of a single component, used to insert components by [`ComponentId`]
+    component_bundle_ids: HashMap<ComponentId, BundleId>,
 }
 
 impl Bundles {
@@ -217,6 +249,36 @@ impl Bundles {
         // SAFE: index either exists, or was initialized
         unsafe { self.bundle_infos.get_unchecked(id.0) }
     }
+
+    /// Initializes the info of the bundle made of the single component `component_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `component_id` doesn't exist in `components`.
+    pub(crate) fn init_component_info<'a>(
+        &'a mut self,
+        components: &Components,
+        component_id: ComponentId,
+    ) -> &'a BundleInfo {
+        let bundle_infos = &mut self.bundle_infos;
+        let id = self
+            .component_bundle_ids
+            .entry(component_id)
+            .or_insert_with(|| {
+                let component_info = components
+                    .get_info(component_id)
+                    .unwrap_or_else(|| panic!("{:?} does not exist", component_id));
+                let id = BundleId(bundle_infos.len());
+                bundle_infos.push(BundleInfo {
+                    id,
+                    component_ids: vec![component_id],
+                    storage_types: vec![component_info.storage_type()],
+                });
+                id
+            });
+        // SAFE: index either exists, or was initialized
+        unsafe { self.bundle_infos.get_unchecked(id.0) }
+    }
 }
 
 /// # Safety
@@ -42,8 +42,8 @@ pub trait DetectChanges {
 }
 
 macro_rules! change_detection_impl {
-    ($name:ident < $( $generics:tt ),+ >, $target:ty, $($traits:ident)?) => {
-        impl<$($generics),* $(: $traits)?> DetectChanges for $name<$($generics),*> {
+    ($name:ident < $( $generics:tt ),+ >, $target:ty, $($bounds:tt)*) => {
+        impl<$($generics),* $($bounds)*> DetectChanges for $name<$($generics),*> {
             #[inline]
             fn is_added(&self) -> bool {
                 self.ticks
@@ -66,7 +66,7 @@ macro_rules! change_detection_impl {
             }
         }
 
-        impl<$($generics),* $(: $traits)?> Deref for $name<$($generics),*> {
+        impl<$($generics),* $($bounds)*> Deref for $name<$($generics),*> {
             type Target = $target;
 
             #[inline]
@@ -75,7 +75,7 @@ macro_rules! change_detection_impl {
             }
         }
 
-        impl<$($generics),* $(: $traits)?> DerefMut for $name<$($generics),*> {
+        impl<$($generics),* $($bounds)*> DerefMut for $name<$($generics),*> {
             #[inline]
             fn deref_mut(&mut self) -> &mut Self::Target {
                 self.set_changed();
@@ -83,14 +83,14 @@ macro_rules! change_detection_impl {
             }
         }
 
-        impl<$($generics),* $(: $traits)?> AsRef<$target> for $name<$($generics),*> {
+        impl<$($generics),* $($bounds)*> AsRef<$target> for $name<$($generics),*> {
             #[inline]
             fn as_ref(&self) -> &$target {
                 self.deref()
             }
         }
 
-        impl<$($generics),* $(: $traits)?> AsMut<$target> for $name<$($generics),*> {
+        impl<$($generics),* $($bounds)*> AsMut<$target> for $name<$($generics),*> {
             #[inline]
             fn as_mut(&mut self) -> &mut $target {
                 self.deref_mut()
@@ -100,8 +100,8 @@ macro_rules! change_detection_impl {
 }
 
 macro_rules! impl_into_inner {
-    ($name:ident < $( $generics:tt ),+ >, $target:ty, $($traits:ident)?) => {
-        impl<$($generics),* $(: $traits)?> $name<$($generics),*> {
+    ($name:ident < $( $generics:tt ),+ >, $target:ty, $($bounds:tt)*) => {
+        impl<$($generics),* $($bounds)*> $name<$($generics),*> {
             /// Consume `self` and return a mutable reference to the
             /// contained value while marking `self` as "changed".
             #[inline]
@@ -146,8 +146,8 @@ pub struct ResMut<'a, T: Component> {
     pub(crate) ticks: Ticks<'a>,
 }
 
-change_detection_impl!(ResMut<'a, T>, T, Component);
-impl_into_inner!(ResMut<'a, T>, T, Component);
+change_detection_impl!(ResMut<'a, T>, T, : Component);
+impl_into_inner!(ResMut<'a, T>, T, : Component);
 impl_debug!(ResMut<'a, T>, Component);
 
 /// Unique borrow of a non-[`Send`] resource.
@@ -171,14 +171,14 @@ change_detection_impl!(NonSendMut<'a, T>, T,);
 impl_into_inner!(NonSendMut<'a, T>, T,);
 impl_debug!(NonSendMut<'a, T>,);
 
-/// Unique mutable borrow of an entity's component
-pub struct Mut<'a, T> {
+/// Unique mutable borrow of an entity's component, or of a trait object of one
+pub struct Mut<'a, T: ?Sized> {
     pub(crate) value: &'a mut T,
     pub(crate) ticks: Ticks<'a>,
 }
 
-change_detection_impl!(Mut<'a, T>, T,);
-impl_into_inner!(Mut<'a, T>, T,);
+change_detection_impl!(Mut<'a, T>, T, : ?Sized);
+impl_into_inner!(Mut<'a, T>, T, : ?Sized);
 impl_debug!(Mut<'a, T>,);
 
 /// Unique mutable borrow of a Reflected component
@@ -1,4 +1,4 @@
-use crate::storage::SparseSetIndex;
+use crate::{entity::Entity, storage::SparseSetIndex, world::World};
 use std::{
     alloc::Layout,
     any::{Any, TypeId},
@@ -22,6 +22,29 @@ use thiserror::Error;
 pub trait Component: Send + Sync + 'static {}
 impl<T: Send + Sync + 'static> Component for T {}
 
+/// Marks an entity as disabled, to take it out of the simulation without despawning it or
+/// removing its other components.
+///
+/// Queries skip disabled entities, unless they refer to [`Disabled`] themselves, for example with
+/// `With<Disabled>`, `Option<&Disabled>`, or the [`IncludeDisabled`](crate::query::IncludeDisabled)
+/// filter to match entities whether they are disabled or not. The components of disabled entities
+/// can still be accessed directly, with [`World::get`](crate::world::World::get) for example.
+///
+/// ```
+/// # use bevy_ecs::{component::Disabled, prelude::*};
+/// struct Bullet;
+///
+/// let mut world = World::new();
+/// let bullet = world.spawn().insert(Bullet).id();
+/// world.entity_mut(bullet).insert(Disabled);
+/// assert_eq!(world.query::<&Bullet>().iter(&world).count(), 0);
+///
+/// world.entity_mut(bullet).remove::<Disabled>();
+/// assert_eq!(world.query::<&Bullet>().iter(&world).count(), 1);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Disabled;
+
 /// The storage used for a specific component type.
 ///
 /// # Examples
@@ -92,6 +115,11 @@ impl ComponentInfo {
         self.descriptor.is_send_and_sync
     }
 
+    #[inline]
+    pub fn hooks(&self) -> &ComponentHooks {
+        &self.descriptor.hooks
+    }
+
     fn new(id: ComponentId, descriptor: ComponentDescriptor) -> Self {
         ComponentInfo { id, descriptor }
     }
@@ -123,6 +151,83 @@ impl SparseSetIndex for ComponentId {
     }
 }
 
+/// A function run when a component is added to, inserted on or removed from an entity. See
+/// [`ComponentHooks`].
+pub type ComponentHook = fn(&mut World, Entity, ComponentId);
+
+/// The hooks of a component type, run synchronously when the component is added to, inserted on
+/// or removed from an entity, to keep data outside of the [`World`] in sync with it.
+///
+/// Hooks get mutable access to the world, but must not despawn the entity they run for.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// struct Collider;
+/// #[derive(Default)]
+/// struct ColliderCount(usize);
+///
+/// let mut world = World::default();
+/// world.insert_resource(ColliderCount::default());
+/// world
+///     .register_component_hooks::<Collider>()
+///     .on_add(|world, _entity, _component_id| {
+///         world.get_resource_mut::<ColliderCount>().unwrap().0 += 1;
+///     })
+///     .on_remove(|world, _entity, _component_id| {
+///         world.get_resource_mut::<ColliderCount>().unwrap().0 -= 1;
+///     });
+///
+/// let entity = world.spawn().insert(Collider).id();
+/// assert_eq!(world.get_resource::<ColliderCount>().unwrap().0, 1);
+/// world.despawn(entity);
+/// assert_eq!(world.get_resource::<ColliderCount>().unwrap().0, 0);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ComponentHooks {
+    on_add: Option<ComponentHook>,
+    on_insert: Option<ComponentHook>,
+    on_remove: Option<ComponentHook>,
+}
+
+impl ComponentHooks {
+    /// Sets the hook run when the component is added to an entity that didn't have it, before its
+    /// `on_insert` hook. Replaces the previous `on_add` hook.
+    pub fn on_add(&mut self, hook: ComponentHook) -> &mut Self {
+        self.on_add = Some(hook);
+        self
+    }
+
+    /// Sets the hook run whenever the component is inserted on an entity, including when it
+    /// replaces a previous value. Replaces the previous `on_insert` hook.
+    pub fn on_insert(&mut self, hook: ComponentHook) -> &mut Self {
+        self.on_insert = Some(hook);
+        self
+    }
+
+    /// Sets the hook run when the component is removed from an entity, including when the entity
+    /// is despawned. It runs while the entity still has the component. Replaces the previous
+    /// `on_remove` hook.
+    pub fn on_remove(&mut self, hook: ComponentHook) -> &mut Self {
+        self.on_remove = Some(hook);
+        self
+    }
+
+    #[inline]
+    pub fn get_on_add(&self) -> Option<ComponentHook> {
+        self.on_add
+    }
+
+    #[inline]
+    pub fn get_on_insert(&self) -> Option<ComponentHook> {
+        self.on_insert
+    }
+
+    #[inline]
+    pub fn get_on_remove(&self) -> Option<ComponentHook> {
+        self.on_remove
+    }
+}
+
 #[derive(Debug)]
 pub struct ComponentDescriptor {
     name: String,
@@ -133,6 +238,7 @@ pub struct ComponentDescriptor {
     type_id: Option<TypeId>,
     layout: Layout,
     drop: unsafe fn(*mut u8),
+    hooks: ComponentHooks,
 }
 
 impl ComponentDescriptor {
@@ -149,6 +255,35 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: Self::drop_ptr::<T>,
+            hooks: ComponentHooks::default(),
+        }
+    }
+
+    /// Creates the descriptor of a component defined at runtime, such as by a scripting language,
+    /// rather than by a Rust type.
+    ///
+    /// Components described this way are only accessible by their [`ComponentId`], for example
+    /// with [`EntityMut::insert_by_id`](crate::world::EntityMut::insert_by_id) and
+    /// [`World::query_dynamic`](crate::world::World::query_dynamic).
+    ///
+    /// # Safety
+    ///
+    /// Values of the component must be [`Send`] and [`Sync`], and `drop` must be safe to call on a
+    /// pointer to a valid value with the given `layout`.
+    pub unsafe fn new_dynamic(
+        name: impl Into<String>,
+        storage_type: StorageType,
+        layout: Layout,
+        drop: unsafe fn(*mut u8),
+    ) -> Self {
+        Self {
+            name: name.into(),
+            storage_type,
+            is_send_and_sync: true,
+            type_id: None,
+            layout,
+            drop,
+            hooks: ComponentHooks::default(),
         }
     }
 
@@ -160,6 +295,7 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: Self::drop_ptr::<T>,
+            hooks: ComponentHooks::default(),
         }
     }
 
@@ -177,6 +313,12 @@ impl ComponentDescriptor {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// The hooks of the component, which can be set before registering it
+    #[inline]
+    pub fn hooks_mut(&mut self) -> &mut ComponentHooks {
+        &mut self.hooks
+    }
 }
 
 #[derive(Debug, Default)]
@@ -255,6 +397,14 @@ impl Components {
         self.components.get_unchecked(id.0)
     }
 
+    /// The hooks of the component `id`, see [`ComponentHooks`]
+    #[inline]
+    pub fn get_hooks_mut(&mut self, id: ComponentId) -> Option<&mut ComponentHooks> {
+        self.components
+            .get_mut(id.0)
+            .map(|info| &mut info.descriptor.hooks)
+    }
+
     #[inline]
     pub fn get_id(&self, type_id: TypeId) -> Option<ComponentId> {
         self.indices.get(&type_id).map(|index| ComponentId(*index))
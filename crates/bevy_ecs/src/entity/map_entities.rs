@@ -16,9 +16,16 @@ pub trait MapEntities {
 #[derive(Default, Debug)]
 pub struct EntityMap {
     map: HashMap<Entity, Entity>,
+    keep_unmapped: bool,
 }
 
 impl EntityMap {
+    /// Sets whether [`EntityMap::get`] returns entities that are not in the map unchanged, instead
+    /// of an error. This keeps references to entities outside of the map as they are.
+    pub fn keep_unmapped(&mut self, keep_unmapped: bool) {
+        self.keep_unmapped = keep_unmapped;
+    }
+
     pub fn insert(&mut self, from: Entity, to: Entity) {
         self.map.insert(from, to);
     }
@@ -32,10 +39,11 @@ impl EntityMap {
     }
 
     pub fn get(&self, entity: Entity) -> Result<Entity, MapEntitiesError> {
-        self.map
-            .get(&entity)
-            .cloned()
-            .ok_or(MapEntitiesError::EntityNotFound(entity))
+        match self.map.get(&entity) {
+            Some(mapped) => Ok(*mapped),
+            None if self.keep_unmapped => Ok(entity),
+            None => Err(MapEntitiesError::EntityNotFound(entity)),
+        }
     }
 
     pub fn keys(&self) -> impl Iterator<Item = Entity> + '_ {
@@ -294,6 +294,38 @@ impl Entities {
         loc
     }
 
+    /// Allocate a specific entity ID, unless it is already used by a live entity.
+    ///
+    /// Location should be written immediately when the entity did not exist.
+    pub fn alloc_at_without_replacement(&mut self, entity: Entity) -> AllocAtWithoutReplacement {
+        self.verify_flushed();
+
+        let result = if entity.id as usize >= self.meta.len() {
+            self.pending.extend((self.meta.len() as u32)..entity.id);
+            let new_free_cursor = self.pending.len() as i64;
+            *self.free_cursor.get_mut() = new_free_cursor;
+            self.meta.resize(entity.id as usize + 1, EntityMeta::EMPTY);
+            self.len += 1;
+            AllocAtWithoutReplacement::DidNotExist
+        } else if let Some(index) = self.pending.iter().position(|item| *item == entity.id) {
+            self.pending.swap_remove(index);
+            let new_free_cursor = self.pending.len() as i64;
+            *self.free_cursor.get_mut() = new_free_cursor;
+            self.len += 1;
+            AllocAtWithoutReplacement::DidNotExist
+        } else {
+            let meta = &self.meta[entity.id as usize];
+            return if meta.generation == entity.generation {
+                AllocAtWithoutReplacement::Exists(meta.location)
+            } else {
+                AllocAtWithoutReplacement::ExistsWithWrongGeneration
+            };
+        };
+
+        self.meta[entity.id as usize].generation = entity.generation;
+        result
+    }
+
     /// Destroy an entity, allowing it to be reused.
     ///
     /// Must not be called while reserved entities are awaiting `flush()`.
@@ -451,6 +483,17 @@ impl Entities {
     }
 }
 
+/// The result of [`Entities::alloc_at_without_replacement`]
+#[derive(Debug, Copy, Clone)]
+pub enum AllocAtWithoutReplacement {
+    /// The entity already exists, at this location
+    Exists(EntityLocation),
+    /// The entity id is used by a live entity of another generation
+    ExistsWithWrongGeneration,
+    /// The entity was allocated
+    DidNotExist,
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct EntityMeta {
     pub generation: u32,
@@ -0,0 +1,264 @@
+use crate as bevy_ecs;
+use crate::{
+    component::Component,
+    entity::Entity,
+    query::{Changed, IncludeDisabled},
+    system::{Query, RemovedComponents, Res, ResMut, SystemParam},
+};
+use bevy_utils::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// An index of the entities with a component of type `T`, by the value of the component.
+///
+/// The index is a resource kept up to date by [`ComponentIndex::update_system`], which uses change
+/// detection and [`RemovedComponents`] to only visit the components that changed since it last
+/// ran. Use [`IndexReader`] to find the entities with a given value from a system.
+///
+/// As removed components are only tracked until [`World::clear_trackers`](crate::world::World::clear_trackers)
+/// is called, the update system must run after the components are removed and before the
+/// trackers are cleared. Changes made after the update system ran are only visible in the index
+/// once it runs again.
+///
+/// ```
+/// use bevy_ecs::{index::ComponentIndex, prelude::*};
+///
+/// #[derive(Clone, PartialEq, Eq, Hash)]
+/// struct TeamId(u32);
+///
+/// let mut world = World::new();
+/// world.insert_resource(ComponentIndex::<TeamId>::default());
+/// let player = world.spawn().insert(TeamId(3)).id();
+/// world.spawn().insert(TeamId(1));
+///
+/// let mut stage = SystemStage::single_threaded()
+///     .with_system(ComponentIndex::<TeamId>::update_system.system());
+/// stage.run(&mut world);
+///
+/// let index = world.get_resource::<ComponentIndex<TeamId>>().unwrap();
+/// assert_eq!(index.get(&TeamId(3)).collect::<Vec<_>>(), vec![player]);
+/// ```
+pub struct ComponentIndex<T> {
+    entities: HashMap<T, HashSet<Entity>>,
+    values: HashMap<Entity, T>,
+}
+
+impl<T> Default for ComponentIndex<T> {
+    fn default() -> Self {
+        ComponentIndex {
+            entities: Default::default(),
+            values: Default::default(),
+        }
+    }
+}
+
+impl<T: Component + Eq + Hash + Clone> ComponentIndex<T> {
+    /// Returns the entities with a `T` component equal to `value`, in no particular order.
+    pub fn get(&self, value: &T) -> impl Iterator<Item = Entity> + '_ {
+        self.entities
+            .get(value)
+            .into_iter()
+            .flat_map(|entities| entities.iter().copied())
+    }
+
+    /// Returns the value of the `T` component of `entity`, as of the last index update.
+    pub fn value(&self, entity: Entity) -> Option<&T> {
+        self.values.get(&entity)
+    }
+
+    /// Returns `true` if an entity has a `T` component equal to `value`.
+    pub fn contains(&self, value: &T) -> bool {
+        self.entities.contains_key(value)
+    }
+
+    /// Returns the number of indexed entities.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    fn insert(&mut self, entity: Entity, value: &T) {
+        if let Some(old) = self.values.get(&entity) {
+            if old == value {
+                return;
+            }
+            self.remove(entity);
+        }
+        self.values.insert(entity, value.clone());
+        self.entities
+            .entry(value.clone())
+            .or_default()
+            .insert(entity);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(value) = self.values.remove(&entity) {
+            if let Some(entities) = self.entities.get_mut(&value) {
+                entities.remove(&entity);
+                if entities.is_empty() {
+                    self.entities.remove(&value);
+                }
+            }
+        }
+    }
+
+    /// A system that updates the index with the `T` components that changed or were removed since
+    /// it last ran.
+    ///
+    /// [`Disabled`](crate::component::Disabled) entities are indexed too, as their changes would
+    /// be missed otherwise once they are enabled again.
+    pub fn update_system(
+        mut index: ResMut<Self>,
+        changed: Query<(Entity, &T), (Changed<T>, IncludeDisabled)>,
+        removed: RemovedComponents<T>,
+    ) {
+        // removals come first, as a component can be removed and then inserted again
+        for entity in removed.iter() {
+            index.remove(entity);
+        }
+        for (entity, value) in changed.iter() {
+            index.insert(entity, value);
+        }
+    }
+}
+
+/// Finds entities by the value of their `T` component, using the [`ComponentIndex`] of `T`.
+#[derive(SystemParam)]
+pub struct IndexReader<'a, T: Component + Eq + Hash + Clone> {
+    index: Res<'a, ComponentIndex<T>>,
+}
+
+impl<'a, T: Component + Eq + Hash + Clone> IndexReader<'a, T> {
+    /// See [`ComponentIndex::get`].
+    pub fn get(&self, value: &T) -> impl Iterator<Item = Entity> + '_ {
+        self.index.get(value)
+    }
+
+    /// See [`ComponentIndex::value`].
+    pub fn value(&self, entity: Entity) -> Option<&T> {
+        self.index.value(entity)
+    }
+
+    /// See [`ComponentIndex::contains`].
+    pub fn contains(&self, value: &T) -> bool {
+        self.index.contains(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::Disabled,
+        schedule::{ParallelSystemDescriptorCoercion, Stage, SystemStage},
+        system::IntoSystem,
+        world::World,
+    };
+
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    struct TeamId(u32);
+
+    fn sorted(entities: impl Iterator<Item = Entity>) -> Vec<Entity> {
+        let mut entities = entities.collect::<Vec<_>>();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn component_index() {
+        let mut world = World::new();
+        world.insert_resource(ComponentIndex::<TeamId>::default());
+        let mut stage = SystemStage::single_threaded()
+            .with_system(ComponentIndex::<TeamId>::update_system.system());
+
+        let a = world.spawn().insert(TeamId(1)).id();
+        let b = world.spawn().insert(TeamId(1)).id();
+        let c = world.spawn().insert(TeamId(2)).id();
+        stage.run(&mut world);
+        {
+            let index = world.get_resource::<ComponentIndex<TeamId>>().unwrap();
+            assert_eq!(sorted(index.get(&TeamId(1))), vec![a, b]);
+            assert_eq!(sorted(index.get(&TeamId(2))), vec![c]);
+            assert_eq!(index.get(&TeamId(3)).count(), 0);
+            assert_eq!(index.len(), 3);
+        }
+        world.clear_trackers();
+
+        world.get_mut::<TeamId>(a).unwrap().0 = 2;
+        world.entity_mut(b).remove::<TeamId>();
+        world.despawn(c);
+        let d = world.spawn().insert(TeamId(1)).id();
+        stage.run(&mut world);
+        {
+            let index = world.get_resource::<ComponentIndex<TeamId>>().unwrap();
+            assert_eq!(sorted(index.get(&TeamId(1))), vec![d]);
+            assert_eq!(sorted(index.get(&TeamId(2))), vec![a]);
+            assert_eq!(index.value(a), Some(&TeamId(2)));
+            assert_eq!(index.value(b), None);
+            assert_eq!(index.len(), 2);
+        }
+        world.clear_trackers();
+
+        // removed and inserted again before the index is updated
+        world.entity_mut(d).remove::<TeamId>();
+        world.entity_mut(d).insert(TeamId(4));
+        stage.run(&mut world);
+        let index = world.get_resource::<ComponentIndex<TeamId>>().unwrap();
+        assert!(!index.contains(&TeamId(1)));
+        assert_eq!(sorted(index.get(&TeamId(4))), vec![d]);
+    }
+
+    #[test]
+    fn index_reader() {
+        #[derive(Default)]
+        struct Found(Vec<Entity>);
+
+        fn find_team(index: IndexReader<TeamId>, mut found: ResMut<Found>) {
+            found.0 = sorted(index.get(&TeamId(7)));
+        }
+
+        let mut world = World::new();
+        world.insert_resource(ComponentIndex::<TeamId>::default());
+        world.insert_resource(Found::default());
+        let mut stage = SystemStage::single_threaded()
+            .with_system(
+                ComponentIndex::<TeamId>::update_system
+                    .system()
+                    .label("index"),
+            )
+            .with_system(find_team.system().after("index"));
+
+        let a = world.spawn().insert(TeamId(7)).id();
+        world.spawn().insert(TeamId(8));
+        stage.run(&mut world);
+        assert_eq!(world.get_resource::<Found>().unwrap().0, vec![a]);
+    }
+
+    #[test]
+    fn index_disabled() {
+        let mut world = World::new();
+        world.insert_resource(ComponentIndex::<TeamId>::default());
+        let mut stage = SystemStage::single_threaded()
+            .with_system(ComponentIndex::<TeamId>::update_system.system());
+
+        let a = world.spawn().insert(TeamId(1)).id();
+        stage.run(&mut world);
+        world.clear_trackers();
+
+        // changed while disabled, and enabled again once the change is no longer tracked
+        world.entity_mut(a).insert(Disabled);
+        world.get_mut::<TeamId>(a).unwrap().0 = 2;
+        let b = world.spawn().insert_bundle((TeamId(2), Disabled)).id();
+        stage.run(&mut world);
+        world.clear_trackers();
+        world.entity_mut(a).remove::<Disabled>();
+        world.entity_mut(b).remove::<Disabled>();
+        stage.run(&mut world);
+
+        let index = world.get_resource::<ComponentIndex<TeamId>>().unwrap();
+        assert!(!index.contains(&TeamId(1)));
+        assert_eq!(sorted(index.get(&TeamId(2))), vec![a, b]);
+    }
+}
@@ -4,6 +4,7 @@ pub mod change_detection;
 pub mod component;
 pub mod entity;
 pub mod event;
+pub mod index;
 pub mod query;
 #[cfg(feature = "bevy_reflect")]
 pub mod reflect;
@@ -15,18 +16,21 @@ pub mod world;
 pub mod prelude {
     #[doc(hidden)]
     #[cfg(feature = "bevy_reflect")]
-    pub use crate::reflect::ReflectComponent;
+    pub use crate::reflect::{ReflectComponent, ReflectResource};
     #[doc(hidden)]
     pub use crate::{
         bundle::Bundle,
         change_detection::DetectChanges,
+        component::Disabled,
         entity::Entity,
         event::{EventReader, EventWriter},
-        query::{Added, ChangeTrackers, Changed, Or, QueryState, With, Without},
+        index::IndexReader,
+        query::{Added, ChangeTrackers, Changed, IncludeDisabled, Or, QueryState, With, Without},
         schedule::{
             AmbiguitySetLabel, ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion,
-            RunCriteria, RunCriteriaDescriptorCoercion, RunCriteriaLabel, RunCriteriaPiping,
-            Schedule, Stage, StageLabel, State, SystemLabel, SystemSet, SystemStage,
+            RunCriteria, RunCriteriaCombinators, RunCriteriaDescriptorCoercion, RunCriteriaLabel,
+            RunCriteriaPiping, Schedule, Stage, StageLabel, State, SystemLabel, SystemSet,
+            SystemStage,
         },
         system::{
             Commands, ConfigurableSystem, In, IntoChainSystem, IntoExclusiveSystem, IntoSystem,
@@ -34,6 +38,10 @@ pub mod prelude {
         },
         world::{FromWorld, Mut, World},
     };
+
+    #[cfg(feature = "bevy_reflect")]
+    #[doc(hidden)]
+    pub use crate::query::{Traits, TraitsMut};
 }
 
 #[cfg(test)]
@@ -41,10 +49,11 @@ mod tests {
     use crate as bevy_ecs;
     use crate::{
         bundle::Bundle,
-        component::{Component, ComponentDescriptor, ComponentId, StorageType},
+        component::{Component, ComponentDescriptor, ComponentId, Disabled, StorageType},
         entity::Entity,
         query::{
-            Added, ChangeTrackers, Changed, FilterFetch, FilteredAccess, With, Without, WorldQuery,
+            Added, ChangeTrackers, Changed, FilterFetch, FilteredAccess, IncludeDisabled, With,
+            Without, WorldQuery,
         },
         world::{Mut, World},
     };
@@ -183,6 +192,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn component_hooks() {
+        #[derive(Default)]
+        struct HookLog(Vec<(&'static str, Entity, usize)>);
+
+        fn log(world: &mut World, hook: &'static str, entity: Entity) {
+            let value = world.get::<A>(entity).map_or(0, |a| a.0);
+            world
+                .get_resource_mut::<HookLog>()
+                .unwrap()
+                .0
+                .push((hook, entity, value));
+        }
+
+        let mut world = World::new();
+        world.insert_resource(HookLog::default());
+        world
+            .register_component(ComponentDescriptor::new::<B>(StorageType::SparseSet))
+            .unwrap();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|world, entity, _| {
+                log(world, "add", entity);
+                // hooks can change the entity they run for
+                world.entity_mut(entity).insert(B(0));
+            })
+            .on_insert(|world, entity, _| log(world, "insert", entity))
+            .on_remove(|world, entity, _| log(world, "remove", entity));
+        world
+            .register_component_hooks::<B>()
+            .on_remove(|world, entity, _| {
+                assert!(world.get::<B>(entity).is_some());
+                log(world, "remove b", entity);
+            });
+
+        let e = world.spawn().insert_bundle((A(1), C)).id();
+        assert!(world.get::<B>(e).is_some());
+        world.entity_mut(e).insert(A(2));
+        assert_eq!(world.entity_mut(e).remove::<A>(), Some(A(2)));
+        world.entity_mut(e).remove_bundle_intersection::<(A, B)>();
+        let batch = world.spawn_batch(vec![(A(3),)]).collect::<Vec<_>>();
+        world.despawn(batch[0]);
+
+        assert_eq!(
+            world.get_resource::<HookLog>().unwrap().0,
+            vec![
+                ("add", e, 1),
+                ("insert", e, 1),
+                ("insert", e, 2),
+                ("remove", e, 2),
+                ("remove b", e, 0),
+                ("add", batch[0], 3),
+                ("insert", batch[0], 3),
+                ("remove", batch[0], 3),
+                ("remove b", batch[0], 3),
+            ]
+        );
+        assert!(world.get::<B>(e).is_none());
+        assert!(world.get::<C>(e).is_some());
+    }
+
     #[test]
     fn despawn_table_storage() {
         let mut world = World::new();
@@ -414,6 +484,52 @@ mod tests {
         assert_eq!(result, vec![456]);
     }
 
+    #[test]
+    fn query_skips_disabled() {
+        let mut world = World::new();
+        // created before any entity is disabled
+        let mut query = world.query::<&u32>();
+        let e = world.spawn().insert(123u32).id();
+        let f = world.spawn().insert_bundle((456u32, Disabled)).id();
+        assert_eq!(query.iter(&world).cloned().collect::<Vec<_>>(), vec![123]);
+        assert!(query.get(&world, f).is_err());
+        assert_eq!(*world.get::<u32>(f).unwrap(), 456);
+
+        let mut disabled = world
+            .query_filtered::<&u32, With<Disabled>>()
+            .iter(&world)
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(disabled, vec![456]);
+
+        let mut all = world
+            .query_filtered::<&u32, IncludeDisabled>()
+            .iter(&world)
+            .cloned()
+            .collect::<Vec<_>>();
+        all.sort_unstable();
+        assert_eq!(all, vec![123, 456]);
+
+        let mut optional = world
+            .query::<(Entity, Option<&Disabled>)>()
+            .iter(&world)
+            .filter(|(entity, _)| *entity == e || *entity == f)
+            .map(|(entity, disabled)| (entity, disabled.is_some()))
+            .collect::<Vec<_>>();
+        optional.sort_unstable();
+        assert_eq!(optional, vec![(e, false), (f, true)]);
+
+        world.entity_mut(e).insert(Disabled);
+        world.entity_mut(f).remove::<Disabled>();
+        assert_eq!(query.iter(&world).cloned().collect::<Vec<_>>(), vec![456]);
+        disabled = world
+            .query_filtered::<&u32, With<Disabled>>()
+            .iter(&world)
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(disabled, vec![123]);
+    }
+
     #[test]
     fn query_optional_component_table() {
         let mut world = World::new();
@@ -1166,6 +1282,9 @@ mod tests {
         let f64_id = world.components.get_id(TypeId::of::<f64>()).unwrap();
         expected.add_write(i32_id);
         expected.add_read(f64_id);
+        // queries skip disabled entities unless they refer to them
+        let disabled_id = world.components.get_id(TypeId::of::<Disabled>()).unwrap();
+        expected.add_without(disabled_id);
         assert!(
             query.component_access.eq(&expected),
             "ComponentId access from query fetch and query filter should be combined"
@@ -122,6 +122,7 @@ pub struct FilteredAccess<T: SparseSetIndex> {
     access: Access<T>,
     with: FixedBitSet,
     without: FixedBitSet,
+    referenced: FixedBitSet,
 }
 
 impl<T: SparseSetIndex> Default for FilteredAccess<T> {
@@ -130,6 +131,7 @@ impl<T: SparseSetIndex> Default for FilteredAccess<T> {
             access: Access::default(),
             with: Default::default(),
             without: Default::default(),
+            referenced: Default::default(),
         }
     }
 }
@@ -140,6 +142,13 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         &self.access
     }
 
+    /// Accesses added through this are not required to match, unlike those added by
+    /// [`FilteredAccess::add_read`] and [`FilteredAccess::add_write`].
+    #[inline]
+    pub fn access_mut(&mut self) -> &mut Access<T> {
+        &mut self.access
+    }
+
     pub fn add_read(&mut self, index: T) {
         self.access.add_read(index.clone());
         self.add_with(index);
@@ -160,6 +169,22 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.without.insert(index.sparse_set_index());
     }
 
+    /// Marks `index` as referred to without being accessed or filtered on.
+    pub fn add_referenced(&mut self, index: T) {
+        self.referenced.grow(index.sparse_set_index() + 1);
+        self.referenced.insert(index.sparse_set_index());
+    }
+
+    /// Returns true if `index` is accessed, filtered on or referred to. Accesses to all indices
+    /// don't count, as they don't refer to any index in particular.
+    pub fn references(&self, index: T) -> bool {
+        let index = index.sparse_set_index();
+        self.access.reads_and_writes.contains(index)
+            || self.with.contains(index)
+            || self.without.contains(index)
+            || self.referenced.contains(index)
+    }
+
     pub fn is_compatible(&self, other: &FilteredAccess<T>) -> bool {
         if self.access.is_compatible(&other.access) {
             true
@@ -173,6 +198,7 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.access.extend(&access.access);
         self.with.union_with(&access.with);
         self.without.union_with(&access.without);
+        self.referenced.union_with(&access.referenced);
     }
 }
 
@@ -0,0 +1,369 @@
+use crate::{
+    archetype::{Archetype, ArchetypeComponentId},
+    component::{ComponentId, ComponentTicks, StorageType},
+    entity::Entity,
+    query::{Access, Fetch, FetchState, FilteredAccess, WorldQuery},
+    storage::{Column, ComponentSparseSet, Table, Tables},
+    world::World,
+};
+use std::{marker::PhantomData, ptr};
+
+/// How a [`DynamicComponents`] query accesses one of its components
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DynamicAccess {
+    /// Shared access to the component, like `&T`
+    Read(ComponentId),
+    /// Exclusive access to the component, like `&mut T`
+    Write(ComponentId),
+}
+
+impl DynamicAccess {
+    #[inline]
+    pub fn id(&self) -> ComponentId {
+        match self {
+            DynamicAccess::Read(id) | DynamicAccess::Write(id) => *id,
+        }
+    }
+
+    #[inline]
+    pub fn is_write(&self) -> bool {
+        matches!(self, DynamicAccess::Write(_))
+    }
+}
+
+/// A [`WorldQuery`] for components chosen at runtime by their [`ComponentId`], such as components
+/// defined with [`ComponentDescriptor::new_dynamic`](crate::component::ComponentDescriptor::new_dynamic).
+///
+/// Its components are set by its [`DynamicComponentsState`], which can't be initialized from the
+/// type alone. Use [`World::query_dynamic`], or configure the state of a
+/// [`Query<DynamicComponents>`](crate::system::Query) system parameter:
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, query::{DynamicAccess, DynamicComponents, DynamicComponentsState}};
+/// # struct Health(u32);
+/// fn heal_system(mut query: Query<DynamicComponents>) {
+///     for mut components in query.iter_mut() {
+///         // SAFE: the first component is `Health`
+///         unsafe { (*components.get_mut(0).unwrap().cast::<Health>()).0 += 1 };
+///     }
+/// }
+///
+/// let mut world = World::default();
+/// let health = world.register_component(
+///     bevy_ecs::component::ComponentDescriptor::new::<Health>(Default::default()),
+/// ).unwrap();
+/// let state = DynamicComponentsState::new(&world, &[DynamicAccess::Write(health)]);
+/// let system = heal_system.system().config(|config| config.0 = Some(state));
+/// ```
+///
+/// Items are [`DynamicItem`]s, giving pointers to the components in the order of the accesses.
+///
+/// Accesses are tracked like those of `&T` and `&mut T`, so systems using dynamic queries run in
+/// parallel with other systems whenever their accesses allow it.
+pub struct DynamicComponents;
+
+impl WorldQuery for DynamicComponents {
+    type Fetch = DynamicComponentsFetch;
+    type State = DynamicComponentsState;
+}
+
+/// The [`FetchState`] of [`DynamicComponents`].
+pub struct DynamicComponentsState {
+    accesses: Vec<DynamicAccess>,
+    storage_types: Vec<StorageType>,
+}
+
+impl DynamicComponentsState {
+    /// Creates the state of a query for the given component accesses.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the components does not exist in `world`.
+    pub fn new(world: &World, accesses: &[DynamicAccess]) -> Self {
+        let storage_types = accesses
+            .iter()
+            .map(|access| {
+                world
+                    .components()
+                    .get_info(access.id())
+                    .unwrap_or_else(|| panic!("{:?} does not exist", access.id()))
+                    .storage_type()
+            })
+            .collect();
+        Self {
+            accesses: accesses.to_vec(),
+            storage_types,
+        }
+    }
+
+    #[inline]
+    pub fn accesses(&self) -> &[DynamicAccess] {
+        &self.accesses
+    }
+}
+
+// SAFETY: component access and archetype component access are properly updated to reflect each
+// component being read or written
+unsafe impl FetchState for DynamicComponentsState {
+    /// Without accesses, the query matches every entity
+    fn init(_world: &mut World) -> Self {
+        Self {
+            accesses: Vec::new(),
+            storage_types: Vec::new(),
+        }
+    }
+
+    fn update_component_access(&self, access: &mut FilteredAccess<ComponentId>) {
+        for dynamic_access in &self.accesses {
+            match *dynamic_access {
+                DynamicAccess::Read(id) => {
+                    if access.access().has_write(id) {
+                        panic!("Reading {:?} conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.", id);
+                    }
+                    access.add_read(id);
+                }
+                DynamicAccess::Write(id) => {
+                    if access.access().has_read(id) {
+                        panic!("Writing {:?} conflicts with a previous access in this query. Mutable component access must be unique.", id);
+                    }
+                    access.add_write(id);
+                }
+            }
+        }
+    }
+
+    fn update_archetype_component_access(
+        &self,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        for dynamic_access in &self.accesses {
+            if let Some(archetype_component_id) =
+                archetype.get_archetype_component_id(dynamic_access.id())
+            {
+                if dynamic_access.is_write() {
+                    access.add_write(archetype_component_id);
+                } else {
+                    access.add_read(archetype_component_id);
+                }
+            }
+        }
+    }
+
+    fn matches_archetype(&self, archetype: &Archetype) -> bool {
+        self.accesses
+            .iter()
+            .all(|access| archetype.contains(access.id()))
+    }
+
+    fn matches_table(&self, table: &Table) -> bool {
+        self.accesses
+            .iter()
+            .zip(&self.storage_types)
+            .all(|(access, storage_type)| {
+                *storage_type == StorageType::SparseSet || table.has_column(access.id())
+            })
+    }
+}
+
+/// The [`Fetch`] of [`DynamicComponents`].
+pub struct DynamicComponentsFetch {
+    state: *const DynamicComponentsState,
+    dense: bool,
+    entities: *const Entity,
+    entity_table_rows: *const usize,
+    /// The column of each table component in the current table, null for sparse set components
+    columns: Vec<*const Column>,
+    /// The sparse set of each sparse set component, null for table components
+    sparse_sets: Vec<*const ComponentSparseSet>,
+    last_change_tick: u32,
+    change_tick: u32,
+}
+
+impl DynamicComponentsFetch {
+    #[inline]
+    unsafe fn set_columns(&mut self, state: &DynamicComponentsState, table: &Table) {
+        for (column, (access, storage_type)) in self
+            .columns
+            .iter_mut()
+            .zip(state.accesses.iter().zip(&state.storage_types))
+        {
+            if *storage_type == StorageType::Table {
+                *column = table.get_column(access.id()).unwrap();
+            }
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The state this was initialized with must outlive `'s`
+    #[inline]
+    unsafe fn fetch<'w, 's>(&self, entity: Entity, table_row: usize) -> DynamicItem<'w, 's> {
+        let components = self
+            .columns
+            .iter()
+            .zip(&self.sparse_sets)
+            .map(|(column, sparse_set)| {
+                if column.is_null() {
+                    (**sparse_set).get_with_ticks(entity).unwrap()
+                } else {
+                    (
+                        (**column).get_data_unchecked(table_row),
+                        (**column).get_ticks_mut_ptr_unchecked(table_row),
+                    )
+                }
+            })
+            .collect();
+        DynamicItem {
+            accesses: &(*self.state).accesses,
+            components,
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'w, 's> Fetch<'w, 's> for DynamicComponentsFetch {
+    type Item = DynamicItem<'w, 's>;
+    type State = DynamicComponentsState;
+
+    #[inline]
+    fn is_dense(&self) -> bool {
+        self.dense
+    }
+
+    unsafe fn init(
+        world: &World,
+        state: &Self::State,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        let sparse_sets = state
+            .accesses
+            .iter()
+            .zip(&state.storage_types)
+            .map(|(access, storage_type)| match storage_type {
+                StorageType::Table => ptr::null(),
+                StorageType::SparseSet => world.storages().sparse_sets.get(access.id()).unwrap()
+                    as *const ComponentSparseSet,
+            })
+            .collect();
+        Self {
+            state,
+            dense: state
+                .storage_types
+                .iter()
+                .all(|storage_type| *storage_type == StorageType::Table),
+            entities: ptr::null::<Entity>(),
+            entity_table_rows: ptr::null::<usize>(),
+            columns: vec![ptr::null(); state.accesses.len()],
+            sparse_sets,
+            last_change_tick,
+            change_tick,
+        }
+    }
+
+    #[inline]
+    unsafe fn set_archetype(
+        &mut self,
+        state: &Self::State,
+        archetype: &Archetype,
+        tables: &Tables,
+    ) {
+        self.entities = archetype.entities().as_ptr();
+        self.entity_table_rows = archetype.entity_table_rows().as_ptr();
+        self.set_columns(state, &tables[archetype.table_id()]);
+    }
+
+    #[inline]
+    unsafe fn set_table(&mut self, state: &Self::State, table: &Table) {
+        self.entities = table.entities().as_ptr();
+        self.set_columns(state, table);
+    }
+
+    #[inline]
+    unsafe fn archetype_fetch(&mut self, archetype_index: usize) -> Self::Item {
+        let entity = *self.entities.add(archetype_index);
+        let table_row = *self.entity_table_rows.add(archetype_index);
+        self.fetch(entity, table_row)
+    }
+
+    #[inline]
+    unsafe fn table_fetch(&mut self, table_row: usize) -> Self::Item {
+        self.fetch(*self.entities.add(table_row), table_row)
+    }
+}
+
+/// The components of an entity matched by a [`DynamicComponents`] query, in the order of the
+/// query accesses
+pub struct DynamicItem<'w, 's> {
+    accesses: &'s [DynamicAccess],
+    components: Vec<(*mut u8, *mut ComponentTicks)>,
+    last_change_tick: u32,
+    change_tick: u32,
+    marker: PhantomData<&'w World>,
+}
+
+impl<'w, 's> DynamicItem<'w, 's> {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    #[inline]
+    pub fn accesses(&self) -> &'s [DynamicAccess] {
+        self.accesses
+    }
+
+    /// A pointer to the component at `index`
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<*const u8> {
+        self.components
+            .get(index)
+            .map(|(component, _)| *component as *const u8)
+    }
+
+    /// A pointer to the component at `index`, marking it as changed. Returns `None` if the
+    /// component is only read by the query.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<*mut u8> {
+        if !self.accesses.get(index)?.is_write() {
+            return None;
+        }
+        let (component, ticks) = self.components[index];
+        // SAFE: the query has exclusive access to the component
+        unsafe { (*ticks).set_changed(self.change_tick) };
+        Some(component)
+    }
+
+    /// Returns true if the component at `index` was added after the system last ran
+    #[inline]
+    pub fn is_added(&self, index: usize) -> bool {
+        match self.components.get(index) {
+            // SAFE: the query has access to the component
+            Some((_, ticks)) => unsafe {
+                (**ticks).is_added(self.last_change_tick, self.change_tick)
+            },
+            None => false,
+        }
+    }
+
+    /// Returns true if the component at `index` was added or mutably dereferenced after the
+    /// system last ran
+    #[inline]
+    pub fn is_changed(&self, index: usize) -> bool {
+        match self.components.get(index) {
+            // SAFE: the query has access to the component
+            Some((_, ticks)) => unsafe {
+                (**ticks).is_changed(self.last_change_tick, self.change_tick)
+            },
+            None => false,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     archetype::{Archetype, ArchetypeComponentId},
-    component::{Component, ComponentId, ComponentTicks, StorageType},
+    component::{Component, ComponentId, ComponentTicks, Disabled, StorageType},
     entity::Entity,
     query::{Access, Fetch, FetchState, FilteredAccess, WorldQuery},
     storage::{ComponentSparseSet, Table, Tables},
@@ -281,6 +281,112 @@ impl<'w, 's, T: Component> Fetch<'w, 's> for WithoutFetch<T> {
     }
 }
 
+/// Filter that selects entities whether they are [`Disabled`] or not.
+///
+/// Queries skip disabled entities by default, unless they refer to [`Disabled`] in another way.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::system::Query;
+/// # use bevy_ecs::query::IncludeDisabled;
+/// # use bevy_ecs::system::IntoSystem;
+/// #
+/// # struct Name { name: &'static str };
+/// #
+/// fn all_names_system(query: Query<&Name, IncludeDisabled>) {
+///     for name in query.iter() {
+///         println!("{} exists, even if it's disabled", name.name);
+///     }
+/// }
+/// # all_names_system.system();
+/// ```
+pub struct IncludeDisabled;
+
+impl WorldQuery for IncludeDisabled {
+    type Fetch = IncludeDisabledFetch;
+    type State = IncludeDisabledState;
+}
+
+/// The [`Fetch`] of [`IncludeDisabled`].
+pub struct IncludeDisabledFetch;
+
+/// The [`FetchState`] of [`IncludeDisabled`].
+pub struct IncludeDisabledState {
+    component_id: ComponentId,
+}
+
+// SAFETY: no component access or archetype component access
+unsafe impl FetchState for IncludeDisabledState {
+    fn init(world: &mut World) -> Self {
+        Self {
+            component_id: world.components.get_or_insert_id::<Disabled>(),
+        }
+    }
+
+    #[inline]
+    fn update_component_access(&self, access: &mut FilteredAccess<ComponentId>) {
+        access.add_referenced(self.component_id);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(
+        &self,
+        _archetype: &Archetype,
+        _access: &mut Access<ArchetypeComponentId>,
+    ) {
+    }
+
+    fn matches_archetype(&self, _archetype: &Archetype) -> bool {
+        true
+    }
+
+    fn matches_table(&self, _table: &Table) -> bool {
+        true
+    }
+}
+
+impl<'w, 's> Fetch<'w, 's> for IncludeDisabledFetch {
+    type Item = bool;
+    type State = IncludeDisabledState;
+
+    unsafe fn init(
+        _world: &World,
+        _state: &Self::State,
+        _last_change_tick: u32,
+        _change_tick: u32,
+    ) -> Self {
+        Self
+    }
+
+    #[inline]
+    fn is_dense(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    unsafe fn set_table(&mut self, _state: &Self::State, _table: &Table) {}
+
+    #[inline]
+    unsafe fn set_archetype(
+        &mut self,
+        _state: &Self::State,
+        _archetype: &Archetype,
+        _tables: &Tables,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn archetype_fetch(&mut self, _archetype_index: usize) -> bool {
+        true
+    }
+
+    #[inline]
+    unsafe fn table_fetch(&mut self, _table_row: usize) -> bool {
+        true
+    }
+}
+
 /// A filter that tests if any of the given filters apply.
 ///
 /// This is useful for example if a system with multiple components in a query only wants to run
@@ -1,21 +1,32 @@
 mod access;
+mod dynamic;
 mod fetch;
 mod filter;
 mod iter;
 mod state;
+#[cfg(feature = "bevy_reflect")]
+mod trait_query;
 
 pub use access::*;
+pub use dynamic::*;
 pub use fetch::*;
 pub use filter::*;
 pub use iter::*;
 pub use state::*;
+#[cfg(feature = "bevy_reflect")]
+pub use trait_query::*;
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        component::{ComponentDescriptor, StorageType},
+        component::{ComponentDescriptor, Disabled, StorageType},
+        query::DynamicAccess,
         world::World,
     };
+    use std::{
+        alloc::Layout,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
 
     #[derive(Debug, Eq, PartialEq)]
     struct A(usize);
@@ -186,4 +197,95 @@ mod tests {
         let values = world.query::<&B>().iter(&world).collect::<Vec<&B>>();
         assert_eq!(values, vec![&B(3)]);
     }
+
+    #[test]
+    fn dynamic_query() {
+        static DROPPED: AtomicUsize = AtomicUsize::new(0);
+        unsafe fn drop_u32(_value: *mut u8) {
+            DROPPED.fetch_add(1, Ordering::SeqCst);
+        }
+
+        let mut world = World::new();
+        // SAFE: u32 values are Send + Sync, and the drop fn doesn't use the value
+        let (table, sparse) = unsafe {
+            (
+                world
+                    .register_component(ComponentDescriptor::new_dynamic(
+                        "table",
+                        StorageType::Table,
+                        Layout::new::<u32>(),
+                        drop_u32,
+                    ))
+                    .unwrap(),
+                world
+                    .register_component(ComponentDescriptor::new_dynamic(
+                        "sparse",
+                        StorageType::SparseSet,
+                        Layout::new::<u32>(),
+                        drop_u32,
+                    ))
+                    .unwrap(),
+            )
+        };
+
+        let mut entities = Vec::new();
+        for i in 0..3u32 {
+            let mut entity = world.spawn();
+            entity.insert(A(i as usize));
+            let mut value = i;
+            // SAFE: the value is a valid u32, and isn't used afterwards
+            unsafe { entity.insert_by_id(table, &mut value as *mut u32 as *mut u8) };
+            if i > 0 {
+                let mut value = i * 10;
+                // SAFE: the value is a valid u32, and isn't used afterwards
+                unsafe { entity.insert_by_id(sparse, &mut value as *mut u32 as *mut u8) };
+            }
+            entities.push(entity.id());
+        }
+        assert_eq!(world.entity(entities[0]).get_by_id(sparse), None);
+
+        // only reads the dense table component
+        let mut query = world.query_dynamic(&[DynamicAccess::Read(table)]);
+        let mut values = query
+            .iter_mut(&mut world)
+            .map(|item| unsafe { *item.get(0).unwrap().cast::<u32>() })
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![0, 1, 2]);
+
+        // entities disabled after the query was created are skipped as well
+        world.entity_mut(entities[2]).insert(Disabled);
+        assert_eq!(query.iter_mut(&mut world).count(), 2);
+        world.entity_mut(entities[2]).remove::<Disabled>();
+
+        let mut query =
+            world.query_dynamic(&[DynamicAccess::Write(table), DynamicAccess::Read(sparse)]);
+        for mut item in query.iter_mut(&mut world) {
+            assert!(item.get_mut(1).is_none(), "read only component");
+            let sparse_value = unsafe { *item.get(1).unwrap().cast::<u32>() };
+            unsafe { *item.get_mut(0).unwrap().cast::<u32>() += sparse_value };
+        }
+        let values = entities
+            .iter()
+            .map(|entity| unsafe {
+                *world
+                    .entity(*entity)
+                    .get_by_id(table)
+                    .unwrap()
+                    .cast::<u32>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![0, 11, 22]);
+
+        // replaced values are dropped
+        let mut value = 7u32;
+        unsafe {
+            world
+                .entity_mut(entities[0])
+                .insert_by_id(table, &mut value as *mut u32 as *mut u8)
+        };
+        assert_eq!(DROPPED.load(Ordering::SeqCst), 1);
+        world.despawn(entities[1]);
+        assert_eq!(DROPPED.load(Ordering::SeqCst), 3);
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     archetype::{Archetype, ArchetypeComponentId, ArchetypeGeneration, ArchetypeId},
-    component::ComponentId,
+    component::{ComponentId, Disabled},
     entity::Entity,
     query::{
         Access, Fetch, FetchState, FilterFetch, FilteredAccess, QueryCombinationIter, QueryIter,
@@ -11,6 +11,7 @@ use crate::{
 };
 use bevy_tasks::TaskPool;
 use fixedbitset::FixedBitSet;
+use std::any::TypeId;
 use thiserror::Error;
 
 pub struct QueryState<Q: WorldQuery, F: WorldQuery = ()>
@@ -23,6 +24,10 @@ where
     pub(crate) matched_archetypes: FixedBitSet,
     pub(crate) archetype_component_access: Access<ArchetypeComponentId>,
     pub(crate) component_access: FilteredAccess<ComponentId>,
+    /// Whether the query skips [`Disabled`] entities, which it does unless it refers to them
+    skip_disabled: bool,
+    /// The id of [`Disabled`] once it is registered, if the query skips disabled entities
+    disabled_filter: Option<ComponentId>,
     // NOTE: we maintain both a TableId bitset and a vec because iterating the vec is faster
     pub(crate) matched_table_ids: Vec<TableId>,
     // NOTE: we maintain both a ArchetypeId bitset and a vec because iterating the vec is faster
@@ -38,7 +43,18 @@ where
     pub fn new(world: &mut World) -> Self {
         let fetch_state = <Q::State as FetchState>::init(world);
         let filter_state = <F::State as FetchState>::init(world);
+        // registered with the first query rather than with the world, so that the access of
+        // queries skipping disabled entities is disjoint from queries on disabled entities
+        world.components.get_or_insert_id::<Disabled>();
+        Self::new_with_state(world, fetch_state, filter_state)
+    }
 
+    /// Creates a [`QueryState`] from already initialized fetch and filter states, such as the
+    /// [`DynamicComponentsState`](crate::query::DynamicComponentsState) of a query on components
+    /// chosen at runtime.
+    ///
+    /// The component ids of both states must come from `world`.
+    pub fn new_with_state(world: &World, fetch_state: Q::State, filter_state: F::State) -> Self {
         let mut component_access = FilteredAccess::default();
         fetch_state.update_component_access(&mut component_access);
 
@@ -52,6 +68,17 @@ where
         // properly considered in a global "cross-query" context (both within systems and across systems).
         component_access.extend(&filter_component_access);
 
+        // disabled entities are skipped, unless the query refers to them
+        let disabled_id = world.components().get_id(TypeId::of::<Disabled>());
+        let skip_disabled = match disabled_id {
+            Some(id) => !component_access.references(id),
+            None => true,
+        };
+        let disabled_filter = disabled_id.filter(|_| skip_disabled);
+        if let Some(id) = disabled_filter {
+            component_access.add_without(id);
+        }
+
         let mut state = Self {
             world_id: world.id(),
             archetype_generation: ArchetypeGeneration::initial(),
@@ -60,6 +87,8 @@ where
             fetch_state,
             filter_state,
             component_access,
+            skip_disabled,
+            disabled_filter,
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             archetype_component_access: Default::default(),
@@ -83,6 +112,13 @@ where
             panic!("Attempted to use {} with a mismatched World. QueryStates can only be used with the World they were created from.",
                 std::any::type_name::<Self>());
         }
+        if self.skip_disabled && self.disabled_filter.is_none() {
+            // entities can only be disabled once `Disabled` is registered
+            self.disabled_filter = world.components().get_id(TypeId::of::<Disabled>());
+            if let Some(id) = self.disabled_filter {
+                self.component_access.add_without(id);
+            }
+        }
         let archetypes = world.archetypes();
         let new_generation = archetypes.generation();
         let old_generation = std::mem::replace(&mut self.archetype_generation, new_generation);
@@ -94,6 +130,11 @@ where
     }
 
     pub fn new_archetype(&mut self, archetype: &Archetype) {
+        if let Some(disabled_id) = self.disabled_filter {
+            if archetype.contains(disabled_id) {
+                return;
+            }
+        }
         if self.fetch_state.matches_archetype(archetype)
             && self.filter_state.matches_archetype(archetype)
         {
@@ -0,0 +1,664 @@
+use crate::{
+    archetype::{Archetype, ArchetypeComponentId},
+    change_detection::Ticks,
+    component::{ComponentId, ComponentTicks, StorageType},
+    entity::Entity,
+    query::{Access, Fetch, FetchState, FilteredAccess, ReadOnlyFetch, WorldQuery},
+    reflect::ReflectComponent,
+    storage::{Column, ComponentSparseSet, Table, Tables},
+    world::{Mut, World},
+};
+use bevy_reflect::{TraitTypeData, TypeRegistryArc};
+use std::{marker::PhantomData, ptr};
+
+/// A [`WorldQuery`] for the components of an entity that implement a reflected trait, as
+/// `&dyn Trait`.
+///
+/// `R` is the type data generated by `#[reflect_trait]` on the trait. The query matches the
+/// entities with at least one component implementing the trait, and yields a `Vec` of all of
+/// them. Use [`TraitsMut`] to get mutable access.
+///
+/// The components are found in the `TypeRegistryArc` resource of the world when the query is
+/// created: their types must be registered with both [`ReflectComponent`] and `R`. Types
+/// registered later are not part of the query.
+///
+/// Creating the query also registers the component types in the world, as with
+/// [`ReflectComponent::register_component`]. Components stored in sparse sets must be registered
+/// with [`World::register_component`] before the query is created, which includes initializing a
+/// system using it, or they end up stored in tables.
+///
+/// ```
+/// use bevy_ecs::{prelude::*, query::{Traits, TraitsMut}, reflect::ReflectComponent};
+/// use bevy_reflect::{reflect_trait, Reflect, TypeRegistryArc};
+///
+/// #[reflect_trait]
+/// pub trait Damageable {
+///     fn damage(&mut self, amount: u32);
+///     fn health(&self) -> u32;
+/// }
+///
+/// #[derive(Reflect, Default)]
+/// #[reflect(Component, Damageable)]
+/// struct Hull(u32);
+///
+/// impl Damageable for Hull {
+///     fn damage(&mut self, amount: u32) {
+///         self.0 = self.0.saturating_sub(amount);
+///     }
+///     fn health(&self) -> u32 {
+///         self.0
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// let type_registry = TypeRegistryArc::default();
+/// type_registry.write().register::<Hull>();
+/// world.insert_resource(type_registry);
+/// let ship = world.spawn().insert(Hull(10)).id();
+///
+/// for mut damageables in world.query::<TraitsMut<ReflectDamageable>>().iter_mut(&mut world) {
+///     for damageable in damageables.iter_mut() {
+///         damageable.damage(4);
+///     }
+/// }
+/// let mut query = world.query::<Traits<ReflectDamageable>>();
+/// assert_eq!(query.get(&world, ship).unwrap()[0].health(), 6);
+/// ```
+///
+/// Each component is read like with `&T`, so systems using trait queries run in parallel with
+/// other systems whenever their accesses allow it.
+pub struct Traits<R>(PhantomData<R>);
+
+impl<R: TraitTypeData> WorldQuery for Traits<R> {
+    type Fetch = TraitsFetch<R>;
+    type State = TraitsState<R>;
+}
+
+/// A [`WorldQuery`] for the components of an entity that implement a reflected trait, as
+/// [`Mut<dyn Trait>`](Mut).
+///
+/// See [`Traits`]. Each component is written like with `&mut T`, and is marked as changed when
+/// it is mutably dereferenced.
+pub struct TraitsMut<R>(PhantomData<R>);
+
+impl<R: TraitTypeData> WorldQuery for TraitsMut<R> {
+    type Fetch = TraitsMutFetch<R>;
+    type State = TraitsMutState<R>;
+}
+
+/// A component implementing the trait of `R`
+struct TraitComponent<R> {
+    id: ComponentId,
+    storage_type: StorageType,
+    reflect_component: ReflectComponent,
+    type_data: R,
+}
+
+/// The components implementing the trait of `R`, shared by [`TraitsState`] and [`TraitsMutState`]
+struct TraitComponents<R> {
+    components: Vec<TraitComponent<R>>,
+}
+
+impl<R: TraitTypeData> TraitComponents<R> {
+    fn new(world: &mut World) -> Self {
+        let registrations = {
+            let type_registry = world
+                .get_resource::<TypeRegistryArc>()
+                .expect("trait queries need a TypeRegistryArc resource")
+                .read();
+            type_registry
+                .iter()
+                .filter_map(|registration| {
+                    Some((
+                        registration.data::<ReflectComponent>()?.clone(),
+                        registration.data::<R>()?.clone(),
+                    ))
+                })
+                .collect::<Vec<_>>()
+        };
+        let components = registrations
+            .into_iter()
+            .map(|(reflect_component, type_data)| {
+                let id = reflect_component.register_component(world);
+                TraitComponent {
+                    id,
+                    storage_type: world.components().get_info(id).unwrap().storage_type(),
+                    reflect_component,
+                    type_data,
+                }
+            })
+            .collect();
+        Self { components }
+    }
+
+    fn update_archetype_component_access(
+        &self,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+        write: bool,
+    ) {
+        for component in &self.components {
+            if let Some(archetype_component_id) = archetype.get_archetype_component_id(component.id)
+            {
+                if write {
+                    access.add_write(archetype_component_id);
+                } else {
+                    access.add_read(archetype_component_id);
+                }
+            }
+        }
+    }
+
+    fn matches_archetype(&self, archetype: &Archetype) -> bool {
+        self.components
+            .iter()
+            .any(|component| archetype.contains(component.id))
+    }
+
+    fn matches_table(&self, table: &Table) -> bool {
+        self.components
+            .iter()
+            .any(|component| table.has_column(component.id))
+    }
+}
+
+/// The [`FetchState`] of [`Traits`].
+pub struct TraitsState<R>(TraitComponents<R>);
+
+// SAFETY: component access and archetype component access are properly updated to reflect each
+// component being read. Entities aren't required to have all the components, so they are not
+// added as `with` filters.
+unsafe impl<R: TraitTypeData> FetchState for TraitsState<R> {
+    fn init(world: &mut World) -> Self {
+        Self(TraitComponents::new(world))
+    }
+
+    fn update_component_access(&self, access: &mut FilteredAccess<ComponentId>) {
+        for component in &self.0.components {
+            if access.access().has_write(component.id) {
+                panic!("Reading {} conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+                    std::any::type_name::<R>());
+            }
+            access.access_mut().add_read(component.id);
+        }
+    }
+
+    fn update_archetype_component_access(
+        &self,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        self.0
+            .update_archetype_component_access(archetype, access, false);
+    }
+
+    fn matches_archetype(&self, archetype: &Archetype) -> bool {
+        self.0.matches_archetype(archetype)
+    }
+
+    fn matches_table(&self, table: &Table) -> bool {
+        self.0.matches_table(table)
+    }
+}
+
+/// The [`FetchState`] of [`TraitsMut`].
+pub struct TraitsMutState<R>(TraitComponents<R>);
+
+// SAFETY: component access and archetype component access are properly updated to reflect each
+// component being written. Entities aren't required to have all the components, so they are not
+// added as `with` filters.
+unsafe impl<R: TraitTypeData> FetchState for TraitsMutState<R> {
+    fn init(world: &mut World) -> Self {
+        Self(TraitComponents::new(world))
+    }
+
+    fn update_component_access(&self, access: &mut FilteredAccess<ComponentId>) {
+        for component in &self.0.components {
+            if access.access().has_read(component.id) {
+                panic!("Writing {} conflicts with a previous access in this query. Mutable component access must be unique.",
+                    std::any::type_name::<R>());
+            }
+            access.access_mut().add_write(component.id);
+        }
+    }
+
+    fn update_archetype_component_access(
+        &self,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        self.0
+            .update_archetype_component_access(archetype, access, true);
+    }
+
+    fn matches_archetype(&self, archetype: &Archetype) -> bool {
+        self.0.matches_archetype(archetype)
+    }
+
+    fn matches_table(&self, table: &Table) -> bool {
+        self.0.matches_table(table)
+    }
+}
+
+/// Finds the components implementing the trait of `R`, shared by [`TraitsFetch`] and
+/// [`TraitsMutFetch`]
+struct TraitComponentsFetch<R> {
+    state: *const TraitComponents<R>,
+    dense: bool,
+    entities: *const Entity,
+    entity_table_rows: *const usize,
+    /// The index in the state and the column of the components of the current archetype or
+    /// table, the column is null for sparse set components
+    matched: Vec<(usize, *const Column)>,
+    /// The sparse set of each sparse set component, null for table components
+    sparse_sets: Vec<*const ComponentSparseSet>,
+}
+
+impl<R: TraitTypeData> TraitComponentsFetch<R> {
+    unsafe fn new(world: &World, state: &TraitComponents<R>) -> Self {
+        let sparse_sets = state
+            .components
+            .iter()
+            .map(|component| match component.storage_type {
+                StorageType::Table => ptr::null(),
+                // without a sparse set, no archetype contains the component
+                StorageType::SparseSet => world
+                    .storages()
+                    .sparse_sets
+                    .get(component.id)
+                    .map_or(ptr::null(), |sparse_set| sparse_set as *const _),
+            })
+            .collect();
+        Self {
+            state,
+            dense: state
+                .components
+                .iter()
+                .all(|component| component.storage_type == StorageType::Table),
+            entities: ptr::null::<Entity>(),
+            entity_table_rows: ptr::null::<usize>(),
+            matched: Vec::new(),
+            sparse_sets,
+        }
+    }
+
+    #[inline]
+    unsafe fn set_archetype(&mut self, archetype: &Archetype, tables: &Tables) {
+        self.entities = archetype.entities().as_ptr();
+        self.entity_table_rows = archetype.entity_table_rows().as_ptr();
+        let table = &tables[archetype.table_id()];
+        self.matched.clear();
+        for (index, component) in (*self.state).components.iter().enumerate() {
+            if archetype.contains(component.id) {
+                let column = match component.storage_type {
+                    StorageType::Table => table.get_column(component.id).unwrap() as *const _,
+                    StorageType::SparseSet => ptr::null(),
+                };
+                self.matched.push((index, column));
+            }
+        }
+    }
+
+    #[inline]
+    unsafe fn set_table(&mut self, table: &Table) {
+        self.entities = table.entities().as_ptr();
+        self.matched.clear();
+        for (index, component) in (*self.state).components.iter().enumerate() {
+            if let Some(column) = table.get_column(component.id) {
+                self.matched.push((index, column));
+            }
+        }
+    }
+
+    /// Returns the matched components of an entity, with pointers to their value and ticks.
+    ///
+    /// # Safety
+    ///
+    /// The state this was initialized with must outlive `'s`
+    #[inline]
+    unsafe fn fetch<'s>(
+        &self,
+        entity: Entity,
+        table_row: usize,
+    ) -> impl Iterator<Item = (&'s TraitComponent<R>, *mut u8, *mut ComponentTicks)> + '_ {
+        let components = &(*self.state).components;
+        self.matched.iter().map(move |&(index, column)| {
+            let (value, ticks) = if column.is_null() {
+                (*self.sparse_sets[index]).get_with_ticks(entity).unwrap()
+            } else {
+                (
+                    (*column).get_data_unchecked(table_row),
+                    (*column).get_ticks_mut_ptr_unchecked(table_row),
+                )
+            };
+            (&components[index], value, ticks)
+        })
+    }
+
+    #[inline]
+    unsafe fn archetype_entity(&self, archetype_index: usize) -> (Entity, usize) {
+        (
+            *self.entities.add(archetype_index),
+            *self.entity_table_rows.add(archetype_index),
+        )
+    }
+}
+
+/// The [`Fetch`] of [`Traits`].
+pub struct TraitsFetch<R>(TraitComponentsFetch<R>);
+
+/// SAFETY: access is read only
+unsafe impl<R> ReadOnlyFetch for TraitsFetch<R> {}
+
+impl<R: TraitTypeData> TraitsFetch<R> {
+    #[inline]
+    unsafe fn fetch<'w>(&self, entity: Entity, table_row: usize) -> Vec<&'w R::Trait> {
+        self.0
+            .fetch(entity, table_row)
+            .map(|(component, value, _)| {
+                let value = &*component.reflect_component.reflect_ptr(value);
+                component.type_data.get(value).unwrap()
+            })
+            .collect()
+    }
+}
+
+impl<'w, 's, R: TraitTypeData> Fetch<'w, 's> for TraitsFetch<R> {
+    type Item = Vec<&'w R::Trait>;
+    type State = TraitsState<R>;
+
+    #[inline]
+    fn is_dense(&self) -> bool {
+        self.0.dense
+    }
+
+    unsafe fn init(
+        world: &World,
+        state: &Self::State,
+        _last_change_tick: u32,
+        _change_tick: u32,
+    ) -> Self {
+        Self(TraitComponentsFetch::new(world, &state.0))
+    }
+
+    #[inline]
+    unsafe fn set_archetype(
+        &mut self,
+        _state: &Self::State,
+        archetype: &Archetype,
+        tables: &Tables,
+    ) {
+        self.0.set_archetype(archetype, tables);
+    }
+
+    #[inline]
+    unsafe fn set_table(&mut self, _state: &Self::State, table: &Table) {
+        self.0.set_table(table);
+    }
+
+    #[inline]
+    unsafe fn archetype_fetch(&mut self, archetype_index: usize) -> Self::Item {
+        let (entity, table_row) = self.0.archetype_entity(archetype_index);
+        self.fetch(entity, table_row)
+    }
+
+    #[inline]
+    unsafe fn table_fetch(&mut self, table_row: usize) -> Self::Item {
+        self.fetch(*self.0.entities.add(table_row), table_row)
+    }
+}
+
+/// The [`Fetch`] of [`TraitsMut`].
+pub struct TraitsMutFetch<R> {
+    inner: TraitComponentsFetch<R>,
+    last_change_tick: u32,
+    change_tick: u32,
+}
+
+impl<R: TraitTypeData> TraitsMutFetch<R> {
+    #[inline]
+    unsafe fn fetch<'w>(&self, entity: Entity, table_row: usize) -> Vec<Mut<'w, R::Trait>> {
+        self.inner
+            .fetch(entity, table_row)
+            .map(|(component, value, ticks)| {
+                let value = &mut *component.reflect_component.reflect_ptr(value);
+                Mut {
+                    value: component.type_data.get_mut(value).unwrap(),
+                    ticks: Ticks {
+                        component_ticks: &mut *ticks,
+                        change_tick: self.change_tick,
+                        last_change_tick: self.last_change_tick,
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+impl<'w, 's, R: TraitTypeData> Fetch<'w, 's> for TraitsMutFetch<R> {
+    type Item = Vec<Mut<'w, R::Trait>>;
+    type State = TraitsMutState<R>;
+
+    #[inline]
+    fn is_dense(&self) -> bool {
+        self.inner.dense
+    }
+
+    unsafe fn init(
+        world: &World,
+        state: &Self::State,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        Self {
+            inner: TraitComponentsFetch::new(world, &state.0),
+            last_change_tick,
+            change_tick,
+        }
+    }
+
+    #[inline]
+    unsafe fn set_archetype(
+        &mut self,
+        _state: &Self::State,
+        archetype: &Archetype,
+        tables: &Tables,
+    ) {
+        self.inner.set_archetype(archetype, tables);
+    }
+
+    #[inline]
+    unsafe fn set_table(&mut self, _state: &Self::State, table: &Table) {
+        self.inner.set_table(table);
+    }
+
+    #[inline]
+    unsafe fn archetype_fetch(&mut self, archetype_index: usize) -> Self::Item {
+        let (entity, table_row) = self.inner.archetype_entity(archetype_index);
+        self.fetch(entity, table_row)
+    }
+
+    #[inline]
+    unsafe fn table_fetch(&mut self, table_row: usize) -> Self::Item {
+        self.fetch(*self.inner.entities.add(table_row), table_row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::ComponentDescriptor,
+        query::Changed,
+        schedule::{Stage, SystemStage},
+        system::{IntoSystem, Query, System},
+    };
+    use bevy_reflect::{reflect_trait, Reflect};
+    use std::any::TypeId;
+
+    #[reflect_trait]
+    pub trait Damageable {
+        fn damage(&mut self, amount: u32);
+        fn health(&self) -> u32;
+    }
+
+    #[derive(Reflect, Default)]
+    #[reflect(Component, Damageable)]
+    struct Hull(u32);
+
+    #[derive(Reflect, Default)]
+    #[reflect(Component, Damageable)]
+    struct Shield(u32);
+
+    #[derive(Reflect, Default)]
+    #[reflect(Component)]
+    struct Cargo(u32);
+
+    impl Damageable for Hull {
+        fn damage(&mut self, amount: u32) {
+            self.0 = self.0.saturating_sub(amount);
+        }
+
+        fn health(&self) -> u32 {
+            self.0
+        }
+    }
+
+    impl Damageable for Shield {
+        fn damage(&mut self, amount: u32) {
+            self.0 = self.0.saturating_sub(amount * 2);
+        }
+
+        fn health(&self) -> u32 {
+            self.0
+        }
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        world
+            .register_component(ComponentDescriptor::new::<Shield>(StorageType::SparseSet))
+            .unwrap();
+        let type_registry = TypeRegistryArc::default();
+        {
+            let mut type_registry = type_registry.write();
+            type_registry.register::<Hull>();
+            type_registry.register::<Shield>();
+            type_registry.register::<Cargo>();
+        }
+        world.insert_resource(type_registry);
+        world
+    }
+
+    fn total_health(damageables: Vec<&dyn Damageable>) -> u32 {
+        damageables
+            .iter()
+            .map(|damageable| damageable.health())
+            .sum()
+    }
+
+    #[test]
+    fn trait_query() {
+        let mut world = world();
+        let a = world.spawn().insert_bundle((Hull(10), Shield(5))).id();
+        let b = world.spawn().insert_bundle((Hull(20), Cargo(1))).id();
+        world.spawn().insert(Cargo(2));
+
+        let mut query = world.query::<(Entity, Traits<ReflectDamageable>)>();
+        let mut healths = query
+            .iter(&world)
+            .map(|(entity, damageables)| (entity, total_health(damageables)))
+            .collect::<Vec<_>>();
+        healths.sort_by_key(|(entity, _)| *entity);
+        assert_eq!(healths, vec![(a, 15), (b, 20)]);
+
+        for damageables in world
+            .query::<TraitsMut<ReflectDamageable>>()
+            .iter_mut(&mut world)
+        {
+            for mut damageable in damageables {
+                damageable.damage(2);
+            }
+        }
+        assert_eq!(world.get::<Hull>(a).unwrap().0, 8);
+        assert_eq!(world.get::<Shield>(a).unwrap().0, 1);
+        assert_eq!(world.get::<Hull>(b).unwrap().0, 18);
+        assert_eq!(world.get::<Cargo>(b).unwrap().0, 1);
+    }
+
+    #[test]
+    fn trait_query_change_detection() {
+        fn damage_shields(mut query: Query<TraitsMut<ReflectDamageable>>) {
+            for damageables in query.iter_mut() {
+                for mut damageable in damageables {
+                    if damageable.health() > 10 {
+                        damageable.damage(1);
+                    }
+                }
+            }
+        }
+
+        let mut world = world();
+        let a = world.spawn().insert_bundle((Hull(5), Shield(20))).id();
+        world.clear_trackers();
+        let mut stage = SystemStage::single_threaded().with_system(damage_shields.system());
+        stage.run(&mut world);
+
+        let mut hulls = world.query_filtered::<Entity, Changed<Hull>>();
+        assert_eq!(hulls.iter(&world).count(), 0);
+        let mut shields = world.query_filtered::<Entity, Changed<Shield>>();
+        assert_eq!(shields.iter(&world).collect::<Vec<_>>(), vec![a]);
+    }
+
+    #[test]
+    fn trait_query_registers_components() {
+        let mut world = world();
+        world.query::<Traits<ReflectDamageable>>();
+        let storage_type = |world: &World, type_id| {
+            let id = world.components().get_id(type_id).unwrap();
+            world.components().get_info(id).unwrap().storage_type()
+        };
+        // registered before the query
+        assert_eq!(
+            storage_type(&world, TypeId::of::<Shield>()),
+            StorageType::SparseSet
+        );
+        // registered by the query, with the default storage
+        assert_eq!(
+            storage_type(&world, TypeId::of::<Hull>()),
+            StorageType::Table
+        );
+        assert!(world
+            .register_component(ComponentDescriptor::new::<Hull>(StorageType::SparseSet))
+            .is_err());
+    }
+
+    #[test]
+    fn trait_query_access() {
+        fn read_traits(_: Query<Traits<ReflectDamageable>>) {}
+        fn write_hulls(_: Query<&mut Hull>) {}
+        fn write_cargo(_: Query<&mut Cargo>) {}
+        fn write_traits(_: Query<TraitsMut<ReflectDamageable>>) {}
+
+        let mut world = world();
+        let mut systems: Vec<Box<dyn System<In = (), Out = ()>>> = vec![
+            Box::new(read_traits.system()),
+            Box::new(write_hulls.system()),
+            Box::new(write_cargo.system()),
+            Box::new(write_traits.system()),
+        ];
+        for system in systems.iter_mut() {
+            system.initialize(&mut world);
+        }
+        let compatible = |a: usize, b: usize| {
+            systems[a]
+                .component_access()
+                .is_compatible(systems[b].component_access())
+        };
+        assert!(!compatible(0, 1));
+        assert!(compatible(0, 2));
+        assert!(!compatible(0, 3));
+        assert!(!compatible(1, 3));
+        assert!(compatible(2, 3));
+    }
+}
@@ -1,6 +1,6 @@
 pub use crate::change_detection::ReflectMut;
 use crate::{
-    component::Component,
+    component::{Component, ComponentId},
     entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
     world::{FromWorld, World},
 };
@@ -10,9 +10,12 @@ use bevy_reflect::{impl_reflect_value, FromType, Reflect, ReflectDeserialize};
 pub struct ReflectComponent {
     add_component: fn(&mut World, Entity, &dyn Reflect),
     apply_component: fn(&mut World, Entity, &dyn Reflect),
+    remove_component: fn(&mut World, Entity),
     reflect_component: fn(&World, Entity) -> Option<&dyn Reflect>,
     reflect_component_mut: unsafe fn(&World, Entity) -> Option<ReflectMut>,
     copy_component: fn(&World, &mut World, Entity, Entity),
+    register_component: fn(&mut World) -> ComponentId,
+    reflect_ptr: unsafe fn(*mut u8) -> *mut dyn Reflect,
 }
 
 impl ReflectComponent {
@@ -24,6 +27,10 @@ impl ReflectComponent {
         (self.apply_component)(world, entity, component);
     }
 
+    pub fn remove_component(&self, world: &mut World, entity: Entity) {
+        (self.remove_component)(world, entity);
+    }
+
     pub fn reflect_component<'a>(
         &self,
         world: &'a World,
@@ -69,6 +76,25 @@ impl ReflectComponent {
             destination_entity,
         );
     }
+
+    /// Registers the component type in `world` if it isn't already, and returns its id.
+    ///
+    /// A type registered here is stored in tables, the default [`StorageType`]. Components stored
+    /// in sparse sets must be registered with [`World::register_component`] before this is
+    /// called, for example before a [trait query](crate::query::Traits) including them is
+    /// created: registering them afterwards fails with
+    /// [`ComponentsError::ComponentAlreadyExists`](crate::component::ComponentsError).
+    ///
+    /// [`StorageType`]: crate::component::StorageType
+    pub fn register_component(&self, world: &mut World) -> ComponentId {
+        (self.register_component)(world)
+    }
+
+    /// # Safety
+    /// `ptr` must point to a component of the reflected type.
+    pub(crate) unsafe fn reflect_ptr(&self, ptr: *mut u8) -> *mut dyn Reflect {
+        (self.reflect_ptr)(ptr)
+    }
 }
 
 impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectComponent {
@@ -83,6 +109,9 @@ impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectComponent {
                 let mut component = world.get_mut::<C>(entity).unwrap();
                 component.apply(reflected_component);
             },
+            remove_component: |world, entity| {
+                world.entity_mut(entity).remove::<C>();
+            },
             copy_component: |source_world, destination_world, source_entity, destination_entity| {
                 let source_component = source_world.get::<C>(source_entity).unwrap();
                 let mut destination_component = C::from_world(destination_world);
@@ -97,6 +126,8 @@ impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectComponent {
                     .get::<C>()
                     .map(|c| c as &dyn Reflect)
             },
+            register_component: |world| world.components.get_or_insert_id::<C>(),
+            reflect_ptr: |ptr| ptr.cast::<C>() as *mut dyn Reflect,
             reflect_component_mut: |world, entity| unsafe {
                 world
                     .get_entity(entity)?
@@ -110,6 +141,52 @@ impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectComponent {
     }
 }
 
+#[derive(Clone)]
+pub struct ReflectResource {
+    insert_resource: fn(&mut World, &dyn Reflect),
+    apply_resource: fn(&mut World, &dyn Reflect),
+    remove_resource: fn(&mut World),
+    reflect_resource: fn(&World) -> Option<&dyn Reflect>,
+}
+
+impl ReflectResource {
+    pub fn insert_resource(&self, world: &mut World, resource: &dyn Reflect) {
+        (self.insert_resource)(world, resource);
+    }
+
+    pub fn apply_resource(&self, world: &mut World, resource: &dyn Reflect) {
+        (self.apply_resource)(world, resource);
+    }
+
+    pub fn remove_resource(&self, world: &mut World) {
+        (self.remove_resource)(world);
+    }
+
+    pub fn reflect_resource<'a>(&self, world: &'a World) -> Option<&'a dyn Reflect> {
+        (self.reflect_resource)(world)
+    }
+}
+
+impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectResource {
+    fn from_type() -> Self {
+        ReflectResource {
+            insert_resource: |world, reflected_resource| {
+                let mut resource = C::from_world(world);
+                resource.apply(reflected_resource);
+                world.insert_resource(resource);
+            },
+            apply_resource: |world, reflected_resource| {
+                let mut resource = world.get_resource_mut::<C>().unwrap();
+                resource.apply(reflected_resource);
+            },
+            remove_resource: |world| {
+                world.remove_resource::<C>();
+            },
+            reflect_resource: |world| world.get_resource::<C>().map(|r| r as &dyn Reflect),
+        }
+    }
+}
+
 impl_reflect_value!(Entity(Hash, PartialEq, Serialize, Deserialize));
 
 #[derive(Clone)]
@@ -0,0 +1,330 @@
+use crate::{
+    schedule::{
+        find_ambiguities, graph_utils, BoxedRunCriteria, GraphNode, RunCriteriaContainer, Schedule,
+        Stage, SystemContainer, SystemStage,
+    },
+    world::World,
+};
+use std::{fmt::Debug, hash::Hash};
+
+/// Formats `schedule` in the [Graphviz](https://graphviz.org) DOT language, to debug the order of
+/// its stages and systems.
+///
+/// Stages are drawn as clusters in execution order, each containing its run criteria and its
+/// systems grouped by when they run in the stage. Solid edges go from a system to the systems
+/// ordered after it, labelled with the labels that order them, and dotted edges go from run
+/// criteria to what they control. Red dashed edges link systems with an ambiguous execution
+/// order, as reported with [`ReportExecutionOrderAmbiguities`](super::ReportExecutionOrderAmbiguities),
+/// which are only known for stages that already ran in `world`.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, schedule::schedule_to_dot};
+/// # fn first() {}
+/// # fn second() {}
+/// let mut schedule = Schedule::default();
+/// schedule.add_stage(
+///     "update",
+///     SystemStage::parallel()
+///         .with_system(first.system().label("first"))
+///         .with_system(second.system().after("first")),
+/// );
+///
+/// let dot = schedule_to_dot(&schedule, &World::default());
+/// assert!(dot.starts_with("digraph schedule {"));
+/// ```
+pub fn schedule_to_dot(schedule: &Schedule, world: &World) -> String {
+    let mut writer = DotWriter {
+        dot: String::new(),
+        indent: 0,
+        next_cluster: 0,
+        world,
+    };
+    writer.line("digraph schedule {");
+    writer.indent += 1;
+    writer.line("compound=true;");
+    writer.line("node [shape=box];");
+    writer.schedule(schedule);
+    writer.indent -= 1;
+    writer.line("}");
+    writer.dot
+}
+
+/// Escapes `string` to be used in a quoted DOT identifier
+fn escape(string: &str) -> String {
+    string.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+struct DotWriter<'w> {
+    dot: String,
+    indent: usize,
+    next_cluster: usize,
+    world: &'w World,
+}
+
+impl<'w> DotWriter<'w> {
+    fn line(&mut self, line: impl AsRef<str>) {
+        for _ in 0..self.indent {
+            self.dot.push_str("    ");
+        }
+        self.dot.push_str(line.as_ref());
+        self.dot.push('\n');
+    }
+
+    /// Writes a cluster with an invisible anchor node, used to draw edges between clusters.
+    /// Returns the id of the cluster.
+    fn cluster(&mut self, label: &str, body: impl FnOnce(&mut Self, &str)) -> String {
+        let id = format!("cluster_{}", self.next_cluster);
+        self.next_cluster += 1;
+        self.line(format!("subgraph {} {{", id));
+        self.indent += 1;
+        self.line(format!("label=\"{}\";", escape(label)));
+        self.line(format!(
+            "\"{}_anchor\" [shape=point, style=invis, label=\"\"];",
+            id
+        ));
+        body(self, &id);
+        self.indent -= 1;
+        self.line("}");
+        id
+    }
+
+    /// Links consecutive clusters, to show the order in which they run
+    fn cluster_order(&mut self, clusters: &[String]) {
+        for pair in clusters.windows(2) {
+            self.line(format!(
+                "\"{0}_anchor\" -> \"{1}_anchor\" [ltail={0}, lhead={1}, style=bold];",
+                pair[0], pair[1]
+            ));
+        }
+    }
+
+    fn run_criteria(&mut self, id: &str, run_criteria: &BoxedRunCriteria) -> Option<String> {
+        let name = run_criteria.name()?;
+        let node = format!("{}_run_criteria", id);
+        self.line(format!(
+            "\"{}\" [label=\"{}\", shape=diamond];",
+            node,
+            escape(&name)
+        ));
+        Some(node)
+    }
+
+    fn schedule(&mut self, schedule: &Schedule) {
+        let mut stages = Vec::new();
+        for label in schedule.stage_order.iter() {
+            let stage = &schedule.stages[label];
+            let stage_label = format!("{:?}", label);
+            stages.push(self.cluster(&stage_label, |writer, id| writer.stage(id, &**stage)));
+        }
+        self.cluster_order(&stages);
+    }
+
+    fn stage(&mut self, id: &str, stage: &dyn Stage) {
+        if let Some(system_stage) = stage.downcast_ref::<SystemStage>() {
+            if let Some(node) = self.run_criteria(id, system_stage.stage_run_criteria()) {
+                self.line(format!(
+                    "\"{}\" -> \"{}_anchor\" [lhead={}, style=dotted];",
+                    node, id, id
+                ));
+            }
+            self.system_stage(id, system_stage);
+        } else if let Some(schedule) = stage.downcast_ref::<Schedule>() {
+            if let Some(node) = self.run_criteria(id, &schedule.run_criteria) {
+                self.line(format!(
+                    "\"{}\" -> \"{}_anchor\" [lhead={}, style=dotted];",
+                    node, id, id
+                ));
+            }
+            self.schedule(schedule);
+        } else {
+            self.line(format!(
+                "\"{}_custom\" [label=\"custom stage\", shape=plaintext];",
+                id
+            ));
+        }
+    }
+
+    fn system_stage(&mut self, id: &str, stage: &SystemStage) {
+        let run_criteria = stage.system_run_criteria();
+        for (index, criteria) in run_criteria.iter().enumerate() {
+            self.line(format!(
+                "\"{}_rc{}\" [label=\"{}\", shape=diamond];",
+                id,
+                index,
+                escape(&criteria.name())
+            ));
+        }
+        self.dependencies(id, "rc", run_criteria);
+
+        let fresh = stage.has_fresh_orders();
+        let mut groups = Vec::new();
+        if let Some(group) = self.systems(
+            id,
+            "exclusive at start",
+            stage.exclusive_at_start_systems(),
+            run_criteria,
+            fresh,
+        ) {
+            groups.push(group);
+        }
+        if let Some(group) = self.systems(
+            id,
+            "parallel",
+            stage.parallel_systems(),
+            run_criteria,
+            fresh,
+        ) {
+            groups.push(group);
+        }
+        if let Some(group) = self.systems(
+            id,
+            "exclusive before commands",
+            stage.exclusive_before_commands_systems(),
+            run_criteria,
+            fresh,
+        ) {
+            groups.push(group);
+        }
+        if let Some(group) = self.systems(
+            id,
+            "exclusive at end",
+            stage.exclusive_at_end_systems(),
+            run_criteria,
+            fresh,
+        ) {
+            groups.push(group);
+        }
+        self.cluster_order(&groups);
+    }
+
+    /// Writes a group of systems of a stage, returning the id of its cluster if it isn't empty
+    fn systems(
+        &mut self,
+        stage_id: &str,
+        label: &str,
+        systems: &[impl SystemContainer],
+        run_criteria: &[RunCriteriaContainer],
+        fresh: bool,
+    ) -> Option<String> {
+        if systems.is_empty() {
+            return None;
+        }
+        Some(self.cluster(label, |writer, id| {
+            for (index, system) in systems.iter().enumerate() {
+                writer.line(format!(
+                    "\"{}_{}\" [label=\"{}\"];",
+                    id,
+                    index,
+                    escape(&system.name())
+                ));
+                // run criteria are only resolved from their label when the stage runs
+                let criteria = system.run_criteria().or_else(|| {
+                    let label = system.run_criteria_label()?;
+                    run_criteria
+                        .iter()
+                        .position(|criteria| criteria.label.as_ref() == Some(label))
+                });
+                if let Some(criteria) = criteria {
+                    writer.line(format!(
+                        "\"{}_rc{}\" -> \"{}_{}\" [style=dotted];",
+                        stage_id, criteria, id, index
+                    ));
+                }
+            }
+            writer.dependencies(id, "", systems);
+            if fresh {
+                for (a, b, conflicts) in find_ambiguities(systems) {
+                    let conflicts = conflicts
+                        .iter()
+                        .filter_map(|id| writer.world.components().get_info(*id))
+                        .map(|info| info.name())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    writer.line(format!(
+                        "\"{0}_{1}\" -> \"{0}_{2}\" [dir=none, constraint=false, style=dashed, color=red, fontcolor=red, label=\"{3}\"];",
+                        id,
+                        a,
+                        b,
+                        escape(&conflicts)
+                    ));
+                }
+            }
+        }))
+    }
+
+    /// Writes the ordering edges between the nodes named `{id}_{prefix}{index}`
+    fn dependencies<Node>(&mut self, id: &str, prefix: &str, nodes: &[Node])
+    where
+        Node: GraphNode,
+        Node::Label: Debug + Clone + Eq + Hash,
+    {
+        let graph = graph_utils::build_dependency_graph(nodes);
+        let mut edges = graph
+            .iter()
+            .flat_map(|(index, dependencies)| {
+                dependencies
+                    .iter()
+                    .map(move |(dependency, labels)| (*dependency, *index, labels))
+            })
+            .collect::<Vec<_>>();
+        edges.sort_unstable_by_key(|(dependency, index, _)| (*dependency, *index));
+        for (dependency, index, labels) in edges {
+            let mut labels = labels
+                .iter()
+                .map(|label| format!("{:?}", label))
+                .collect::<Vec<_>>();
+            labels.sort();
+            self.line(format!(
+                "\"{0}_{1}{2}\" -> \"{0}_{1}{3}\" [label=\"{4}\"];",
+                id,
+                prefix,
+                dependency,
+                index,
+                escape(&labels.join(", "))
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        schedule::{schedule_to_dot, ReportExecutionOrderAmbiguities},
+    };
+
+    fn first(_: ResMut<u32>) {}
+    fn second(_: ResMut<u32>) {}
+    fn third(_: ResMut<u32>) {}
+
+    #[test]
+    fn schedule_dot() {
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        world.insert_resource(ReportExecutionOrderAmbiguities);
+        let mut schedule = Schedule::default();
+        schedule.add_stage(
+            "update",
+            SystemStage::parallel()
+                .with_system(first.system().label("first"))
+                .with_system(second.system().after("first"))
+                .with_system(third.system()),
+        );
+        schedule.add_stage("last", SystemStage::single_threaded());
+
+        let dot = schedule_to_dot(&schedule, &world);
+        assert!(dot.contains("subgraph cluster_0 {"));
+        assert!(dot.contains("label=\"\\\"update\\\"\";"));
+        assert!(dot.contains("[label=\"\\\"first\\\"\"];"));
+        assert!(dot.contains(
+            "\"cluster_0_anchor\" -> \"cluster_2_anchor\" [ltail=cluster_0, lhead=cluster_2"
+        ));
+        // ambiguities are only known once the stage ran
+        assert!(!dot.contains("style=dashed, color=red"));
+
+        schedule.run(&mut world);
+        let dot = schedule_to_dot(&schedule, &world);
+        assert_eq!(dot.matches("style=dashed, color=red").count(), 2);
+        assert!(dot.contains("label=\"u32\"];"));
+    }
+}
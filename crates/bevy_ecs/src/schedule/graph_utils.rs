@@ -1,6 +1,6 @@
 use bevy_utils::{tracing::warn, HashMap, HashSet};
 use fixedbitset::FixedBitSet;
-use std::{borrow::Cow, fmt::Debug, hash::Hash};
+use std::{borrow::Cow, cmp::Reverse, collections::BinaryHeap, fmt::Debug, hash::Hash};
 
 pub enum DependencyGraphError<Labels> {
     GraphCycles(Vec<(usize, Labels)>),
@@ -124,3 +124,57 @@ pub fn topological_order<Labels: Clone>(
     }
     Ok(sorted)
 }
+
+/// Generates a topological order for the given graph that doesn't depend on the order the nodes
+/// were inserted in. Whenever several nodes could come next, the one with the smallest labels
+/// (sorted and compared by their `Debug` representation) goes first, then the one with the
+/// smallest name; nodes with identical labels and names keep their insertion order.
+pub fn deterministic_topological_order<Node>(
+    nodes: &[Node],
+    graph: &HashMap<usize, HashMap<usize, HashSet<Node::Label>>>,
+) -> Result<Vec<usize>, DependencyGraphError<HashSet<Node::Label>>>
+where
+    Node: GraphNode,
+    Node::Label: Debug + Clone,
+{
+    let keys = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| {
+            let mut labels = node
+                .labels()
+                .iter()
+                .map(|label| format!("{:?}", label))
+                .collect::<Vec<_>>();
+            labels.sort();
+            (labels, node.name(), index)
+        })
+        .collect::<Vec<_>>();
+    let mut dependencies_left = vec![0; nodes.len()];
+    let mut dependants = vec![Vec::new(); nodes.len()];
+    for (&node, dependencies) in graph {
+        dependencies_left[node] = dependencies.len();
+        for &dependency in dependencies.keys() {
+            dependants[dependency].push(node);
+        }
+    }
+    let mut ready = (0..nodes.len())
+        .filter(|&node| dependencies_left[node] == 0)
+        .map(|node| Reverse(&keys[node]))
+        .collect::<BinaryHeap<_>>();
+    let mut sorted = Vec::with_capacity(nodes.len());
+    while let Some(Reverse(&(_, _, node))) = ready.pop() {
+        sorted.push(node);
+        for &dependant in &dependants[node] {
+            dependencies_left[dependant] -= 1;
+            if dependencies_left[dependant] == 0 {
+                ready.push(Reverse(&keys[dependant]));
+            }
+        }
+    }
+    if sorted.len() < nodes.len() {
+        // The remaining nodes are in or after a cycle, which the regular sort finds and reports.
+        return Err(topological_order(graph).expect_err("dependency graph should have a cycle"));
+    }
+    Ok(sorted)
+}
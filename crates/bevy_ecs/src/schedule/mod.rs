@@ -1,3 +1,4 @@
+mod dot;
 mod executor;
 mod executor_parallel;
 pub mod graph_utils;
@@ -9,6 +10,7 @@ mod system_container;
 mod system_descriptor;
 mod system_set;
 
+pub use dot::*;
 pub use executor::*;
 pub use executor_parallel::*;
 pub use graph_utils::GraphNode;
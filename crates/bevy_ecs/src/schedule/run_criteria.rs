@@ -3,10 +3,10 @@ use crate::{
     component::ComponentId,
     query::Access,
     schedule::{BoxedRunCriteriaLabel, GraphNode, RunCriteriaLabel},
-    system::{BoxedSystem, IntoSystem, System, SystemId},
+    system::{BoxedSystem, In, IntoSystem, System, SystemId},
     world::World,
 };
-use std::borrow::Cow;
+use std::{borrow::Cow, marker::PhantomData};
 
 /// Determines whether a system should be executed or not, and how many times it should be ran each
 /// time the stage is executed.
@@ -61,6 +61,10 @@ impl Default for BoxedRunCriteria {
 }
 
 impl BoxedRunCriteria {
+    pub fn name(&self) -> Option<Cow<'static, str>> {
+        self.criteria_system.as_ref().map(|system| system.name())
+    }
+
     pub fn set(&mut self, criteria_system: BoxedSystem<(), ShouldRun>) {
         self.criteria_system = Some(criteria_system);
         self.initialized = false;
@@ -455,3 +459,342 @@ impl System for RunOnce {
 
     fn check_change_tick(&mut self, _change_tick: u32) {}
 }
+
+/// Combines run criteria with boolean logic.
+///
+/// Any run criteria can be combined: systems returning [`ShouldRun`], descriptors such as
+/// [`State::on_update`](super::State::on_update), and labels of other run criteria, whose result
+/// is then used as is.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, schedule::ShouldRun};
+/// # #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// # enum AppState { InGame }
+/// struct Paused(bool);
+///
+/// fn paused(paused: Res<Paused>) -> ShouldRun {
+///     if paused.0 {
+///         ShouldRun::Yes
+///     } else {
+///         ShouldRun::No
+///     }
+/// }
+///
+/// # fn movement() {}
+/// SystemSet::new()
+///     .with_run_criteria(State::on_update(AppState::InGame).and(paused.not()))
+///     .with_system(movement.system());
+/// ```
+///
+/// Criteria returning [`ShouldRun::YesAndCheckAgain`] or [`ShouldRun::NoAndCheckAgain`] are
+/// checked again as long as they ask to, and the combination is checked again as long as one of
+/// them is. Criteria returning [`ShouldRun::Yes`] or [`ShouldRun::No`] keep that answer until
+/// the next time the stage runs, except that criteria ending their loop of checks with
+/// [`ShouldRun::No`] keep their previous answer while the other criteria loop. This lets a
+/// [`State`](super::State) stay active while a fixed timestep loops over its steps, for example.
+///
+/// Like `&&` and `||`, the first criteria is checked first and the second one isn't checked when
+/// the first one decides the result for the whole stage run. The labels of the combined criteria
+/// are ignored: label the combination instead.
+///
+/// # Panics
+///
+/// Combining criteria piped from different run criteria panics, as a combination can only be
+/// piped from a single one.
+pub trait RunCriteriaCombinators<Marker>: IntoRunCriteria<Marker> + Sized {
+    /// Runs when both criteria want to run
+    fn and<OtherMarker>(self, other: impl IntoRunCriteria<OtherMarker>) -> RunCriteriaDescriptor {
+        combine(
+            CriteriaOperator::And,
+            vec![into_descriptor(self.into()), into_descriptor(other.into())],
+        )
+    }
+
+    /// Runs when at least one of the criteria wants to run
+    fn or<OtherMarker>(self, other: impl IntoRunCriteria<OtherMarker>) -> RunCriteriaDescriptor {
+        combine(
+            CriteriaOperator::Or,
+            vec![into_descriptor(self.into()), into_descriptor(other.into())],
+        )
+    }
+
+    /// Runs when the criteria doesn't want to run
+    fn not(self) -> RunCriteriaDescriptor {
+        combine(CriteriaOperator::Not, vec![into_descriptor(self.into())])
+    }
+}
+
+impl<Marker, T: IntoRunCriteria<Marker>> RunCriteriaCombinators<Marker> for T {}
+
+fn into_descriptor(criteria: RunCriteriaDescriptorOrLabel) -> RunCriteriaDescriptor {
+    match criteria {
+        RunCriteriaDescriptorOrLabel::Descriptor(descriptor) => descriptor,
+        RunCriteriaDescriptorOrLabel::Label(label) => {
+            label.pipe((|In(should_run): In<ShouldRun>| should_run).system())
+        }
+    }
+}
+
+fn combine(
+    operator: CriteriaOperator,
+    descriptors: Vec<RunCriteriaDescriptor>,
+) -> RunCriteriaDescriptor {
+    let mut input = None;
+    let mut before = Vec::new();
+    let mut after = Vec::new();
+    let mut children = Vec::new();
+    for descriptor in descriptors {
+        let mut descriptor_after = descriptor.after.into_iter();
+        if let RunCriteriaSystem::Piped(_) = descriptor.system {
+            // piped criteria get their input from the first criteria they are after
+            let label = descriptor_after.next().unwrap();
+            match &input {
+                Some(input) if *input != label => panic!(
+                    "Cannot combine run criteria piped from {:?} and {:?}.",
+                    input, label
+                ),
+                Some(_) => (),
+                None => input = Some(label),
+            }
+        }
+        before.extend(descriptor.before);
+        after.extend(descriptor_after);
+        children.push(CombinedChild {
+            system: descriptor.system,
+            should_run: false,
+            check_again: false,
+        });
+    }
+    let system = match input {
+        Some(input) => {
+            after.insert(0, input);
+            RunCriteriaSystem::Piped(Box::new(CombinedRunCriteria::<ShouldRun>::new(
+                operator, children,
+            )))
+        }
+        None => {
+            RunCriteriaSystem::Single(Box::new(CombinedRunCriteria::<()>::new(operator, children)))
+        }
+    };
+    RunCriteriaDescriptor {
+        system,
+        label: None,
+        duplicate_label_strategy: DuplicateLabelStrategy::Panic,
+        before,
+        after,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum CriteriaOperator {
+    And,
+    Or,
+    Not,
+}
+
+/// The input of [`CombinedRunCriteria`], which is piped when one of its children is
+trait CriteriaInput: Send + Sync + 'static {
+    fn piped(self) -> Option<ShouldRun>;
+}
+
+impl CriteriaInput for () {
+    fn piped(self) -> Option<ShouldRun> {
+        None
+    }
+}
+
+impl CriteriaInput for ShouldRun {
+    fn piped(self) -> Option<ShouldRun> {
+        Some(self)
+    }
+}
+
+struct CombinedChild {
+    system: RunCriteriaSystem,
+    /// Whether the child wants to run, kept while other children are checked again
+    should_run: bool,
+    check_again: bool,
+}
+
+impl CombinedChild {
+    /// # Safety
+    ///
+    /// See [`System::run_unsafe`]
+    unsafe fn run(&mut self, input: Option<ShouldRun>, world: &World) -> ShouldRun {
+        match &mut self.system {
+            RunCriteriaSystem::Single(system) => system.run_unsafe((), world),
+            RunCriteriaSystem::Piped(system) => system.run_unsafe(input.unwrap(), world),
+        }
+    }
+}
+
+struct CombinedRunCriteria<In> {
+    operator: CriteriaOperator,
+    children: Vec<CombinedChild>,
+    /// Whether the last result asked to be checked again
+    checking_again: bool,
+    name: Cow<'static, str>,
+    id: SystemId,
+    component_access: Access<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+    marker: PhantomData<fn(In)>,
+}
+
+impl<In> CombinedRunCriteria<In> {
+    fn new(operator: CriteriaOperator, children: Vec<CombinedChild>) -> Self {
+        let names = children
+            .iter()
+            .map(|child| match &child.system {
+                RunCriteriaSystem::Single(system) => system.name(),
+                RunCriteriaSystem::Piped(system) => system.name(),
+            })
+            .collect::<Vec<_>>();
+        let name = match operator {
+            CriteriaOperator::And => format!("({} && {})", names[0], names[1]),
+            CriteriaOperator::Or => format!("({} || {})", names[0], names[1]),
+            CriteriaOperator::Not => format!("!{}", names[0]),
+        };
+        Self {
+            operator,
+            children,
+            checking_again: false,
+            name: name.into(),
+            id: SystemId::new(),
+            component_access: Default::default(),
+            archetype_component_access: Default::default(),
+            marker: PhantomData,
+        }
+    }
+
+    fn should_run(&self) -> bool {
+        match self.operator {
+            CriteriaOperator::And => self.children.iter().all(|child| child.should_run),
+            CriteriaOperator::Or => self.children.iter().any(|child| child.should_run),
+            CriteriaOperator::Not => !self.children[0].should_run,
+        }
+    }
+}
+
+impl<In: CriteriaInput> System for CombinedRunCriteria<In> {
+    type In = In;
+    type Out = ShouldRun;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    fn id(&self) -> SystemId {
+        self.id
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype) {
+        for child in self.children.iter_mut() {
+            match &mut child.system {
+                RunCriteriaSystem::Single(system) => {
+                    system.new_archetype(archetype);
+                    self.archetype_component_access
+                        .extend(system.archetype_component_access());
+                }
+                RunCriteriaSystem::Piped(system) => {
+                    system.new_archetype(archetype);
+                    self.archetype_component_access
+                        .extend(system.archetype_component_access());
+                }
+            }
+        }
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        &self.component_access
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    fn is_send(&self) -> bool {
+        self.children.iter().all(|child| match &child.system {
+            RunCriteriaSystem::Single(system) => system.is_send(),
+            RunCriteriaSystem::Piped(system) => system.is_send(),
+        })
+    }
+
+    unsafe fn run_unsafe(&mut self, input: In, world: &World) -> ShouldRun {
+        let input = input.piped();
+        let checking_again = self.checking_again;
+        // the children ending their loop of checks with `No` during this check
+        let mut ended = Vec::new();
+        for (i, child) in self.children.iter_mut().enumerate() {
+            if checking_again && !child.check_again {
+                continue;
+            }
+            let should_run = child.run(input, world);
+            child.check_again = matches!(
+                should_run,
+                ShouldRun::YesAndCheckAgain | ShouldRun::NoAndCheckAgain
+            );
+            if checking_again && should_run == ShouldRun::No {
+                // keep the previous answer while the other children loop, like a state staying
+                // active during the steps of a fixed timestep
+                ended.push(i);
+            } else {
+                child.should_run =
+                    matches!(should_run, ShouldRun::Yes | ShouldRun::YesAndCheckAgain);
+            }
+            if !checking_again && !child.check_again {
+                // short-circuit when the child decides the result for this stage run
+                match (self.operator, child.should_run) {
+                    (CriteriaOperator::And, false) => return ShouldRun::No,
+                    (CriteriaOperator::Or, true) => return ShouldRun::Yes,
+                    _ => (),
+                }
+            }
+        }
+        self.checking_again = self.children.iter().any(|child| child.check_again);
+        if !self.checking_again {
+            // the loop ends with the last answer of every child, including a final `No`
+            for i in ended {
+                self.children[i].should_run = false;
+            }
+        }
+        match (self.checking_again, self.should_run()) {
+            (true, true) => ShouldRun::YesAndCheckAgain,
+            (true, false) => ShouldRun::NoAndCheckAgain,
+            (false, true) => ShouldRun::Yes,
+            (false, false) => ShouldRun::No,
+        }
+    }
+
+    fn apply_buffers(&mut self, world: &mut World) {
+        for child in self.children.iter_mut() {
+            match &mut child.system {
+                RunCriteriaSystem::Single(system) => system.apply_buffers(world),
+                RunCriteriaSystem::Piped(system) => system.apply_buffers(world),
+            }
+        }
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        for child in self.children.iter_mut() {
+            match &mut child.system {
+                RunCriteriaSystem::Single(system) => {
+                    system.initialize(world);
+                    self.component_access.extend(system.component_access());
+                }
+                RunCriteriaSystem::Piped(system) => {
+                    system.initialize(world);
+                    self.component_access.extend(system.component_access());
+                }
+            }
+        }
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        for child in self.children.iter_mut() {
+            match &mut child.system {
+                RunCriteriaSystem::Single(system) => system.check_change_tick(change_tick),
+                RunCriteriaSystem::Piped(system) => system.check_change_tick(change_tick),
+            }
+        }
+    }
+}
@@ -48,7 +48,8 @@ impl_downcast!(Stage);
 pub struct ReportExecutionOrderAmbiguities;
 
 /// Stores and executes systems. Execution order is not defined unless explicitly specified;
-/// see `SystemDescriptor` documentation.
+/// see `SystemDescriptor` documentation, or use [`SystemStage::deterministic`] for a reproducible
+/// order.
 pub struct SystemStage {
     /// The WorldId this stage was last run on.
     world_id: Option<WorldId>,
@@ -71,6 +72,9 @@ pub struct SystemStage {
     systems_modified: bool,
     /// Determines if the stage's executor was changed.
     executor_modified: bool,
+    /// Determines if systems and run criteria are sorted in an order that doesn't depend on
+    /// insertion order.
+    deterministic: bool,
     /// Newly inserted run criteria that will be initialized at the next opportunity.
     uninitialized_run_criteria: Vec<(usize, DuplicateLabelStrategy)>,
     /// Newly inserted systems that will be initialized at the next opportunity.
@@ -99,6 +103,7 @@ impl SystemStage {
             parallel: vec![],
             systems_modified: true,
             executor_modified: true,
+            deterministic: false,
             uninitialized_parallel: vec![],
             uninitialized_at_start: vec![],
             uninitialized_before_commands: vec![],
@@ -119,6 +124,25 @@ impl SystemStage {
         Self::new(Box::new(ParallelExecutor::default()))
     }
 
+    /// Creates a single-threaded stage that runs its systems in a reproducible order, for
+    /// lockstep networking or replays.
+    ///
+    /// Systems are sorted topologically, and systems without an ordering constraint between
+    /// them are ordered by their labels, then by their names, regardless of the order they were
+    /// added in. Only systems with identical labels and names keep their insertion order. The
+    /// command buffers of parallel systems are applied in the same order, and run criteria are
+    /// sorted the same way.
+    ///
+    /// In debug builds, the stage warns about systems with ambiguous execution order, as they
+    /// could behave differently in a parallel stage. Replacing the executor with
+    /// [`SystemStage::set_executor`] keeps the order, but a parallel executor may still run
+    /// systems out of it.
+    pub fn deterministic() -> Self {
+        let mut stage = Self::single_threaded();
+        stage.deterministic = true;
+        stage
+    }
+
     pub fn get_executor<T: ParallelSystemExecutor>(&self) -> Option<&T> {
         self.executor.downcast_ref()
     }
@@ -232,6 +256,20 @@ impl SystemStage {
         &self.exclusive_before_commands
     }
 
+    pub(super) fn stage_run_criteria(&self) -> &BoxedRunCriteria {
+        &self.stage_run_criteria
+    }
+
+    pub(super) fn system_run_criteria(&self) -> &[RunCriteriaContainer] {
+        &self.run_criteria
+    }
+
+    /// Returns true if the systems are sorted and their dependencies resolved, which happens when
+    /// the stage runs.
+    pub(super) fn has_fresh_orders(&self) -> bool {
+        !self.systems_modified
+    }
+
     pub fn with_system_set(mut self, system_set: SystemSet) -> Self {
         self.add_system_set(system_set);
         self
@@ -442,28 +480,41 @@ impl SystemStage {
                 }
             }
         }
+        let deterministic = self.deterministic;
         let run_criteria_labels = unwrap_dependency_cycle_error(
             self.process_run_criteria(),
             &self.run_criteria,
             "run criteria",
         );
         unwrap_dependency_cycle_error(
-            process_systems(&mut self.parallel, &run_criteria_labels),
+            process_systems(&mut self.parallel, &run_criteria_labels, deterministic),
             &self.parallel,
             "parallel systems",
         );
         unwrap_dependency_cycle_error(
-            process_systems(&mut self.exclusive_at_start, &run_criteria_labels),
+            process_systems(
+                &mut self.exclusive_at_start,
+                &run_criteria_labels,
+                deterministic,
+            ),
             &self.exclusive_at_start,
             "exclusive systems at start of stage",
         );
         unwrap_dependency_cycle_error(
-            process_systems(&mut self.exclusive_before_commands, &run_criteria_labels),
+            process_systems(
+                &mut self.exclusive_before_commands,
+                &run_criteria_labels,
+                deterministic,
+            ),
             &self.exclusive_before_commands,
             "exclusive systems before commands of stage",
         );
         unwrap_dependency_cycle_error(
-            process_systems(&mut self.exclusive_at_end, &run_criteria_labels),
+            process_systems(
+                &mut self.exclusive_at_end,
+                &run_criteria_labels,
+                deterministic,
+            ),
             &self.exclusive_at_end,
             "exclusive systems at end of stage",
         );
@@ -471,6 +522,31 @@ impl SystemStage {
 
     /// Logs execution order ambiguities between systems. System orders must be fresh.
     fn report_ambiguities(&self, world: &World) {
+        if let Some(report) = self.ambiguities_report(
+            "Execution order ambiguities detected, you might want to \
+            add an explicit dependency relation between some of these systems:",
+            world,
+        ) {
+            info!("{}", report);
+        }
+    }
+
+    /// Warns about systems of a deterministic stage that would run in an arbitrary order in a
+    /// parallel stage. System orders must be fresh.
+    #[cfg(debug_assertions)]
+    fn check_deterministic_ambiguities(&self, world: &World) {
+        if let Some(report) = self.ambiguities_report(
+            "Deterministic stage has execution order ambiguities, these systems could \
+            behave differently in a parallel stage unless they have an explicit dependency:",
+            world,
+        ) {
+            bevy_utils::tracing::warn!("{}", report);
+        }
+    }
+
+    /// Describes execution order ambiguities between systems, if there are any, under the given
+    /// heading. System orders must be fresh.
+    fn ambiguities_report(&self, heading: &str, world: &World) -> Option<String> {
         debug_assert!(!self.systems_modified);
         use std::fmt::Write;
         fn write_display_names_of_pairs(
@@ -505,9 +581,8 @@ impl SystemStage {
             && before_commands.is_empty()
             && at_end.is_empty())
         {
-            let mut string = "Execution order ambiguities detected, you might want to \
-                    add an explicit dependency relation between some of these systems:\n"
-                .to_owned();
+            let mut string = heading.to_owned();
+            writeln!(string).unwrap();
             if !parallel.is_empty() {
                 writeln!(string, " * Parallel systems:").unwrap();
                 write_display_names_of_pairs(&mut string, &self.parallel, parallel, world);
@@ -534,7 +609,9 @@ impl SystemStage {
                 writeln!(string, " * Exclusive systems at end of stage:").unwrap();
                 write_display_names_of_pairs(&mut string, &self.exclusive_at_end, at_end, world);
             }
-            info!("{}", string);
+            Some(string)
+        } else {
+            None
         }
     }
 
@@ -578,7 +655,11 @@ impl SystemStage {
         DependencyGraphError<HashSet<BoxedRunCriteriaLabel>>,
     > {
         let graph = graph_utils::build_dependency_graph(&self.run_criteria);
-        let order = graph_utils::topological_order(&graph)?;
+        let order = if self.deterministic {
+            graph_utils::deterministic_topological_order(&self.run_criteria, &graph)?
+        } else {
+            graph_utils::topological_order(&graph)?
+        };
         let mut order_inverted = order.iter().enumerate().collect::<Vec<_>>();
         order_inverted.sort_unstable_by_key(|(_, &key)| key);
         let labels: HashMap<_, _> = self
@@ -629,13 +710,18 @@ impl SystemStage {
 }
 
 /// Sorts given system containers topologically, populates their resolved dependencies
-/// and run criteria.
+/// and run criteria. If `deterministic` is set, the order doesn't depend on insertion order.
 fn process_systems(
     systems: &mut Vec<impl SystemContainer>,
     run_criteria_labels: &HashMap<BoxedRunCriteriaLabel, usize>,
+    deterministic: bool,
 ) -> Result<(), DependencyGraphError<HashSet<BoxedSystemLabel>>> {
     let mut graph = graph_utils::build_dependency_graph(systems);
-    let order = graph_utils::topological_order(&graph)?;
+    let order = if deterministic {
+        graph_utils::deterministic_topological_order(systems, &graph)?
+    } else {
+        graph_utils::topological_order(&graph)?
+    };
     let mut order_inverted = order.iter().enumerate().collect::<Vec<_>>();
     order_inverted.sort_unstable_by_key(|(_, &key)| key);
     for (index, container) in systems.iter_mut().enumerate() {
@@ -664,7 +750,9 @@ fn process_systems(
 /// Returns vector containing all pairs of indices of systems with ambiguous execution order,
 /// along with specific components that have triggered the warning.
 /// Systems must be topologically sorted beforehand.
-fn find_ambiguities(systems: &[impl SystemContainer]) -> Vec<(usize, usize, Vec<ComponentId>)> {
+pub(super) fn find_ambiguities(
+    systems: &[impl SystemContainer],
+) -> Vec<(usize, usize, Vec<ComponentId>)> {
     let mut ambiguity_set_labels = HashMap::default();
     for set in systems.iter().flat_map(|c| c.ambiguity_sets()) {
         let len = ambiguity_set_labels.len();
@@ -757,6 +845,10 @@ impl Stage for SystemStage {
             if world.contains_resource::<ReportExecutionOrderAmbiguities>() {
                 self.report_ambiguities(world);
             }
+            #[cfg(debug_assertions)]
+            if self.deterministic {
+                self.check_deterministic_ambiguities(world);
+            }
         } else if self.executor_modified {
             self.executor.rebuild_cached_data(&self.parallel);
             self.executor_modified = false;
@@ -897,8 +989,8 @@ mod tests {
         query::{ChangeTrackers, Changed},
         schedule::{
             BoxedSystemLabel, ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion,
-            RunCriteria, RunCriteriaDescriptorCoercion, RunCriteriaPiping, ShouldRun,
-            SingleThreadedExecutor, Stage, SystemSet, SystemStage,
+            RunCriteria, RunCriteriaCombinators, RunCriteriaDescriptorCoercion, RunCriteriaPiping,
+            ShouldRun, SingleThreadedExecutor, Stage, State, SystemSet, SystemStage,
         },
         system::{In, IntoExclusiveSystem, IntoSystem, Local, Query, ResMut},
         world::World,
@@ -1509,6 +1601,137 @@ mod tests {
         assert_eq!(stage.run_criteria.len(), 1);
     }
 
+    #[test]
+    fn combined_run_criteria() {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        enum AppState {
+            InGame,
+            Menu,
+        }
+
+        /// Loops over the pending steps, like a fixed timestep
+        struct Steps(usize);
+        fn steps(mut steps: ResMut<Steps>) -> ShouldRun {
+            if steps.0 > 0 {
+                steps.0 -= 1;
+                ShouldRun::YesAndCheckAgain
+            } else {
+                ShouldRun::No
+            }
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Vec::<usize>::new());
+        world.insert_resource(State::new(AppState::InGame));
+        world.insert_resource(Steps(0));
+
+        // The state stays active while the steps loop.
+        let mut stage = SystemStage::parallel()
+            .with_system_set(State::<AppState>::get_driver())
+            .with_system_set(
+                SystemSet::new()
+                    .with_run_criteria(State::on_update(AppState::InGame).and(steps))
+                    .with_system(make_parallel(0)),
+            );
+        // enter the initial state
+        stage.run(&mut world);
+        assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![]);
+        world.get_resource_mut::<Steps>().unwrap().0 = 3;
+        stage.run(&mut world);
+        assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![0, 0, 0]);
+        stage.run(&mut world);
+        assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![0, 0, 0]);
+        world
+            .get_resource_mut::<State<AppState>>()
+            .unwrap()
+            .set(AppState::Menu)
+            .unwrap();
+        world.get_resource_mut::<Steps>().unwrap().0 = 3;
+        stage.run(&mut world);
+        assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![0, 0, 0]);
+
+        // The second criteria isn't checked when the first one decides.
+        world.get_resource_mut::<Vec<usize>>().unwrap().clear();
+        let mut stage = SystemStage::parallel()
+            .with_system(make_parallel(0).with_run_criteria(every_other_time.and(steps)))
+            .with_system(make_parallel(1).with_run_criteria(every_other_time.or(steps)));
+        for _ in 0..2 {
+            world.get_resource_mut::<Steps>().unwrap().0 = 2;
+            stage.run(&mut world);
+        }
+        let mut result = world.get_resource::<Vec<usize>>().unwrap().clone();
+        result.sort_unstable();
+        assert_eq!(result, vec![0, 0, 1, 1, 1]);
+        assert_eq!(world.get_resource::<Steps>().unwrap().0, 0);
+
+        // Negating criteria and labels.
+        world.get_resource_mut::<Vec<usize>>().unwrap().clear();
+        let mut stage = SystemStage::parallel()
+            .with_system_run_criteria(every_other_time.label("every other time"))
+            .with_system(
+                make_parallel(0)
+                    .label("0")
+                    .with_run_criteria("every other time"),
+            )
+            .with_system(
+                make_parallel(1)
+                    .after("0")
+                    .with_run_criteria("every other time".not()),
+            );
+        for _ in 0..4 {
+            stage.run(&mut world);
+        }
+        assert_eq!(
+            *world.get_resource::<Vec<usize>>().unwrap(),
+            vec![0, 1, 0, 1]
+        );
+
+        // Criteria of sets are checked again each time the stage criteria loops.
+        world.get_resource_mut::<Vec<usize>>().unwrap().clear();
+        fn twice(mut count: Local<usize>) -> ShouldRun {
+            *count += 1;
+            if *count < 2 {
+                ShouldRun::YesAndCheckAgain
+            } else {
+                *count = 0;
+                ShouldRun::Yes
+            }
+        }
+        let mut stage = SystemStage::parallel()
+            .with_run_criteria(twice)
+            .with_system_set(
+                SystemSet::new()
+                    .with_run_criteria(every_other_time.not().or(steps))
+                    .with_system(make_parallel(0)),
+            );
+        world.get_resource_mut::<Steps>().unwrap().0 = 2;
+        stage.run(&mut world);
+        assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![0, 0, 0]);
+
+        // A loop ending with `Yes` runs one last time, combined or not.
+        world.get_resource_mut::<Vec<usize>>().unwrap().clear();
+        fn always() -> ShouldRun {
+            ShouldRun::Yes
+        }
+        let mut stage = SystemStage::parallel()
+            .with_system(make_parallel(0).with_run_criteria(twice))
+            .with_system(make_parallel(1).with_run_criteria(twice.and(always)))
+            .with_system(make_parallel(2).with_run_criteria(always.and(twice)));
+        stage.run(&mut world);
+        let mut result = world.get_resource::<Vec<usize>>().unwrap().clone();
+        result.sort_unstable();
+        assert_eq!(result, vec![0, 0, 1, 1, 2, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn combined_run_criteria_piped_from_different_criteria_panic() {
+        fn identity(In(should_run): In<ShouldRun>) -> ShouldRun {
+            should_run
+        }
+        let _ = "a".pipe(identity.system()).and("b".pipe(identity.system()));
+    }
+
     #[test]
     #[should_panic]
     fn duplicate_run_criteria_label_panic() {
@@ -1552,6 +1775,84 @@ mod tests {
         stage.run(&mut world);
     }
 
+    #[test]
+    fn deterministic_order() {
+        struct Tag(usize);
+
+        fn make_spawner(tag: usize) -> impl FnMut(crate::prelude::Commands) {
+            move |mut commands: crate::prelude::Commands| {
+                commands.spawn().insert(Tag(tag));
+            }
+        }
+
+        fn spawned_tags(world: &mut World) -> Vec<usize> {
+            world.query::<&Tag>().iter(world).map(|tag| tag.0).collect()
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Vec::<usize>::new());
+        let mut stage = SystemStage::deterministic()
+            .with_system(make_parallel(0).label("a"))
+            .with_system(make_parallel(1).label("b"))
+            .with_system(make_parallel(2).label("c").before("a"))
+            .with_system(make_parallel(3).label("d").after("b"))
+            .with_system(make_spawner(0).label("e"))
+            .with_system(make_spawner(1).label("f"));
+        stage.run(&mut world);
+        assert_eq!(
+            *world.get_resource::<Vec<usize>>().unwrap(),
+            vec![1, 2, 0, 3]
+        );
+        assert_eq!(spawned_tags(&mut world), vec![0, 1]);
+
+        let mut world = World::new();
+        world.insert_resource(Vec::<usize>::new());
+        let mut stage = SystemStage::deterministic()
+            .with_system(make_spawner(1).label("f"))
+            .with_system(make_spawner(0).label("e"))
+            .with_system(make_parallel(3).label("d").after("b"))
+            .with_system(make_parallel(2).label("c").before("a"))
+            .with_system(make_parallel(1).label("b"))
+            .with_system(make_parallel(0).label("a"));
+        stage.run(&mut world);
+        assert_eq!(
+            *world.get_resource::<Vec<usize>>().unwrap(),
+            vec![1, 2, 0, 3]
+        );
+        assert_eq!(spawned_tags(&mut world), vec![0, 1]);
+    }
+
+    #[test]
+    fn deterministic_order_exclusive() {
+        let mut world = World::new();
+        world.insert_resource(Vec::<usize>::new());
+        let mut stage = SystemStage::deterministic()
+            .with_system(make_exclusive(2).exclusive_system().label("z"))
+            .with_system(make_exclusive(1).exclusive_system().label("y"))
+            .with_system(make_exclusive(0).exclusive_system().label("x").after("z"));
+        stage.run(&mut world);
+        assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn deterministic_ambiguities() {
+        fn resource(_: ResMut<usize>) {}
+
+        let mut world = World::new();
+        world.insert_resource(0usize);
+        let mut stage = SystemStage::deterministic()
+            .with_system(resource.label("0"))
+            .with_system(resource.label("1").after("0"));
+        stage.run(&mut world);
+        assert!(stage.ambiguities_report("", &world).is_none());
+
+        let mut stage = SystemStage::deterministic()
+            .with_system(resource.label("0"))
+            .with_system(resource.label("1"));
+        stage.run(&mut world);
+        assert!(stage.ambiguities_report("", &world).is_some());
+    }
+
     #[test]
     fn ambiguity_detection() {
         use super::{find_ambiguities, SystemContainer};
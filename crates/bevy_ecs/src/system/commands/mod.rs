@@ -4,6 +4,7 @@ use crate::{
     bundle::Bundle,
     component::Component,
     entity::{Entities, Entity},
+    system::{RunSystem, SystemId},
     world::World,
 };
 use bevy_utils::tracing::debug;
@@ -152,6 +153,17 @@ impl<'a> Commands<'a> {
         });
     }
 
+    /// Queue running a system registered with [`World::register_system`].
+    pub fn run_system(&mut self, id: SystemId) {
+        self.queue.push(RunSystem { id, input: () });
+    }
+
+    /// Queue running a system registered with [`World::register_system`], with the given input.
+    /// The system must not return anything.
+    pub fn run_system_with_input<In: Send + Sync + 'static>(&mut self, id: SystemId, input: In) {
+        self.queue.push(RunSystem { id, input });
+    }
+
     /// Adds a command directly to the command list.
     pub fn add<C: Command>(&mut self, command: C) {
         self.queue.push(command);
@@ -6,6 +6,7 @@ mod query;
 mod system;
 mod system_chaining;
 mod system_param;
+mod system_registry;
 
 pub use commands::*;
 pub use exclusive_system::*;
@@ -14,6 +15,7 @@ pub use query::*;
 pub use system::*;
 pub use system_chaining::*;
 pub use system_param::*;
+pub use system_registry::*;
 
 #[cfg(test)]
 mod tests {
@@ -22,9 +24,12 @@ mod tests {
     use crate::{
         archetype::Archetypes,
         bundle::Bundles,
-        component::Components,
+        component::{Components, Disabled},
         entity::{Entities, Entity},
-        query::{Added, Changed, Or, With, Without},
+        query::{
+            Added, Changed, DynamicAccess, DynamicComponents, DynamicComponentsState,
+            IncludeDisabled, Or, With, Without,
+        },
         schedule::{Schedule, Stage, SystemStage},
         system::{
             ConfigurableSystem, IntoExclusiveSystem, IntoSystem, Local, Query, QuerySet,
@@ -217,6 +222,23 @@ mod tests {
         run_system(&mut world, sys);
     }
 
+    #[test]
+    fn disjoint_query_mut_disabled_system() {
+        fn sys(_q1: Query<&mut A>, _q2: Query<&mut A, With<Disabled>>) {}
+
+        let mut world = World::default();
+        run_system(&mut world, sys);
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_query_mut_include_disabled_system() {
+        fn sys(_q1: Query<&mut A>, _q2: Query<&mut A, IncludeDisabled>) {}
+
+        let mut world = World::default();
+        run_system(&mut world, sys);
+    }
+
     #[test]
     fn disjoint_query_mut_read_component_system() {
         fn sys(_q1: Query<(&mut A, &B)>, _q2: Query<&mut A, Without<B>>) {}
@@ -436,6 +458,39 @@ mod tests {
         assert_eq!(conflicts, vec![b_id, d_id]);
     }
 
+    #[test]
+    fn dynamic_query_system() {
+        fn sys_x(mut query: Query<DynamicComponents>) {
+            for mut item in query.iter_mut() {
+                // SAFE: the written component is a u32
+                unsafe { *item.get_mut(0).unwrap().cast::<u32>() += 1 };
+            }
+        }
+
+        fn sys_y(_: Query<&u32>) {}
+
+        let mut world = World::default();
+        world.spawn().insert(1u32);
+        let id = world.components().get_id(TypeId::of::<u32>()).unwrap();
+        let config_state = DynamicComponentsState::new(&world, &[DynamicAccess::Write(id)]);
+        let mut x = sys_x.config(|config| config.0 = Some(config_state));
+        let mut y = sys_y.system();
+        x.initialize(&mut world);
+        y.initialize(&mut world);
+        let conflicts = x.component_access().get_conflicts(y.component_access());
+        assert_eq!(conflicts, vec![id]);
+
+        let config_state = DynamicComponentsState::new(&world, &[DynamicAccess::Write(id)]);
+        run_system(
+            &mut world,
+            sys_x.config(|config| config.0 = Some(config_state)),
+        );
+        assert_eq!(
+            world.query::<&u32>().iter(&world).collect::<Vec<_>>(),
+            vec![&2]
+        );
+    }
+
     #[test]
     fn query_is_empty() {
         fn without_filter(not_empty: Query<&A>, empty: Query<&B>) {
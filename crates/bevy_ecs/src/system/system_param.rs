@@ -6,7 +6,8 @@ use crate::{
     component::{Component, ComponentId, ComponentTicks, Components},
     entity::{Entities, Entity},
     query::{
-        FilterFetch, FilteredAccess, FilteredAccessSet, QueryState, ReadOnlyFetch, WorldQuery,
+        FetchState, FilterFetch, FilteredAccess, FilteredAccessSet, QueryState, ReadOnlyFetch,
+        WorldQuery,
     },
     system::{CommandQueue, Commands, Query, SystemMeta},
     world::{FromWorld, World},
@@ -115,10 +116,19 @@ unsafe impl<Q: WorldQuery + 'static, F: WorldQuery + 'static> SystemParamState f
 where
     F::Fetch: FilterFetch,
 {
-    type Config = ();
-
-    fn init(world: &mut World, system_meta: &mut SystemMeta, _config: Self::Config) -> Self {
-        let state = QueryState::new(world);
+    /// The fetch state to use instead of initializing one from `Q`, required by queries whose
+    /// state depends on more than their type, like
+    /// [`DynamicComponents`](crate::query::DynamicComponents).
+    type Config = Option<Q::State>;
+
+    fn init(world: &mut World, system_meta: &mut SystemMeta, config: Self::Config) -> Self {
+        let state = match config {
+            Some(fetch_state) => {
+                let filter_state = <F::State as FetchState>::init(world);
+                QueryState::new_with_state(world, fetch_state, filter_state)
+            }
+            None => QueryState::new(world),
+        };
         assert_component_access_compatibility(
             &system_meta.name,
             std::any::type_name::<Q>(),
@@ -143,7 +153,9 @@ where
             .extend(&self.archetype_component_access);
     }
 
-    fn default_config() {}
+    fn default_config() -> Option<Q::State> {
+        None
+    }
 }
 
 impl<'a, Q: WorldQuery + 'static, F: WorldQuery + 'static> SystemParamFetch<'a> for QueryState<Q, F>
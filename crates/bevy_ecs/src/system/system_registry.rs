@@ -0,0 +1,203 @@
+use crate::{
+    archetype::ArchetypeGeneration,
+    system::{BoxedSystem, Command, SystemId},
+    world::World,
+};
+use bevy_utils::{tracing::warn, HashMap};
+use std::any::Any;
+use thiserror::Error;
+
+/// The systems registered with [`World::register_system`], stored as a resource
+#[derive(Default)]
+pub(crate) struct SystemRegistry {
+    /// The systems are taken out of the registry while they run
+    pub(crate) systems: HashMap<SystemId, Option<Box<dyn Any + Send + Sync>>>,
+}
+
+/// A system registered with [`World::register_system`], along with the state needed to run it
+/// outside of a stage
+pub(crate) struct RegisteredSystem<In, Out> {
+    system: BoxedSystem<In, Out>,
+    initialized: bool,
+    archetype_generation: ArchetypeGeneration,
+}
+
+impl<In: 'static, Out: 'static> RegisteredSystem<In, Out> {
+    pub(crate) fn new(system: BoxedSystem<In, Out>) -> Self {
+        Self {
+            system,
+            initialized: false,
+            archetype_generation: ArchetypeGeneration::initial(),
+        }
+    }
+
+    pub(crate) fn run(&mut self, input: In, world: &mut World) -> Out {
+        if !self.initialized {
+            self.system.initialize(world);
+            self.initialized = true;
+        }
+        let change_tick = world.change_tick();
+        self.system.check_change_tick(change_tick);
+
+        let archetypes = world.archetypes();
+        let new_generation = archetypes.generation();
+        let old_generation = std::mem::replace(&mut self.archetype_generation, new_generation);
+        let archetype_index_range = old_generation.value()..new_generation.value();
+        for archetype in archetypes.archetypes[archetype_index_range].iter() {
+            self.system.new_archetype(archetype);
+        }
+
+        let out = self.system.run(input, world);
+        self.system.apply_buffers(world);
+        out
+    }
+}
+
+/// An error running a system registered with [`World::register_system`]
+#[derive(Error, Debug)]
+pub enum RegisteredSystemError {
+    #[error("system {0:?} is not registered")]
+    SystemIdNotRegistered(SystemId),
+    #[error("system {0:?} tried to run itself recursively")]
+    Recursive(SystemId),
+    #[error("system {0:?} does not have the requested input and output types")]
+    IncorrectType(SystemId),
+}
+
+/// Runs a system registered with [`World::register_system`], with the given input.
+///
+/// Use [`Commands::run_system`](crate::system::Commands::run_system) or
+/// [`Commands::run_system_with_input`](crate::system::Commands::run_system_with_input) to add it
+/// to a command queue.
+#[derive(Debug)]
+pub struct RunSystem<In = ()> {
+    pub id: SystemId,
+    pub input: In,
+}
+
+impl<In: Send + Sync + 'static> Command for RunSystem<In> {
+    fn write(self, world: &mut World) {
+        if let Err(err) = world.run_system_with_input::<In, ()>(self.id, self.input) {
+            warn!("Failed to run system: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        system::{Command, CommandQueue, RegisteredSystemError, SystemId},
+    };
+
+    struct Counter(usize);
+
+    fn increment(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    #[test]
+    fn run_registered_system() {
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+        let id = world.register_system(increment);
+        world.run_system(id).unwrap();
+        world.run_system(id).unwrap();
+        assert_eq!(world.get_resource::<Counter>().unwrap().0, 2);
+
+        assert!(world.remove_system(id));
+        assert!(matches!(
+            world.run_system(id),
+            Err(RegisteredSystemError::SystemIdNotRegistered(_))
+        ));
+    }
+
+    #[test]
+    fn registered_system_input_and_output() {
+        fn add(In(amount): In<usize>, mut counter: ResMut<Counter>) -> usize {
+            counter.0 += amount;
+            counter.0
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Counter(1));
+        let id = world.register_system(add);
+        assert_eq!(
+            world.run_system_with_input::<usize, usize>(id, 2).unwrap(),
+            3
+        );
+        assert_eq!(
+            world.run_system_with_input::<usize, usize>(id, 4).unwrap(),
+            7
+        );
+        assert!(matches!(
+            world.run_system(id),
+            Err(RegisteredSystemError::IncorrectType(_))
+        ));
+    }
+
+    #[test]
+    fn registered_system_commands() {
+        struct Callback(SystemId);
+
+        fn spawn_counted(mut commands: Commands, query: Query<&Counter>) {
+            commands.spawn().insert(Counter(query.iter().len()));
+        }
+
+        fn trigger(mut commands: Commands, callback: Res<Callback>) {
+            commands.run_system(callback.0);
+        }
+
+        let mut world = World::new();
+        let callback = world.register_system(spawn_counted);
+        world.insert_resource(Callback(callback));
+        let trigger = world.register_system(trigger);
+        world.run_system(trigger).unwrap();
+        world.run_system(trigger).unwrap();
+
+        // the commands of the callback are applied, and its queries see the new archetypes
+        let mut counters = world
+            .query::<&Counter>()
+            .iter(&world)
+            .map(|counter| counter.0)
+            .collect::<Vec<_>>();
+        counters.sort_unstable();
+        assert_eq!(counters, vec![0, 1]);
+
+        // a command with the wrong input type is logged and ignored
+        let mut queue = CommandQueue::default();
+        Commands::new(&mut queue, &world).run_system_with_input(callback, 3usize);
+        queue.apply(&mut world);
+        assert_eq!(world.query::<&Counter>().iter(&world).len(), 2);
+    }
+
+    #[test]
+    fn recursive_registered_system() {
+        struct Recursion(Option<RegisteredSystemError>);
+
+        // the commands of a system are applied while it is still taken out of the registry
+        fn recurse(mut commands: Commands) {
+            commands.add(RunSelf);
+        }
+
+        struct RunSelf;
+        impl Command for RunSelf {
+            fn write(self, world: &mut World) {
+                let id = world.get_resource::<SelfId>().unwrap().0;
+                let error = world.run_system(id).err();
+                world.insert_resource(Recursion(error));
+            }
+        }
+
+        struct SelfId(SystemId);
+
+        let mut world = World::new();
+        let id = world.register_system(recurse);
+        world.insert_resource(SelfId(id));
+        world.run_system(id).unwrap();
+        assert!(matches!(
+            world.get_resource::<Recursion>().unwrap().0,
+            Some(RegisteredSystemError::Recursive(_))
+        ));
+    }
+}
@@ -0,0 +1,372 @@
+use crate::{
+    component::{Component, Disabled},
+    entity::{Entity, EntityMap, MapEntitiesError},
+    world::World,
+};
+use bevy_utils::HashMap;
+use std::any::TypeId;
+use thiserror::Error;
+
+#[cfg(feature = "bevy_reflect")]
+use crate::reflect::{ReflectComponent, ReflectMapEntities};
+
+/// Inserts a component cloned from another entity
+type ClonedComponent = Box<dyn FnOnce(&mut World, Entity) + Send + Sync>;
+
+/// Reads a component of an entity, to clone it
+type ComponentCloner = fn(&World, Entity) -> Option<ClonedComponent>;
+
+/// Fixes the components of cloned entities before their references are mapped, see
+/// [`CloneRegistry::add_hook`]
+pub type CloneHook = fn(&mut World, &EntityMap);
+
+/// The components that can be cloned with [`World::clone_entity`] and [`move_entities`] without
+/// being reflected, stored as a resource of the source world.
+///
+/// Components registered with [`ReflectComponent`](crate::reflect::ReflectComponent) in the
+/// `TypeRegistryArc` resource of the world are cloned through reflection instead. [`Disabled`] is
+/// registered by default.
+pub struct CloneRegistry {
+    cloners: HashMap<TypeId, ComponentCloner>,
+    hooks: Vec<CloneHook>,
+}
+
+impl Default for CloneRegistry {
+    fn default() -> Self {
+        let mut registry = CloneRegistry {
+            cloners: Default::default(),
+            hooks: Vec::new(),
+        };
+        registry.register::<Disabled>();
+        registry
+    }
+}
+
+impl CloneRegistry {
+    /// Allows components of type `T` to be cloned with their [`Clone`] implementation.
+    pub fn register<T: Component + Clone>(&mut self) {
+        self.cloners.insert(TypeId::of::<T>(), |world, entity| {
+            let component = world.get::<T>(entity)?.clone();
+            Some(Box::new(move |world: &mut World, entity| {
+                world.entity_mut(entity).insert(component);
+            }))
+        });
+    }
+
+    /// Returns `true` if components of the given type can be cloned with [`Clone`].
+    pub fn contains(&self, type_id: TypeId) -> bool {
+        self.cloners.contains_key(&type_id)
+    }
+
+    /// Adds a hook run on the world of the copies once their components are inserted, with the
+    /// map from the cloned entities to their copies. References in the components of the copies
+    /// still point to the cloned entities, and are mapped after the hooks ran.
+    ///
+    /// Hooks can remove references that should not be kept as they are, like the parent of a
+    /// copy whose parent is not cloned.
+    pub fn add_hook(&mut self, hook: CloneHook) {
+        self.hooks.push(hook);
+    }
+}
+
+/// An error cloning or moving entities
+#[derive(Error, Debug)]
+pub enum EntityCloneError {
+    #[error("entity {0:?} does not exist")]
+    NoSuchEntity(Entity),
+    #[error("component {name} of entity {entity:?} is neither reflected nor registered in the CloneRegistry")]
+    UnclonableComponent { entity: Entity, name: String },
+    #[error("failed to map the entities referenced by the cloned components: {0}")]
+    MapEntities(#[from] MapEntitiesError),
+}
+
+/// The components of the entities to clone, read from the source world
+struct ClonedEntities {
+    entities: Vec<(Entity, Vec<ClonedComponent>)>,
+    hooks: Vec<CloneHook>,
+    #[cfg(feature = "bevy_reflect")]
+    map_entities: HashMap<TypeId, ReflectMapEntities>,
+}
+
+impl ClonedEntities {
+    fn read(world: &World, entities: &[Entity]) -> Result<Self, EntityCloneError> {
+        let default_registry = CloneRegistry::default();
+        let clone_registry = world
+            .get_resource::<CloneRegistry>()
+            .unwrap_or(&default_registry);
+        #[cfg(feature = "bevy_reflect")]
+        let type_registry = world
+            .get_resource::<bevy_reflect::TypeRegistryArc>()
+            .map(|type_registry| type_registry.read());
+
+        let mut cloned = ClonedEntities {
+            entities: Vec::with_capacity(entities.len()),
+            hooks: clone_registry.hooks.clone(),
+            #[cfg(feature = "bevy_reflect")]
+            map_entities: HashMap::default(),
+        };
+        for &entity in entities {
+            let entity_ref = world
+                .get_entity(entity)
+                .ok_or(EntityCloneError::NoSuchEntity(entity))?;
+            let mut components = Vec::new();
+            for component_id in entity_ref.archetype().components() {
+                let info = world.components().get_info(component_id).unwrap();
+                let unclonable = || EntityCloneError::UnclonableComponent {
+                    entity,
+                    name: info.name().to_string(),
+                };
+                let type_id = info.type_id().ok_or_else(unclonable)?;
+
+                #[cfg(feature = "bevy_reflect")]
+                if let Some(registration) = type_registry
+                    .as_ref()
+                    .and_then(|type_registry| type_registry.get(type_id))
+                {
+                    if let Some(reflect_component) = registration.data::<ReflectComponent>() {
+                        if let Some(map_entities) = registration.data::<ReflectMapEntities>() {
+                            cloned.map_entities.insert(type_id, map_entities.clone());
+                        }
+                        let component = reflect_component
+                            .reflect_component(world, entity)
+                            .unwrap()
+                            .clone_value();
+                        let reflect_component = reflect_component.clone();
+                        components.push(Box::new(move |world: &mut World, entity| {
+                            reflect_component.add_component(world, entity, &*component);
+                        }) as ClonedComponent);
+                        continue;
+                    }
+                }
+
+                let cloner = clone_registry
+                    .cloners
+                    .get(&type_id)
+                    .ok_or_else(unclonable)?;
+                components.push(cloner(world, entity).unwrap());
+            }
+            cloned.entities.push((entity, components));
+        }
+        Ok(cloned)
+    }
+
+    /// Spawns the cloned entities in `world`, and maps the entities referenced by their components.
+    /// Returns the new entities, in the order they were read.
+    ///
+    /// References to entities that are not cloned are kept as they are if `keep_unmapped` is
+    /// `true`. Otherwise they are an error, and the new entities are despawned.
+    fn write(
+        self,
+        world: &mut World,
+        keep_unmapped: bool,
+    ) -> Result<Vec<Entity>, EntityCloneError> {
+        let mut entity_map = EntityMap::default();
+        entity_map.keep_unmapped(keep_unmapped);
+        let mut new_entities = Vec::with_capacity(self.entities.len());
+        for (entity, components) in self.entities {
+            let new_entity = world.spawn().id();
+            for component in components {
+                component(world, new_entity);
+            }
+            entity_map.insert(entity, new_entity);
+            new_entities.push(new_entity);
+        }
+        for hook in &self.hooks {
+            hook(world, &entity_map);
+        }
+        #[cfg(feature = "bevy_reflect")]
+        for map_entities in self.map_entities.values() {
+            if let Err(error) = map_entities.map_entities(world, &entity_map) {
+                for &entity in &new_entities {
+                    world.despawn(entity);
+                }
+                return Err(error.into());
+            }
+        }
+        Ok(new_entities)
+    }
+}
+
+impl World {
+    /// Spawns a copy of `entity`, with clones of its components.
+    ///
+    /// Components are cloned through reflection if they are registered with
+    /// [`ReflectComponent`](crate::reflect::ReflectComponent) in the `TypeRegistryArc` resource,
+    /// or with their [`Clone`] implementation if they are registered in the [`CloneRegistry`]
+    /// resource. References to `entity` in the cloned components are mapped to the copy, with
+    /// [`ReflectMapEntities`](crate::reflect::ReflectMapEntities). Other references are kept as
+    /// they are, unless a hook of the [`CloneRegistry`] removes them.
+    ///
+    /// ```
+    /// use bevy_ecs::{prelude::*, world::CloneRegistry};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// world
+    ///     .get_resource_or_insert_with(CloneRegistry::default)
+    ///     .register::<Health>();
+    /// let prefab = world.spawn().insert(Health(10)).id();
+    /// let instance = world.clone_entity(prefab).unwrap();
+    /// assert_eq!(world.get::<Health>(instance), Some(&Health(10)));
+    /// ```
+    pub fn clone_entity(&mut self, entity: Entity) -> Result<Entity, EntityCloneError> {
+        Ok(self.clone_entities(&[entity])?[0])
+    }
+
+    /// Spawns copies of `entities`, like [`World::clone_entity`]. References between the entities
+    /// are mapped to their copies, so that a hierarchy can be copied at once. Returns the copies
+    /// in the same order as `entities`.
+    ///
+    /// No entity is spawned if one of the components can't be cloned.
+    pub fn clone_entities(&mut self, entities: &[Entity]) -> Result<Vec<Entity>, EntityCloneError> {
+        ClonedEntities::read(self, entities)?.write(self, true)
+    }
+}
+
+/// Moves `entities` from `source` to `destination`, like [`World::clone_entities`] followed by
+/// despawning the entities from `source`. The components are cloned with the registrations of
+/// `source`. Returns the entities in `destination`, in the same order as `entities`.
+///
+/// Nothing is moved if one of the components can't be cloned, or if a moved component refers to
+/// an entity that is not moved, which fails with [`EntityCloneError::MapEntities`]: the reference
+/// would point to an unrelated entity of `destination`. Only references mapped with
+/// [`ReflectMapEntities`](crate::reflect::ReflectMapEntities) are checked, after the hooks of the
+/// [`CloneRegistry`] ran.
+///
+/// ```
+/// use bevy_ecs::{prelude::*, world::{move_entities, CloneRegistry}};
+///
+/// #[derive(Clone)]
+/// struct Chunk(u32);
+///
+/// let mut staging = World::new();
+/// staging
+///     .get_resource_or_insert_with(CloneRegistry::default)
+///     .register::<Chunk>();
+/// let chunk = staging.spawn().insert(Chunk(4)).id();
+///
+/// let mut world = World::new();
+/// let moved = move_entities(&mut staging, &mut world, &[chunk]).unwrap();
+/// assert_eq!(world.get::<Chunk>(moved[0]).unwrap().0, 4);
+/// assert!(staging.get_entity(chunk).is_none());
+/// ```
+pub fn move_entities(
+    source: &mut World,
+    destination: &mut World,
+    entities: &[Entity],
+) -> Result<Vec<Entity>, EntityCloneError> {
+    let moved = ClonedEntities::read(source, entities)?.write(destination, false)?;
+    for &entity in entities {
+        source.despawn(entity);
+    }
+    Ok(moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        entity::{EntityMap, MapEntities},
+        reflect::{ReflectComponent, ReflectMapEntities},
+    };
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Health(u32);
+
+    struct Unclonable;
+
+    #[derive(Reflect, Debug, PartialEq)]
+    #[reflect(Component, MapEntities)]
+    struct Link(Entity);
+
+    impl Default for Link {
+        fn default() -> Self {
+            Link(Entity::new(u32::MAX))
+        }
+    }
+
+    impl MapEntities for Link {
+        fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+            self.0 = entity_map.get(self.0)?;
+            Ok(())
+        }
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        world
+            .get_resource_or_insert_with(CloneRegistry::default)
+            .register::<Health>();
+        let type_registry = TypeRegistryArc::default();
+        type_registry.write().register::<Link>();
+        world.insert_resource(type_registry);
+        world
+    }
+
+    #[test]
+    fn clone_entities() {
+        let mut world = world();
+        let outside = world.spawn().id();
+        let a = world.spawn().insert(Health(1)).id();
+        let b = world
+            .spawn()
+            .insert_bundle((Health(2), Link(a), Disabled))
+            .id();
+        world.entity_mut(a).insert(Link(outside));
+
+        let copies = world.clone_entities(&[a, b]).unwrap();
+        assert_eq!(world.get::<Health>(copies[0]), Some(&Health(1)));
+        assert_eq!(world.get::<Health>(copies[1]), Some(&Health(2)));
+        assert!(world.get::<Disabled>(copies[1]).is_some());
+        // references between the copies are mapped, other references are kept
+        assert_eq!(world.get::<Link>(copies[1]), Some(&Link(copies[0])));
+        assert_eq!(world.get::<Link>(copies[0]), Some(&Link(outside)));
+        assert_eq!(world.get::<Link>(b), Some(&Link(a)));
+
+        let copy = world.clone_entity(b).unwrap();
+        assert_eq!(world.get::<Link>(copy), Some(&Link(a)));
+
+        let unclonable = world.spawn().insert_bundle((Health(3), Unclonable)).id();
+        let entity_count = world.entities().len();
+        assert!(matches!(
+            world.clone_entities(&[a, unclonable]),
+            Err(EntityCloneError::UnclonableComponent { entity, .. }) if entity == unclonable
+        ));
+        assert_eq!(world.entities().len(), entity_count);
+    }
+
+    #[test]
+    fn move_entities_between_worlds() {
+        let mut source = world();
+        let a = source.spawn().insert(Health(1)).id();
+        let b = source.spawn().insert_bundle((Health(2), Link(a))).id();
+        let kept = source.spawn().insert(Health(3)).id();
+
+        let mut destination = World::new();
+        destination.spawn().insert(Health(0));
+        let moved = move_entities(&mut source, &mut destination, &[a, b]).unwrap();
+        assert_eq!(destination.get::<Health>(moved[0]), Some(&Health(1)));
+        assert_eq!(destination.get::<Link>(moved[1]), Some(&Link(moved[0])));
+        assert!(source.get_entity(a).is_none());
+        assert!(source.get_entity(b).is_none());
+        assert!(source.get_entity(kept).is_some());
+
+        assert!(matches!(
+            move_entities(&mut source, &mut destination, &[a]),
+            Err(EntityCloneError::NoSuchEntity(entity)) if entity == a
+        ));
+
+        // references to entities that are not moved would dangle
+        let c = source.spawn().insert(Link(kept)).id();
+        let entity_count = destination.entities().len();
+        assert!(matches!(
+            move_entities(&mut source, &mut destination, &[c]),
+            Err(EntityCloneError::MapEntities(MapEntitiesError::EntityNotFound(entity))) if entity == kept
+        ));
+        assert_eq!(destination.entities().len(), entity_count);
+        assert_eq!(source.get::<Link>(c), Some(&Link(kept)));
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes, ComponentStatus},
     bundle::{Bundle, BundleInfo},
     change_detection::Ticks,
-    component::{Component, ComponentId, ComponentTicks, Components, StorageType},
+    component::{Component, ComponentHook, ComponentId, ComponentTicks, Components, StorageType},
     entity::{Entities, Entity, EntityLocation},
     storage::{SparseSet, Storages},
     world::{Mut, World},
@@ -69,6 +69,22 @@ impl<'w> EntityRef<'w> {
         }
     }
 
+    /// Gets a pointer to the component `component_id` of this entity, which is how components
+    /// defined at runtime with [`ComponentDescriptor::new_dynamic`] are read.
+    ///
+    /// [`ComponentDescriptor::new_dynamic`]: crate::component::ComponentDescriptor::new_dynamic
+    #[inline]
+    pub fn get_by_id(&self, component_id: ComponentId) -> Option<*const u8> {
+        if !self.contains_id(component_id) {
+            return None;
+        }
+        // SAFE: entity location is valid and the component exists
+        unsafe {
+            get_component(self.world, component_id, self.entity, self.location)
+                .map(|value| value as *const u8)
+        }
+    }
+
     /// # Safety
     /// This allows aliased mutability. You must make sure this call does not result in multiple
     /// mutable references to the same component
@@ -151,6 +167,40 @@ impl<'w> EntityMut<'w> {
         }
     }
 
+    /// Gets a pointer to the component `component_id` of this entity, which is how components
+    /// defined at runtime with [`ComponentDescriptor::new_dynamic`] are read.
+    ///
+    /// [`ComponentDescriptor::new_dynamic`]: crate::component::ComponentDescriptor::new_dynamic
+    #[inline]
+    pub fn get_by_id(&self, component_id: ComponentId) -> Option<*const u8> {
+        if !self.contains_id(component_id) {
+            return None;
+        }
+        // SAFE: entity location is valid and the component exists
+        unsafe {
+            get_component(self.world, component_id, self.entity, self.location)
+                .map(|value| value as *const u8)
+        }
+    }
+
+    /// Gets a pointer to the component `component_id` of this entity, marking it as changed. See
+    /// [`EntityMut::get_by_id`].
+    #[inline]
+    pub fn get_mut_by_id(&mut self, component_id: ComponentId) -> Option<*mut u8> {
+        if !self.contains_id(component_id) {
+            return None;
+        }
+        // SAFE: world access is unique, entity location is valid, and the component exists
+        unsafe {
+            get_component_and_ticks(self.world, component_id, self.entity, self.location).map(
+                |(value, ticks)| {
+                    (*ticks).set_changed(self.world.change_tick());
+                    value
+                },
+            )
+        }
+    }
+
     #[inline]
     pub fn get_mut<T: Component>(&mut self) -> Option<Mut<'w, T>> {
         // SAFE: world access is unique, entity location is valid, and returned component is of type
@@ -292,10 +342,85 @@ impl<'w> EntityMut<'w> {
                 change_tick,
             )
         };
+        let hooks = insert_hooks(&self.world.components, bundle_info, bundle_status);
+        self.run_hooks(hooks);
+        self
+    }
+
+    /// Inserts the component `component_id` on this entity, replacing its current value if it
+    /// already has one. This is how components defined at runtime with
+    /// [`ComponentDescriptor::new_dynamic`] are added.
+    ///
+    /// The value pointed to by `component` is moved into the world, for example the bytes of a
+    /// value serialized by a scripting language.
+    ///
+    /// # Safety
+    ///
+    /// `component` must point to a valid value of the component `component_id`, with its size
+    /// but not necessarily its alignment. The caller must not use nor drop that value afterwards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `component_id` does not exist in the world.
+    ///
+    /// [`ComponentDescriptor::new_dynamic`]: crate::component::ComponentDescriptor::new_dynamic
+    pub unsafe fn insert_by_id(
+        &mut self,
+        component_id: ComponentId,
+        component: *mut u8,
+    ) -> &mut Self {
+        let change_tick = self.world.change_tick();
+        let bundle_info = self
+            .world
+            .bundles
+            .init_component_info(&self.world.components, component_id);
+
+        let (archetype, bundle_status, new_location) = Self::get_insert_bundle_info(
+            &mut self.world.entities,
+            &mut self.world.archetypes,
+            &mut self.world.components,
+            &mut self.world.storages,
+            bundle_info,
+            self.location,
+            self.entity,
+        );
+        self.location = new_location;
+
+        let table = &mut self.world.storages.tables[archetype.table_id()];
+        let table_row = archetype.entity_table_row(new_location.index);
+        bundle_info.write_component(
+            &mut self.world.storages.sparse_sets,
+            self.entity,
+            table,
+            table_row,
+            bundle_status,
+            0,
+            component,
+            change_tick,
+        );
+        let hooks = insert_hooks(&self.world.components, bundle_info, bundle_status);
+        self.run_hooks(hooks);
         self
     }
 
     pub fn remove_bundle<T: Bundle>(&mut self) -> Option<T> {
+        let bundle_info = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components);
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        if bundle_info
+            .component_ids
+            .iter()
+            .all(|component_id| archetype.contains(*component_id))
+        {
+            let hooks = remove_hooks(
+                &self.world.components,
+                bundle_info.component_ids.iter().cloned(),
+            );
+            self.run_hooks(hooks);
+        }
+
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
@@ -417,6 +542,21 @@ impl<'w> EntityMut<'w> {
 
     /// Remove any components in the bundle that the entity has.
     pub fn remove_bundle_intersection<T: Bundle>(&mut self) {
+        let bundle_info = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components);
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        let hooks = remove_hooks(
+            &self.world.components,
+            bundle_info
+                .component_ids
+                .iter()
+                .cloned()
+                .filter(|component_id| archetype.contains(*component_id)),
+        );
+        self.run_hooks(hooks);
+
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
@@ -483,7 +623,13 @@ impl<'w> EntityMut<'w> {
         self.remove_bundle::<(T,)>().map(|v| v.0)
     }
 
-    pub fn despawn(self) {
+    pub fn despawn(mut self) {
+        let hooks = remove_hooks(
+            &self.world.components,
+            self.world.archetypes[self.location.archetype_id].components(),
+        );
+        self.run_hooks(hooks);
+
         let world = self.world;
         world.flush();
         let location = world
@@ -543,6 +689,60 @@ impl<'w> EntityMut<'w> {
     pub fn update_location(&mut self) {
         self.location = self.world.entities().get(self.entity).unwrap();
     }
+
+    /// Runs component `hooks` for this entity, which can move it to another location
+    fn run_hooks(&mut self, hooks: Vec<(ComponentHook, ComponentId)>) {
+        if hooks.is_empty() {
+            return;
+        }
+        for (hook, component_id) in hooks {
+            hook(self.world, self.entity, component_id);
+        }
+        self.update_location();
+    }
+}
+
+/// The hooks to run after inserting the components of `bundle_info` on an entity, with the
+/// `on_add` hooks of the newly added components first
+pub(crate) fn insert_hooks(
+    components: &Components,
+    bundle_info: &BundleInfo,
+    bundle_status: &[ComponentStatus],
+) -> Vec<(ComponentHook, ComponentId)> {
+    let mut hooks = Vec::new();
+    for (component_id, status) in bundle_info.component_ids.iter().zip(bundle_status) {
+        if let ComponentStatus::Added = status {
+            // SAFE: bundle components exist
+            let info = unsafe { components.get_info_unchecked(*component_id) };
+            if let Some(hook) = info.hooks().get_on_add() {
+                hooks.push((hook, *component_id));
+            }
+        }
+    }
+    for component_id in bundle_info.component_ids.iter() {
+        // SAFE: bundle components exist
+        let info = unsafe { components.get_info_unchecked(*component_id) };
+        if let Some(hook) = info.hooks().get_on_insert() {
+            hooks.push((hook, *component_id));
+        }
+    }
+    hooks
+}
+
+/// The hooks to run before removing `component_ids` from an entity
+fn remove_hooks(
+    components: &Components,
+    component_ids: impl Iterator<Item = ComponentId>,
+) -> Vec<(ComponentHook, ComponentId)> {
+    component_ids
+        .filter_map(|component_id| {
+            // SAFE: components of an archetype or a bundle exist
+            let info = unsafe { components.get_info_unchecked(component_id) };
+            info.hooks()
+                .get_on_remove()
+                .map(|hook| (hook, component_id))
+        })
+        .collect()
 }
 
 /// # Safety
@@ -1,8 +1,10 @@
+mod clone_entities;
 mod entity_ref;
 mod spawn_batch;
 mod world_cell;
 
 pub use crate::change_detection::Mut;
+pub use clone_entities::*;
 pub use entity_ref::*;
 pub use spawn_batch::*;
 pub use world_cell::*;
@@ -12,12 +14,18 @@ use crate::{
     bundle::{Bundle, Bundles},
     change_detection::Ticks,
     component::{
-        Component, ComponentDescriptor, ComponentId, ComponentTicks, Components, ComponentsError,
-        StorageType,
+        Component, ComponentDescriptor, ComponentHooks, ComponentId, ComponentTicks, Components,
+        ComponentsError, StorageType,
+    },
+    entity::{AllocAtWithoutReplacement, Entities, Entity},
+    query::{
+        DynamicAccess, DynamicComponents, DynamicComponentsState, FilterFetch, QueryState,
+        WorldQuery,
     },
-    entity::{Entities, Entity},
-    query::{FilterFetch, QueryState, WorldQuery},
     storage::{Column, SparseSet, Storages},
+    system::{
+        IntoSystem, RegisteredSystem, RegisteredSystemError, System, SystemId, SystemRegistry,
+    },
 };
 use std::{
     any::TypeId,
@@ -164,6 +172,21 @@ impl World {
         Ok(component_id)
     }
 
+    /// Returns the [ComponentHooks] of the component type `T`, registering it with the default
+    /// configuration if it wasn't already. See [ComponentHooks] for an example.
+    pub fn register_component_hooks<T: Component>(&mut self) -> &mut ComponentHooks {
+        let component_id = self.components.get_or_insert_id::<T>();
+        self.components.get_hooks_mut(component_id).unwrap()
+    }
+
+    /// Returns the [ComponentHooks] of the component `component_id`, if it exists
+    pub fn register_component_hooks_by_id(
+        &mut self,
+        component_id: ComponentId,
+    ) -> Option<&mut ComponentHooks> {
+        self.components.get_hooks_mut(component_id)
+    }
+
     /// Retrieves an [EntityRef] that exposes read-only operations for the given `entity`.
     /// This will panic if the `entity` does not exist. Use [World::get_entity] if you want
     /// to check for entity existence instead of implicitly panic-ing.
@@ -308,6 +331,49 @@ impl World {
         }
     }
 
+    /// Returns an [EntityMut] for the given `entity`, spawning it with this exact id and
+    /// generation if it doesn't exist. Returns [None] if the id is used by a live entity of
+    /// another generation.
+    ///
+    /// This is useful to restore entities whose ids are referenced elsewhere, such as entities
+    /// synchronized over the network.
+    ///
+    /// ```
+    /// use bevy_ecs::{entity::Entity, world::World};
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn().insert(1u32).id();
+    /// world.despawn(entity);
+    ///
+    /// let respawned = world.get_or_spawn(entity).unwrap().id();
+    /// assert_eq!(respawned, entity);
+    /// ```
+    pub fn get_or_spawn(&mut self, entity: Entity) -> Option<EntityMut> {
+        self.flush();
+        match self.entities.alloc_at_without_replacement(entity) {
+            AllocAtWithoutReplacement::Exists(location) => {
+                // SAFE: `entity` exists and `location` is that entity's location
+                Some(unsafe { EntityMut::new(self, entity, location) })
+            }
+            AllocAtWithoutReplacement::DidNotExist => {
+                let archetype = self.archetypes.empty_mut();
+                unsafe {
+                    let table_row = self.storages.tables[archetype.table_id()].allocate(entity);
+                    // SAFE: no components are allocated by archetype.allocate() because the
+                    // archetype is empty
+                    let location = archetype.allocate(entity, table_row);
+                    // SAFE: entity index was just allocated
+                    self.entities
+                        .meta
+                        .get_unchecked_mut(entity.id() as usize)
+                        .location = location;
+                    Some(EntityMut::new(self, entity, location))
+                }
+            }
+            AllocAtWithoutReplacement::ExistsWithWrongGeneration => None,
+        }
+    }
+
     /// Spawns a batch of entities with the same component [Bundle] type. Takes a given [Bundle]
     /// iterator and returns a corresponding [Entity] iterator.
     /// This is more efficient than spawning entities and adding components to them individually,
@@ -494,6 +560,47 @@ impl World {
         QueryState::new(self)
     }
 
+    /// Returns [QueryState] for the components with the given [ComponentId]s, which can be defined
+    /// at runtime with [ComponentDescriptor::new_dynamic]. Each component is read or written
+    /// depending on its [DynamicAccess].
+    /// ```
+    /// use bevy_ecs::{
+    ///     component::{ComponentDescriptor, StorageType},
+    ///     query::DynamicAccess,
+    ///     world::World,
+    /// };
+    /// use std::alloc::Layout;
+    ///
+    /// let mut world = World::new();
+    /// // SAFE: u64 values are Send + Sync and don't need to be dropped
+    /// let descriptor = unsafe {
+    ///     ComponentDescriptor::new_dynamic("score", StorageType::Table, Layout::new::<u64>(), |_| {})
+    /// };
+    /// let score = world.register_component(descriptor).unwrap();
+    /// let mut value = 3u64;
+    /// // SAFE: the value is a valid score, and is not used afterwards
+    /// let entity = unsafe {
+    ///     world.spawn().insert_by_id(score, &mut value as *mut u64 as *mut u8).id()
+    /// };
+    ///
+    /// let mut query = world.query_dynamic(&[DynamicAccess::Write(score)]);
+    /// for mut components in query.iter_mut(&mut world) {
+    ///     // SAFE: the first component is a score
+    ///     unsafe { *components.get_mut(0).unwrap().cast::<u64>() *= 2 };
+    /// }
+    ///
+    /// let score = world.entity(entity).get_by_id(score).unwrap();
+    /// assert_eq!(unsafe { *score.cast::<u64>() }, 6);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the components does not exist.
+    #[inline]
+    pub fn query_dynamic(&self, accesses: &[DynamicAccess]) -> QueryState<DynamicComponents, ()> {
+        QueryState::new_with_state(self, DynamicComponentsState::new(self, accesses), ())
+    }
+
     /// Returns an iterator of entities that had components of type `T` removed
     /// since the last call to [World::clear_trackers].
     pub fn removed<T: Component>(&self) -> std::iter::Cloned<std::slice::Iter<'_, Entity>> {
@@ -885,6 +992,104 @@ impl World {
         }
     }
 
+    /// Stores `system` in the world, to run it later with [`World::run_system`] or
+    /// [`Commands::run_system`](crate::system::Commands::run_system). The system is initialized
+    /// the first time it runs, and its commands are applied each time it runs.
+    ///
+    /// ```
+    /// use bevy_ecs::{prelude::*, system::SystemId};
+    ///
+    /// struct Button {
+    ///     on_click: SystemId,
+    /// }
+    /// struct Clicks(u32);
+    ///
+    /// fn count_click(mut clicks: ResMut<Clicks>) {
+    ///     clicks.0 += 1;
+    /// }
+    ///
+    /// fn click_buttons(mut commands: Commands, buttons: Query<&Button>) {
+    ///     for button in buttons.iter() {
+    ///         commands.run_system(button.on_click);
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.insert_resource(Clicks(0));
+    /// let on_click = world.register_system(count_click);
+    /// world.spawn().insert(Button { on_click });
+    ///
+    /// let mut stage = SystemStage::single_threaded().with_system(click_buttons.system());
+    /// stage.run(&mut world);
+    /// assert_eq!(world.get_resource::<Clicks>().unwrap().0, 1);
+    /// ```
+    pub fn register_system<In: 'static, Out: 'static, Param>(
+        &mut self,
+        system: impl IntoSystem<In, Out, Param>,
+    ) -> SystemId {
+        let system = system.system();
+        let id = system.id();
+        let registered: RegisteredSystem<In, Out> = RegisteredSystem::new(Box::new(system));
+        self.get_resource_or_insert_with(SystemRegistry::default)
+            .systems
+            .insert(id, Some(Box::new(registered)));
+        id
+    }
+
+    /// Removes a system registered with [`World::register_system`]. Returns `true` if it was
+    /// registered.
+    pub fn remove_system(&mut self, id: SystemId) -> bool {
+        match self.get_resource_mut::<SystemRegistry>() {
+            Some(mut registry) => registry.systems.remove(&id).is_some(),
+            None => false,
+        }
+    }
+
+    /// Runs a system registered with [`World::register_system`], and applies its commands.
+    pub fn run_system(&mut self, id: SystemId) -> Result<(), RegisteredSystemError> {
+        self.run_system_with_input(id, ())
+    }
+
+    /// Runs a system registered with [`World::register_system`] with the given input, applies its
+    /// commands and returns its output.
+    ///
+    /// A system can't be run while it is already running, for example by one of its own commands.
+    pub fn run_system_with_input<In: 'static, Out: 'static>(
+        &mut self,
+        id: SystemId,
+        input: In,
+    ) -> Result<Out, RegisteredSystemError> {
+        let slot = self
+            .get_resource_mut::<SystemRegistry>()
+            .and_then(|mut registry| registry.systems.get_mut(&id).map(Option::take))
+            .ok_or(RegisteredSystemError::SystemIdNotRegistered(id))?;
+        let boxed = slot.ok_or(RegisteredSystemError::Recursive(id))?;
+        let mut registered = match boxed.downcast::<RegisteredSystem<In, Out>>() {
+            Ok(registered) => registered,
+            Err(boxed) => {
+                self.return_registered_system(id, boxed);
+                return Err(RegisteredSystemError::IncorrectType(id));
+            }
+        };
+        let out = registered.run(input, self);
+        self.return_registered_system(id, registered);
+        Ok(out)
+    }
+
+    /// Puts a system taken out by [`World::run_system_with_input`] back, unless it was removed
+    /// in the meantime
+    fn return_registered_system(
+        &mut self,
+        id: SystemId,
+        system: Box<dyn std::any::Any + Send + Sync>,
+    ) {
+        if let Some(mut registry) = self.get_resource_mut::<SystemRegistry>() {
+            if let Some(slot) = registry.systems.get_mut(&id) {
+                *slot = Some(system);
+            }
+        }
+    }
+
     #[inline]
     pub fn increment_change_tick(&self) -> u32 {
         self.change_tick.fetch_add(1, Ordering::AcqRel)
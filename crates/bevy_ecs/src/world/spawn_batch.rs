@@ -1,9 +1,8 @@
 use crate::{
-    archetype::{Archetype, ArchetypeId, ComponentStatus},
-    bundle::{Bundle, BundleInfo},
-    entity::{Entities, Entity},
-    storage::{SparseSets, Table},
-    world::{add_bundle_to_archetype, World},
+    archetype::ArchetypeId,
+    bundle::{Bundle, BundleId},
+    entity::Entity,
+    world::{add_bundle_to_archetype, insert_hooks, World},
 };
 
 pub struct SpawnBatchIter<'w, I>
@@ -12,12 +11,12 @@ where
     I::Item: Bundle,
 {
     inner: I,
-    entities: &'w mut Entities,
-    archetype: &'w mut Archetype,
-    table: &'w mut Table,
-    sparse_sets: &'w mut SparseSets,
-    bundle_info: &'w BundleInfo,
-    bundle_status: &'w [ComponentStatus],
+    // NOTE: the world is borrowed as a whole, rather than its archetype, table and bundle info,
+    // so that component hooks can run with it between spawns
+    world: &'w mut World,
+    bundle_id: BundleId,
+    archetype_id: ArchetypeId,
+    has_hooks: bool,
     change_tick: u32,
 }
 
@@ -47,26 +46,24 @@ where
                 bundle_info,
             )
         };
-        let (empty_archetype, archetype) = world
-            .archetypes
-            .get_2_mut(ArchetypeId::empty(), archetype_id);
+        let archetype = &mut world.archetypes[archetype_id];
         let table = &mut world.storages.tables[archetype.table_id()];
         archetype.reserve(length);
         table.reserve(length);
         world.entities.reserve(length as u32);
-        let edge = empty_archetype
+        let edge = world.archetypes[ArchetypeId::empty()]
             .edges()
             .get_add_bundle(bundle_info.id())
             .unwrap();
+        let has_hooks =
+            !insert_hooks(&world.components, bundle_info, &edge.bundle_status).is_empty();
         Self {
             inner: iter,
-            entities: &mut world.entities,
-            archetype,
-            table,
-            sparse_sets: &mut world.storages.sparse_sets,
-            bundle_info,
+            bundle_id: bundle_info.id(),
+            archetype_id,
+            has_hooks,
             change_tick: *world.change_tick.get_mut(),
-            bundle_status: &edge.bundle_status,
+            world,
         }
     }
 }
@@ -90,22 +87,40 @@ where
 
     fn next(&mut self) -> Option<Entity> {
         let bundle = self.inner.next()?;
-        let entity = self.entities.alloc();
+        let world = &mut *self.world;
+        let entity = world.entities.alloc();
+        // the bundle info was initialized when creating this iterator
+        let bundle_info = world.bundles.get(self.bundle_id).unwrap();
+        let (empty_archetype, archetype) = world
+            .archetypes
+            .get_2_mut(ArchetypeId::empty(), self.archetype_id);
+        let bundle_status = &empty_archetype
+            .edges()
+            .get_add_bundle(self.bundle_id)
+            .unwrap()
+            .bundle_status;
+        let table = &mut world.storages.tables[archetype.table_id()];
         // SAFE: component values are immediately written to relevant storages (which have been
         // allocated)
         unsafe {
-            let table_row = self.table.allocate(entity);
-            let location = self.archetype.allocate(entity, table_row);
-            self.bundle_info.write_components(
-                self.sparse_sets,
+            let table_row = table.allocate(entity);
+            let location = archetype.allocate(entity, table_row);
+            bundle_info.write_components(
+                &mut world.storages.sparse_sets,
                 entity,
-                self.table,
+                table,
                 table_row,
-                self.bundle_status,
+                bundle_status,
                 bundle,
                 self.change_tick,
             );
-            self.entities.meta[entity.id as usize].location = location;
+            world.entities.meta[entity.id as usize].location = location;
+        }
+        if self.has_hooks {
+            let hooks = insert_hooks(&world.components, bundle_info, bundle_status);
+            for (hook, component_id) in hooks {
+                hook(world, entity, component_id);
+            }
         }
         Some(entity)
     }
@@ -0,0 +1,342 @@
+use crate::converter::convert_gamepad_id;
+use bevy_app::EventReader;
+use bevy_core::Time;
+use bevy_ecs::system::{NonSendMut, Res};
+use bevy_input::gamepad::{Gamepad, GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy_utils::{tracing::warn, Duration, HashMap, Instant};
+use gilrs::{
+    ff::{self, BaseEffect, BaseEffectType, Repeat, Replay},
+    Gilrs,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub(crate) enum RumbleError {
+    #[error("gamepad {0:?} is not connected")]
+    GamepadNotFound(Gamepad),
+    #[error("gilrs error: {0}")]
+    GilrsError(#[from] ff::Error),
+}
+
+/// Plays force feedback effects on gamepads
+pub(crate) trait RumbleBackend: 'static {
+    /// A playing effect, stopped by [`RumbleBackend::stop_rumble`]
+    type Effect: 'static;
+
+    fn play_rumble(
+        &mut self,
+        gamepad: Gamepad,
+        effects: &[BaseEffect],
+        duration: Duration,
+    ) -> Result<Self::Effect, RumbleError>;
+
+    fn stop_rumble(&mut self, effect: Self::Effect);
+}
+
+impl RumbleBackend for Gilrs {
+    type Effect = ff::Effect;
+
+    fn play_rumble(
+        &mut self,
+        gamepad: Gamepad,
+        effects: &[BaseEffect],
+        duration: Duration,
+    ) -> Result<Self::Effect, RumbleError> {
+        let (id, _) = self
+            .gamepads()
+            .find(|(id, _)| convert_gamepad_id(*id) == gamepad)
+            .ok_or(RumbleError::GamepadNotFound(gamepad))?;
+        let mut builder = ff::EffectBuilder::new();
+        for effect in effects {
+            builder.add_effect(*effect);
+        }
+        let effect = builder
+            .gamepads(&[id])
+            .repeat(Repeat::For(duration.into()))
+            .finish(self)?;
+        effect.play()?;
+        Ok(effect)
+    }
+
+    fn stop_rumble(&mut self, effect: Self::Effect) {
+        // the effect is also stopped once dropped, an error only means it already is
+        let _ = effect.stop();
+    }
+}
+
+/// Translates a rumble intensity into the base effects driving each motor of a gamepad. Rumbles
+/// without duration or intensity have no effects.
+pub(crate) fn rumble_base_effects(
+    intensity: GamepadRumbleIntensity,
+    duration: Duration,
+) -> Vec<BaseEffect> {
+    if duration == Duration::from_secs(0) {
+        return Vec::new();
+    }
+    let scheduling = Replay {
+        play_for: duration.into(),
+        ..Default::default()
+    };
+    let magnitude = |intensity: f32| (intensity.clamp(0.0, 1.0) * u16::MAX as f32) as u16;
+    let mut effects = Vec::new();
+    if intensity.strong_motor > 0.0 {
+        effects.push(BaseEffect {
+            kind: BaseEffectType::Strong {
+                magnitude: magnitude(intensity.strong_motor),
+            },
+            scheduling,
+            ..Default::default()
+        });
+    }
+    if intensity.weak_motor > 0.0 {
+        effects.push(BaseEffect {
+            kind: BaseEffectType::Weak {
+                magnitude: magnitude(intensity.weak_motor),
+            },
+            scheduling,
+            ..Default::default()
+        });
+    }
+    effects
+}
+
+struct RunningRumble<E> {
+    deadline: Instant,
+    effect: E,
+}
+
+/// The rumble effects playing on each gamepad
+pub(crate) struct RunningRumbleEffects<B: RumbleBackend> {
+    rumbles: HashMap<Gamepad, Vec<RunningRumble<B::Effect>>>,
+}
+
+impl<B: RumbleBackend> Default for RunningRumbleEffects<B> {
+    fn default() -> Self {
+        Self {
+            rumbles: Default::default(),
+        }
+    }
+}
+
+/// Plays the [`GamepadRumbleRequest`]s, and stops the effects once their duration is over
+pub(crate) fn rumble_system<B: RumbleBackend>(
+    time: Res<Time>,
+    mut backend: NonSendMut<B>,
+    mut running: NonSendMut<RunningRumbleEffects<B>>,
+    mut requests: EventReader<GamepadRumbleRequest>,
+) {
+    let now = time.last_update().unwrap_or_else(|| time.startup());
+    let backend = &mut *backend;
+    for rumbles in running.rumbles.values_mut() {
+        for rumble in std::mem::take(rumbles) {
+            if rumble.deadline <= now {
+                backend.stop_rumble(rumble.effect);
+            } else {
+                rumbles.push(rumble);
+            }
+        }
+    }
+    running.rumbles.retain(|_, rumbles| !rumbles.is_empty());
+
+    for request in requests.iter() {
+        match *request {
+            GamepadRumbleRequest::Add {
+                gamepad,
+                intensity,
+                duration,
+            } => {
+                let effects = rumble_base_effects(intensity, duration);
+                if effects.is_empty() {
+                    continue;
+                }
+                match backend.play_rumble(gamepad, &effects, duration) {
+                    Ok(effect) => running
+                        .rumbles
+                        .entry(gamepad)
+                        .or_default()
+                        .push(RunningRumble {
+                            deadline: now + duration,
+                            effect,
+                        }),
+                    Err(err) => warn!("Failed to rumble {:?}: {}", gamepad, err),
+                }
+            }
+            GamepadRumbleRequest::Stop { gamepad } => {
+                for rumble in running.rumbles.remove(&gamepad).unwrap_or_default() {
+                    backend.stop_rumble(rumble.effect);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::Events;
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        world::World,
+    };
+
+    /// Records the effects played instead of sending them to hardware
+    #[derive(Default)]
+    struct FakeRumbleBackend {
+        connected: Vec<Gamepad>,
+        next_effect: usize,
+        playing: Vec<(usize, Gamepad, Vec<BaseEffect>)>,
+    }
+
+    impl RumbleBackend for FakeRumbleBackend {
+        type Effect = usize;
+
+        fn play_rumble(
+            &mut self,
+            gamepad: Gamepad,
+            effects: &[BaseEffect],
+            _duration: Duration,
+        ) -> Result<Self::Effect, RumbleError> {
+            if !self.connected.contains(&gamepad) {
+                return Err(RumbleError::GamepadNotFound(gamepad));
+            }
+            self.next_effect += 1;
+            self.playing
+                .push((self.next_effect, gamepad, effects.to_vec()));
+            Ok(self.next_effect)
+        }
+
+        fn stop_rumble(&mut self, effect: Self::Effect) {
+            self.playing.retain(|(playing, _, _)| *playing != effect);
+        }
+    }
+
+    fn send(world: &mut World, request: GamepadRumbleRequest) {
+        world
+            .get_resource_mut::<Events<GamepadRumbleRequest>>()
+            .unwrap()
+            .send(request);
+    }
+
+    #[test]
+    fn translates_intensity_to_base_effects() {
+        let duration = Duration::from_millis(500);
+        let effects = rumble_base_effects(
+            GamepadRumbleIntensity {
+                strong_motor: 1.0,
+                weak_motor: 0.5,
+            },
+            duration,
+        );
+        assert_eq!(effects.len(), 2);
+        assert_eq!(
+            effects[0].kind,
+            BaseEffectType::Strong {
+                magnitude: u16::MAX
+            }
+        );
+        assert_eq!(
+            effects[1].kind,
+            BaseEffectType::Weak {
+                magnitude: u16::MAX / 2
+            }
+        );
+        assert_eq!(effects[0].scheduling.play_for, duration.into());
+
+        let effects = rumble_base_effects(GamepadRumbleIntensity::strong_motor(2.0), duration);
+        assert_eq!(
+            effects,
+            vec![BaseEffect {
+                kind: BaseEffectType::Strong {
+                    magnitude: u16::MAX
+                },
+                scheduling: Replay {
+                    play_for: duration.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }]
+        );
+        assert!(rumble_base_effects(GamepadRumbleIntensity::default(), duration).is_empty());
+        assert!(
+            rumble_base_effects(GamepadRumbleIntensity::MAX, Duration::from_secs(0)).is_empty()
+        );
+    }
+
+    #[test]
+    fn plays_and_stops_rumble_effects() {
+        let mut world = World::default();
+        let mut time = Time::default();
+        time.update();
+        world.insert_resource(time);
+        world.insert_resource(Events::<GamepadRumbleRequest>::default());
+        world.insert_non_send(FakeRumbleBackend {
+            connected: vec![Gamepad(0), Gamepad(1)],
+            ..Default::default()
+        });
+        world.insert_non_send(RunningRumbleEffects::<FakeRumbleBackend>::default());
+        let mut stage = SystemStage::single(rumble_system::<FakeRumbleBackend>);
+
+        let long = Duration::from_secs(3600);
+        send(
+            &mut world,
+            GamepadRumbleRequest::Add {
+                gamepad: Gamepad(0),
+                intensity: GamepadRumbleIntensity::MAX,
+                duration: long,
+            },
+        );
+        send(
+            &mut world,
+            GamepadRumbleRequest::Add {
+                gamepad: Gamepad(0),
+                intensity: GamepadRumbleIntensity::WEAK_MAX,
+                duration: Duration::from_nanos(1),
+            },
+        );
+        send(
+            &mut world,
+            GamepadRumbleRequest::Add {
+                gamepad: Gamepad(1),
+                intensity: GamepadRumbleIntensity::STRONG_MAX,
+                duration: long,
+            },
+        );
+        // not connected, only logs a warning
+        send(
+            &mut world,
+            GamepadRumbleRequest::Add {
+                gamepad: Gamepad(2),
+                intensity: GamepadRumbleIntensity::MAX,
+                duration: long,
+            },
+        );
+        stage.run(&mut world);
+        let playing = |world: &World| {
+            world
+                .get_non_send_resource::<FakeRumbleBackend>()
+                .unwrap()
+                .playing
+                .iter()
+                .map(|(_, gamepad, effects)| (*gamepad, effects.len()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            playing(&world),
+            vec![(Gamepad(0), 2), (Gamepad(0), 1), (Gamepad(1), 1)]
+        );
+
+        // the short effect runs out
+        world.get_resource_mut::<Time>().unwrap().update();
+        stage.run(&mut world);
+        assert_eq!(playing(&world), vec![(Gamepad(0), 2), (Gamepad(1), 1)]);
+
+        send(
+            &mut world,
+            GamepadRumbleRequest::Stop {
+                gamepad: Gamepad(0),
+            },
+        );
+        stage.run(&mut world);
+        assert_eq!(playing(&world), vec![(Gamepad(1), 1)]);
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 mod loader;
 pub use loader::*;
 
+use bevy_animation::AnimationClip;
 use bevy_app::prelude::*;
 use bevy_asset::{AddAsset, Handle};
 use bevy_pbr::prelude::StandardMaterial;
@@ -36,6 +37,8 @@ pub struct Gltf {
     pub nodes: Vec<Handle<GltfNode>>,
     pub named_nodes: HashMap<String, Handle<GltfNode>>,
     pub default_scene: Option<Handle<Scene>>,
+    pub animations: Vec<Handle<AnimationClip>>,
+    pub named_animations: HashMap<String, Handle<AnimationClip>>,
 }
 
 #[derive(Debug, Clone, TypeUuid)]
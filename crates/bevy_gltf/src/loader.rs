@@ -1,17 +1,21 @@
 use anyhow::Result;
-use bevy_asset::{
-    AssetIoError, AssetLoader, AssetPath, BoxedFuture, Handle, LoadContext, LoadedAsset,
+use bevy_animation::{
+    AnimationClip, AnimationPlayer, EntityPath, Interpolation, Keyframes, MorphWeights,
+    VariableCurve,
 };
+use bevy_asset::{AssetIoError, AssetLoader, BoxedFuture, Handle, LoadContext, LoadedAsset};
 use bevy_core::Name;
-use bevy_ecs::world::World;
+use bevy_ecs::{entity::Entity, world::World};
 use bevy_log::warn;
-use bevy_math::Mat4;
+use bevy_math::{Mat4, Quat, Vec3};
 use bevy_pbr::prelude::{PbrBundle, StandardMaterial};
 use bevy_render::{
     camera::{
         Camera, CameraProjection, OrthographicProjection, PerspectiveProjection, VisibleEntities,
     },
-    mesh::{Indices, Mesh, VertexAttributeValues},
+    mesh::{
+        Indices, Mesh, SkinnedMesh, SkinnedMeshInverseBindposes, VertexAttributeValues, MAX_JOINTS,
+    },
     pipeline::PrimitiveTopology,
     prelude::{Color, Texture},
     render_graph::base,
@@ -23,10 +27,12 @@ use bevy_transform::{
     prelude::{GlobalTransform, Transform},
 };
 use gltf::{
+    animation::util::ReadOutputs,
     mesh::Mode,
     texture::{MagFilter, MinFilter, WrappingMode},
     Material, Primitive,
 };
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     path::Path,
@@ -54,19 +60,62 @@ pub enum GltfError {
     ImageError(#[from] TextureError),
     #[error("failed to load an asset path: {0}")]
     AssetIoError(#[from] AssetIoError),
+    #[error("missing sampler input for a channel of animation {0}")]
+    MissingAnimationSampler(usize),
+    #[error("joint {joint} of skin {skin} is not a node of scene {scene}")]
+    MissingSkinJoint {
+        skin: usize,
+        joint: usize,
+        scene: usize,
+    },
+    #[error("a channel of animation {animation} has {outputs} outputs for {inputs} timestamps")]
+    InvalidAnimationKeyframes {
+        animation: usize,
+        inputs: usize,
+        outputs: usize,
+    },
 }
 
 /// Loads meshes from GLTF files into Mesh assets
+///
+/// Its settings are [`GltfLoaderSettings`].
 #[derive(Default)]
 pub struct GltfLoader;
 
+/// The settings of [`GltfLoader`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GltfLoaderSettings {
+    /// Whether to load the animations of the file
+    pub load_animations: bool,
+    /// Whether to load the scenes of the file
+    pub load_scenes: bool,
+    /// Whether to compute flat normals for meshes without vertex normals
+    pub compute_flat_normals: bool,
+}
+
+impl Default for GltfLoaderSettings {
+    fn default() -> Self {
+        Self {
+            load_animations: true,
+            load_scenes: true,
+            compute_flat_normals: true,
+        }
+    }
+}
+
 impl AssetLoader for GltfLoader {
+    type Settings = GltfLoaderSettings;
+
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
-        Box::pin(async move { Ok(load_gltf(bytes, load_context).await?) })
+        Box::pin(async move {
+            let settings = load_context.settings::<Self::Settings>()?;
+            Ok(load_gltf(bytes, load_context, &settings).await?)
+        })
     }
 
     fn extensions(&self) -> &[&str] {
@@ -77,10 +126,75 @@ impl AssetLoader for GltfLoader {
 async fn load_gltf<'a, 'b>(
     bytes: &'a [u8],
     load_context: &'a mut LoadContext<'b>,
+    settings: &GltfLoaderSettings,
 ) -> Result<(), GltfError> {
     let gltf = gltf::Gltf::from_slice(bytes)?;
     let buffer_data = load_buffers(&gltf, load_context, load_context.path()).await?;
 
+    let node_paths = node_paths(&gltf);
+    let mut animations = vec![];
+    let mut named_animations = HashMap::new();
+    for animation in gltf.animations().filter(|_| settings.load_animations) {
+        let mut animation_clip = AnimationClip::default();
+        for channel in animation.channels() {
+            let path = match node_paths.get(&channel.target().node().index()) {
+                Some(path) => path,
+                None => {
+                    warn!(
+                        "Animating node {} which is not part of a scene is not supported",
+                        channel.target().node().index()
+                    );
+                    continue;
+                }
+            };
+            let reader = channel.reader(|buffer| Some(&buffer_data[buffer.index()]));
+            let keyframe_timestamps: Vec<f32> = match reader.read_inputs() {
+                Some(inputs) => inputs.collect(),
+                None => return Err(GltfError::MissingAnimationSampler(animation.index())),
+            };
+            let keyframes = match reader.read_outputs() {
+                Some(ReadOutputs::Translations(translations)) => {
+                    Keyframes::Translation(translations.map(Vec3::from).collect())
+                }
+                Some(ReadOutputs::Rotations(rotations)) => Keyframes::Rotation(
+                    rotations
+                        .into_f32()
+                        .map(|rotation| {
+                            Quat::from_xyzw(rotation[0], rotation[1], rotation[2], rotation[3])
+                        })
+                        .collect(),
+                ),
+                Some(ReadOutputs::Scales(scales)) => {
+                    Keyframes::Scale(scales.map(Vec3::from).collect())
+                }
+                Some(ReadOutputs::MorphTargetWeights(weights)) => {
+                    Keyframes::Weights(weights.into_f32().collect())
+                }
+                None => return Err(GltfError::MissingAnimationSampler(animation.index())),
+            };
+            let interpolation = match channel.sampler().interpolation() {
+                gltf::animation::Interpolation::Step => Interpolation::Step,
+                gltf::animation::Interpolation::Linear => Interpolation::Linear,
+                gltf::animation::Interpolation::CubicSpline => Interpolation::CubicSpline,
+            };
+            let curve = VariableCurve {
+                keyframe_timestamps,
+                keyframes,
+                interpolation,
+            };
+            validate_keyframe_count(&curve, animation.index())?;
+            animation_clip.add_curve_to_path(EntityPath::new(path.clone()), curve);
+        }
+        let handle = load_context.set_labeled_asset(
+            &animation_label(&animation),
+            LoadedAsset::new(animation_clip),
+        );
+        if let Some(name) = animation.name() {
+            named_animations.insert(name.to_string(), handle.clone());
+        }
+        animations.push(handle);
+    }
+
     let mut materials = vec![];
     let mut named_materials = HashMap::new();
     let mut linear_textures = HashSet::new();
@@ -155,11 +269,25 @@ async fn load_gltf<'a, 'b>(
                 mesh.set_attribute(Mesh::ATTRIBUTE_COLOR, vertex_attribute);
             }
 
+            if let Some(vertex_attribute) = reader
+                .read_joints(0)
+                .map(|v| VertexAttributeValues::Uint16x4(v.into_u16().collect()))
+            {
+                mesh.set_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, vertex_attribute);
+            }
+
+            if let Some(vertex_attribute) = reader
+                .read_weights(0)
+                .map(|v| VertexAttributeValues::Float32x4(v.into_f32().collect()))
+            {
+                mesh.set_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, vertex_attribute);
+            }
+
             if let Some(indices) = reader.read_indices() {
                 mesh.set_indices(Some(Indices::U32(indices.into_u32().collect())));
             };
 
-            if mesh.attribute(Mesh::ATTRIBUTE_NORMAL).is_none() {
+            if settings.compute_flat_normals && mesh.attribute(Mesh::ATTRIBUTE_NORMAL).is_none() {
                 let vertex_count_before = mesh.count_vertices();
                 mesh.duplicate_vertices();
                 mesh.compute_flat_normals();
@@ -273,26 +401,73 @@ async fn load_gltf<'a, 'b>(
             load_context.set_labeled_asset(&label, LoadedAsset::new(texture));
         });
 
+    let skinned_mesh_inverse_bindposes: Vec<_> = gltf
+        .skins()
+        .map(|gltf_skin| {
+            let reader = gltf_skin.reader(|buffer| Some(&buffer_data[buffer.index()]));
+            let inverse_bindposes: Vec<Mat4> = match reader.read_inverse_bind_matrices() {
+                Some(matrices) => matrices
+                    .map(|matrix| Mat4::from_cols_array_2d(&matrix))
+                    .collect(),
+                // the glTF specification defaults to identity matrices when they are not provided
+                None => vec![Mat4::IDENTITY; gltf_skin.joints().count()],
+            };
+            if inverse_bindposes.len() > MAX_JOINTS {
+                warn!(
+                    "skin {} has {} joints, more than the {} that can be rendered",
+                    gltf_skin.index(),
+                    inverse_bindposes.len(),
+                    MAX_JOINTS
+                );
+            }
+            load_context.set_labeled_asset(
+                &skin_label(&gltf_skin),
+                LoadedAsset::new(SkinnedMeshInverseBindposes::from(inverse_bindposes)),
+            )
+        })
+        .collect();
+
     let mut scenes = vec![];
     let mut named_scenes = HashMap::new();
-    for scene in gltf.scenes() {
+    for scene in gltf.scenes().filter(|_| settings.load_scenes) {
         let mut err = None;
         let mut world = World::default();
-        world
-            .spawn()
+        let mut node_index_to_entity_map = HashMap::new();
+        let mut entity_to_skin_index_map = HashMap::new();
+        let mut scene_root = world.spawn();
+        scene_root
             .insert_bundle((Transform::identity(), GlobalTransform::identity()))
             .with_children(|parent| {
                 for node in scene.nodes() {
-                    let result = load_node(&node, parent, load_context, &buffer_data);
+                    let result = load_node(
+                        &node,
+                        parent,
+                        load_context,
+                        &buffer_data,
+                        &mut node_index_to_entity_map,
+                        &mut entity_to_skin_index_map,
+                    );
                     if result.is_err() {
                         err = Some(result);
                         return;
                     }
                 }
             });
+        if !animations.is_empty() {
+            scene_root.insert(AnimationPlayer::default());
+        }
         if let Some(Err(err)) = err {
             return Err(err);
         }
+
+        for (&entity, &skin_index) in entity_to_skin_index_map.iter() {
+            let skin = gltf.skins().nth(skin_index).unwrap();
+            let joints = skin_joints(&skin, &scene, &node_index_to_entity_map)?;
+            world.entity_mut(entity).insert(SkinnedMesh {
+                inverse_bindposes: skinned_mesh_inverse_bindposes[skin_index].clone(),
+                joints,
+            });
+        }
         let scene_handle = load_context
             .set_labeled_asset(&scene_label(&scene), LoadedAsset::new(Scene::new(world)));
 
@@ -315,6 +490,8 @@ async fn load_gltf<'a, 'b>(
         named_materials,
         nodes,
         named_nodes,
+        animations,
+        named_animations,
     }));
 
     Ok(())
@@ -377,7 +554,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
     let base_color_texture = if let Some(info) = pbr.base_color_texture() {
         // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.get_labeled_asset_path(&label);
         Some(load_context.get_handle(path))
     } else {
         None
@@ -387,7 +564,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         // TODO: handle normal_texture.scale
         // TODO: handle normal_texture.tex_coord() (the *set* index for the right texcoords)
         let label = texture_label(&normal_texture.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.get_labeled_asset_path(&label);
         Some(load_context.get_handle(path))
     } else {
         None
@@ -396,7 +573,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
     let metallic_roughness_texture = if let Some(info) = pbr.metallic_roughness_texture() {
         // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.get_labeled_asset_path(&label);
         Some(load_context.get_handle(path))
     } else {
         None
@@ -406,7 +583,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
         // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
         let label = texture_label(&occlusion_texture.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.get_labeled_asset_path(&label);
         Some(load_context.get_handle(path))
     } else {
         None
@@ -417,7 +594,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
         // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.get_labeled_asset_path(&label);
         Some(load_context.get_handle(path))
     } else {
         None
@@ -447,16 +624,25 @@ fn load_node(
     world_builder: &mut WorldChildBuilder,
     load_context: &mut LoadContext,
     buffer_data: &[Vec<u8>],
+    node_index_to_entity_map: &mut HashMap<usize, Entity>,
+    entity_to_skin_index_map: &mut HashMap<Entity, usize>,
 ) -> Result<(), GltfError> {
     let transform = gltf_node.transform();
     let mut gltf_error = None;
     let mut node = world_builder.spawn_bundle((
         Transform::from_matrix(Mat4::from_cols_array_2d(&transform.matrix())),
         GlobalTransform::identity(),
+        node_name(gltf_node),
     ));
-
-    if let Some(name) = gltf_node.name() {
-        node.insert(Name::new(name.to_string()));
+    node_index_to_entity_map.insert(gltf_node.index(), node.id());
+
+    if let Some(weights) = gltf_node
+        .weights()
+        .or_else(|| gltf_node.mesh().and_then(|mesh| mesh.weights()))
+    {
+        node.insert(MorphWeights {
+            weights: weights.to_vec(),
+        });
     }
 
     // create camera node
@@ -523,22 +709,32 @@ fn load_node(
                 }
 
                 let primitive_label = primitive_label(&mesh, &primitive);
-                let mesh_asset_path =
-                    AssetPath::new_ref(load_context.path(), Some(&primitive_label));
-                let material_asset_path =
-                    AssetPath::new_ref(load_context.path(), Some(&material_label));
-
-                parent.spawn_bundle(PbrBundle {
-                    mesh: load_context.get_handle(mesh_asset_path),
-                    material: load_context.get_handle(material_asset_path),
-                    ..Default::default()
-                });
+                let mesh_asset_path = load_context.get_labeled_asset_path(&primitive_label);
+                let material_asset_path = load_context.get_labeled_asset_path(&material_label);
+
+                let primitive_entity = parent
+                    .spawn_bundle(PbrBundle {
+                        mesh: load_context.get_handle(mesh_asset_path),
+                        material: load_context.get_handle(material_asset_path),
+                        ..Default::default()
+                    })
+                    .id();
+                if let Some(skin) = gltf_node.skin() {
+                    entity_to_skin_index_map.insert(primitive_entity, skin.index());
+                }
             }
         }
 
         // append other nodes
         for child in gltf_node.children() {
-            if let Err(err) = load_node(&child, parent, load_context, buffer_data) {
+            if let Err(err) = load_node(
+                &child,
+                parent,
+                load_context,
+                buffer_data,
+                node_index_to_entity_map,
+                entity_to_skin_index_map,
+            ) {
                 gltf_error = Some(err);
                 return;
             }
@@ -579,6 +775,44 @@ fn scene_label(scene: &gltf::Scene) -> String {
     format!("Scene{}", scene.index())
 }
 
+fn skin_label(skin: &gltf::Skin) -> String {
+    format!("Skin{}", skin.index())
+}
+
+fn animation_label(animation: &gltf::Animation) -> String {
+    format!("Animation{}", animation.index())
+}
+
+/// The [`Name`] of the entity spawned for a node. Unnamed nodes get a name derived from their
+/// index so that animations can still target them.
+fn node_name(node: &gltf::Node) -> Name {
+    match node.name() {
+        Some(name) => Name::new(name.to_string()),
+        None => Name::new(format!("GltfNode{}", node.index())),
+    }
+}
+
+/// The path of [`Name`]s leading to every node that is part of a scene, starting from the root
+/// entity of the scene
+fn node_paths(gltf: &gltf::Gltf) -> HashMap<usize, Vec<Name>> {
+    fn visit(node: gltf::Node, parent_path: &[Name], paths: &mut HashMap<usize, Vec<Name>>) {
+        let mut path = parent_path.to_vec();
+        path.push(node_name(&node));
+        for child in node.children() {
+            visit(child, &path, paths);
+        }
+        paths.insert(node.index(), path);
+    }
+
+    let mut paths = HashMap::new();
+    for scene in gltf.scenes() {
+        for node in scene.nodes() {
+            visit(node, &[], &mut paths);
+        }
+    }
+    paths
+}
+
 fn texture_sampler(texture: &gltf::Texture) -> SamplerDescriptor {
     let gltf_sampler = texture.sampler();
 
@@ -631,6 +865,37 @@ fn texture_address_mode(gltf_address_mode: &gltf::texture::WrappingMode) -> Addr
     }
 }
 
+/// Checks that a curve has the number of outputs its timestamps need, so sampling it can't index
+/// out of bounds
+fn validate_keyframe_count(curve: &VariableCurve, animation: usize) -> Result<(), GltfError> {
+    let values_per_keyframe = match curve.interpolation {
+        Interpolation::CubicSpline => 3,
+        Interpolation::Step | Interpolation::Linear => 1,
+    };
+    let inputs = curve.keyframe_timestamps.len();
+    let expected = inputs * values_per_keyframe;
+    let (outputs, valid) = match &curve.keyframes {
+        Keyframes::Rotation(keyframes) => (keyframes.len(), keyframes.len() == expected),
+        Keyframes::Translation(keyframes) | Keyframes::Scale(keyframes) => {
+            (keyframes.len(), keyframes.len() == expected)
+        }
+        // every keyframe holds one weight per morph target
+        Keyframes::Weights(weights) => (
+            weights.len(),
+            weights.is_empty() || (expected > 0 && weights.len() % expected == 0),
+        ),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(GltfError::InvalidAnimationKeyframes {
+            animation,
+            inputs,
+            outputs,
+        })
+    }
+}
+
 fn get_primitive_topology(mode: Mode) -> Result<PrimitiveTopology, GltfError> {
     match mode {
         Mode::Points => Ok(PrimitiveTopology::PointList),
@@ -765,10 +1030,34 @@ impl<'a> DataUri<'a> {
     }
 }
 
+/// Returns the entities of the joints of a skin in a scene, in the order of its inverse bind
+/// matrices
+fn skin_joints(
+    skin: &gltf::Skin,
+    scene: &gltf::Scene,
+    node_index_to_entity_map: &HashMap<usize, Entity>,
+) -> Result<Vec<Entity>, GltfError> {
+    skin.joints()
+        .map(|joint| {
+            node_index_to_entity_map.get(&joint.index()).copied().ok_or(
+                GltfError::MissingSkinJoint {
+                    skin: skin.index(),
+                    joint: joint.index(),
+                    scene: scene.index(),
+                },
+            )
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
-    use super::resolve_node_hierarchy;
+    use super::{resolve_node_hierarchy, skin_joints, validate_keyframe_count, GltfError};
     use crate::GltfNode;
+    use bevy_animation::{Interpolation, Keyframes, VariableCurve};
+    use bevy_ecs::entity::Entity;
+    use bevy_math::Vec3;
+    use std::collections::HashMap;
 
     impl GltfNode {
         fn empty() -> Self {
@@ -866,4 +1155,80 @@ mod test {
         assert_eq!(result[0].0, "l2");
         assert_eq!(result[0].1.children.len(), 0);
     }
+
+    #[test]
+    fn skin_joint_outside_scene() {
+        let gltf = gltf::Gltf::from_slice(
+            br#"{
+                "asset": { "version": "2.0" },
+                "nodes": [{ "children": [1] }, {}, {}],
+                "skins": [{ "joints": [0, 2, 1] }],
+                "scenes": [{ "nodes": [0] }]
+            }"#,
+        )
+        .unwrap();
+        let skin = gltf.skins().next().unwrap();
+        let scene = gltf.scenes().next().unwrap();
+        let entities = (0..3).map(Entity::new).collect::<Vec<_>>();
+        let mut node_index_to_entity_map = HashMap::new();
+        node_index_to_entity_map.insert(0, entities[0]);
+        node_index_to_entity_map.insert(1, entities[1]);
+
+        // the joints would be out of order if the missing one was skipped
+        assert!(matches!(
+            skin_joints(&skin, &scene, &node_index_to_entity_map),
+            Err(GltfError::MissingSkinJoint {
+                skin: 0,
+                joint: 2,
+                scene: 0
+            })
+        ));
+
+        node_index_to_entity_map.insert(2, entities[2]);
+        assert_eq!(
+            skin_joints(&skin, &scene, &node_index_to_entity_map).unwrap(),
+            vec![entities[0], entities[2], entities[1]]
+        );
+    }
+
+    #[test]
+    fn keyframe_count() {
+        let curve = |timestamps: usize, keyframes, interpolation| VariableCurve {
+            keyframe_timestamps: (0..timestamps).map(|i| i as f32).collect(),
+            keyframes,
+            interpolation,
+        };
+        let is_valid = |curve| validate_keyframe_count(&curve, 0).is_ok();
+
+        assert!(is_valid(curve(
+            2,
+            Keyframes::Translation(vec![Vec3::ZERO; 2]),
+            Interpolation::Linear
+        )));
+        assert!(!is_valid(curve(
+            3,
+            Keyframes::Translation(vec![Vec3::ZERO; 2]),
+            Interpolation::Linear
+        )));
+        assert!(is_valid(curve(
+            2,
+            Keyframes::Scale(vec![Vec3::ONE; 6]),
+            Interpolation::CubicSpline
+        )));
+        assert!(!is_valid(curve(
+            2,
+            Keyframes::Scale(vec![Vec3::ONE; 2]),
+            Interpolation::CubicSpline
+        )));
+        assert!(is_valid(curve(
+            2,
+            Keyframes::Weights(vec![0.0; 4]),
+            Interpolation::Step
+        )));
+        assert!(!is_valid(curve(
+            2,
+            Keyframes::Weights(vec![0.0; 3]),
+            Interpolation::Step
+        )));
+    }
 }
@@ -0,0 +1,611 @@
+use crate::{
+    gamepad::{Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, Gamepads},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    Axis, Input, InputSystem,
+};
+use bevy_app::{App, CoreStage, Plugin};
+use bevy_ecs::{
+    schedule::ParallelSystemDescriptorCoercion,
+    system::{Res, ResMut},
+};
+use bevy_utils::{HashMap, HashSet};
+use std::{hash::Hash, marker::PhantomData};
+
+/// A gameplay action that inputs can be bound to, usually a user enum
+pub trait Action: Copy + Eq + Hash + Send + Sync + 'static {}
+
+impl<T: Copy + Eq + Hash + Send + Sync + 'static> Action for T {}
+
+/// A button of the keyboard, the mouse or a gamepad
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputButton {
+    Keyboard(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+}
+
+impl From<KeyCode> for InputButton {
+    fn from(key_code: KeyCode) -> Self {
+        InputButton::Keyboard(key_code)
+    }
+}
+
+impl From<MouseButton> for InputButton {
+    fn from(mouse_button: MouseButton) -> Self {
+        InputButton::Mouse(mouse_button)
+    }
+}
+
+impl From<GamepadButtonType> for InputButton {
+    fn from(gamepad_button: GamepadButtonType) -> Self {
+        InputButton::Gamepad(gamepad_button)
+    }
+}
+
+/// What triggers an action in an [`ActionMap`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Binding {
+    /// A single button, with a value of `1.0` while pressed
+    Button(InputButton),
+    /// Buttons that must all be pressed together, with a value of `1.0` while they are
+    Chord(Vec<InputButton>),
+    /// A gamepad axis, pressing the action once its value is past
+    /// [`ActionAxisSettings::press_threshold`]
+    GamepadAxis(GamepadAxisType),
+    /// Two buttons acting as an axis: `-1.0` while `negative` is pressed, `1.0` while `positive`
+    /// is pressed
+    VirtualAxis {
+        negative: InputButton,
+        positive: InputButton,
+    },
+}
+
+impl<T: Into<InputButton>> From<T> for Binding {
+    fn from(button: T) -> Self {
+        Binding::Button(button.into())
+    }
+}
+
+impl From<GamepadAxisType> for Binding {
+    fn from(axis: GamepadAxisType) -> Self {
+        Binding::GamepadAxis(axis)
+    }
+}
+
+/// Dead zones applied to the values of axis bindings, like the [`AxisSettings`] of gamepads
+///
+/// [`AxisSettings`]: crate::gamepad::AxisSettings
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionAxisSettings {
+    /// Values closer to `0.0` than this are `0.0`
+    pub deadzone: f32,
+    /// Values further from `0.0` than this are `1.0` or `-1.0`
+    pub livezone: f32,
+    /// Actions bound to an axis are pressed once its value is further from `0.0` than this
+    pub press_threshold: f32,
+}
+
+impl Default for ActionAxisSettings {
+    fn default() -> Self {
+        ActionAxisSettings {
+            deadzone: 0.1,
+            livezone: 0.95,
+            press_threshold: 0.5,
+        }
+    }
+}
+
+impl ActionAxisSettings {
+    pub fn filter(&self, value: f32) -> f32 {
+        if value.abs() <= self.deadzone {
+            0.0
+        } else if value.abs() >= self.livezone {
+            value.signum()
+        } else {
+            value
+        }
+    }
+}
+
+/// Binds inputs to the actions `A`, updating the matching [`ActionState<A>`].
+///
+/// Bindings can be changed at any time, and saved to or loaded from files with the `serialize`
+/// feature.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionMap<A: Action> {
+    bindings: HashMap<A, Vec<Binding>>,
+    axis_settings: ActionAxisSettings,
+    gamepad: Option<Gamepad>,
+}
+
+impl<A: Action> Default for ActionMap<A> {
+    fn default() -> Self {
+        Self {
+            bindings: Default::default(),
+            axis_settings: Default::default(),
+            gamepad: None,
+        }
+    }
+}
+
+impl<A: Action> ActionMap<A> {
+    /// Adds `binding` to the ones triggering `action`
+    pub fn bind(&mut self, action: A, binding: impl Into<Binding>) -> &mut Self {
+        let binding = binding.into();
+        let bindings = self.bindings.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+        self
+    }
+
+    /// Replaces all the bindings of `action` with `binding`
+    pub fn rebind(&mut self, action: A, binding: impl Into<Binding>) -> &mut Self {
+        self.bindings.insert(action, vec![binding.into()]);
+        self
+    }
+
+    /// Removes `binding` from the ones triggering `action`. Returns `true` if it was bound.
+    pub fn unbind(&mut self, action: A, binding: &Binding) -> bool {
+        if let Some(bindings) = self.bindings.get_mut(&action) {
+            let len = bindings.len();
+            bindings.retain(|bound| bound != binding);
+            return bindings.len() != len;
+        }
+        false
+    }
+
+    /// Removes all the bindings of `action`
+    pub fn clear(&mut self, action: A) {
+        self.bindings.remove(&action);
+    }
+
+    /// The bindings triggering `action`
+    pub fn bindings(&self, action: A) -> &[Binding] {
+        self.bindings
+            .get(&action)
+            .map(|bindings| bindings.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// An iterator visiting every action and its bindings
+    pub fn iter(&self) -> impl Iterator<Item = (A, &[Binding])> {
+        self.bindings
+            .iter()
+            .map(|(action, bindings)| (*action, bindings.as_slice()))
+    }
+
+    pub fn axis_settings(&self) -> &ActionAxisSettings {
+        &self.axis_settings
+    }
+
+    pub fn set_axis_settings(&mut self, axis_settings: ActionAxisSettings) {
+        self.axis_settings = axis_settings;
+    }
+
+    /// The gamepad whose buttons and axes are read. When `None`, any connected gamepad can
+    /// trigger actions.
+    pub fn gamepad(&self) -> Option<Gamepad> {
+        self.gamepad
+    }
+
+    pub fn set_gamepad(&mut self, gamepad: Option<Gamepad>) {
+        self.gamepad = gamepad;
+    }
+}
+
+/// The state of the actions `A`, mirroring [`Input`] for their bindings in [`ActionMap<A>`]
+#[derive(Debug)]
+pub struct ActionState<A: Action> {
+    pressed: HashSet<A>,
+    just_pressed: HashSet<A>,
+    just_released: HashSet<A>,
+    values: HashMap<A, f32>,
+}
+
+impl<A: Action> Default for ActionState<A> {
+    fn default() -> Self {
+        Self {
+            pressed: Default::default(),
+            just_pressed: Default::default(),
+            just_released: Default::default(),
+            values: Default::default(),
+        }
+    }
+}
+
+impl<A: Action> ActionState<A> {
+    /// Register a press for `action`, with a value of `1.0`.
+    pub fn press(&mut self, action: A) {
+        self.set(action, true, 1.0);
+    }
+
+    /// Register a release for `action`, with a value of `0.0`.
+    pub fn release(&mut self, action: A) {
+        self.set(action, false, 0.0);
+    }
+
+    /// Check if `action` is pressed.
+    pub fn pressed(&self, action: A) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    /// Check if `action` has just been pressed.
+    pub fn just_pressed(&self, action: A) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    /// Check if `action` has just been released.
+    pub fn just_released(&self, action: A) -> bool {
+        self.just_released.contains(&action)
+    }
+
+    /// The value of `action`, from `-1.0` to `1.0`. Actions bound to buttons are `1.0` while
+    /// pressed, and `0.0` otherwise.
+    pub fn value(&self, action: A) -> f32 {
+        self.values.get(&action).copied().unwrap_or(0.0)
+    }
+
+    /// Clear the "just pressed" state of `action`. Returns true if `action` was "just pressed".
+    pub fn clear_just_pressed(&mut self, action: A) -> bool {
+        self.just_pressed.remove(&action)
+    }
+
+    /// Clear the "just released" state of `action`. Returns true if `action` was "just
+    /// released".
+    pub fn clear_just_released(&mut self, action: A) -> bool {
+        self.just_released.remove(&action)
+    }
+
+    /// Clear the "just pressed" and "just released" states of every action.
+    pub fn clear(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+
+    /// Reset all the states of `action`.
+    pub fn reset(&mut self, action: A) {
+        self.pressed.remove(&action);
+        self.just_pressed.remove(&action);
+        self.just_released.remove(&action);
+        self.values.remove(&action);
+    }
+
+    /// An iterator visiting every pressed action
+    pub fn get_pressed(&self) -> impl ExactSizeIterator<Item = &A> {
+        self.pressed.iter()
+    }
+
+    /// An iterator visiting every just pressed action
+    pub fn get_just_pressed(&self) -> impl ExactSizeIterator<Item = &A> {
+        self.just_pressed.iter()
+    }
+
+    /// An iterator visiting every just released action
+    pub fn get_just_released(&self) -> impl ExactSizeIterator<Item = &A> {
+        self.just_released.iter()
+    }
+
+    fn set(&mut self, action: A, pressed: bool, value: f32) {
+        if pressed {
+            if self.pressed.insert(action) {
+                self.just_pressed.insert(action);
+            }
+        } else if self.pressed.remove(&action) {
+            self.just_released.insert(action);
+        }
+        if value == 0.0 {
+            self.values.remove(&action);
+        } else {
+            self.values.insert(action, value);
+        }
+    }
+}
+
+/// The inputs read by [`action_system`]
+struct ActionInputs<'a> {
+    keyboard: &'a Input<KeyCode>,
+    mouse: &'a Input<MouseButton>,
+    gamepad_buttons: &'a Input<GamepadButton>,
+    gamepad_axes: &'a Axis<GamepadAxis>,
+    gamepads: Vec<Gamepad>,
+}
+
+impl ActionInputs<'_> {
+    fn pressed(&self, button: InputButton) -> bool {
+        match button {
+            InputButton::Keyboard(key_code) => self.keyboard.pressed(key_code),
+            InputButton::Mouse(mouse_button) => self.mouse.pressed(mouse_button),
+            InputButton::Gamepad(button_type) => self.gamepads.iter().any(|gamepad| {
+                self.gamepad_buttons
+                    .pressed(GamepadButton(*gamepad, button_type))
+            }),
+        }
+    }
+
+    /// Whether the binding is pressed, and its value
+    fn read(&self, binding: &Binding, axis_settings: &ActionAxisSettings) -> (bool, f32) {
+        let button_value = |pressed: bool| if pressed { 1.0 } else { 0.0 };
+        match binding {
+            Binding::Button(button) => {
+                let pressed = self.pressed(*button);
+                (pressed, button_value(pressed))
+            }
+            Binding::Chord(buttons) => {
+                let pressed =
+                    !buttons.is_empty() && buttons.iter().all(|button| self.pressed(*button));
+                (pressed, button_value(pressed))
+            }
+            Binding::GamepadAxis(axis_type) => {
+                let value = self
+                    .gamepads
+                    .iter()
+                    .filter_map(|gamepad| self.gamepad_axes.get(GamepadAxis(*gamepad, *axis_type)))
+                    .map(|value| axis_settings.filter(value))
+                    .fold(0.0, |strongest: f32, value| {
+                        if value.abs() > strongest.abs() {
+                            value
+                        } else {
+                            strongest
+                        }
+                    });
+                (value.abs() >= axis_settings.press_threshold, value)
+            }
+            Binding::VirtualAxis { negative, positive } => {
+                let value =
+                    button_value(self.pressed(*positive)) - button_value(self.pressed(*negative));
+                (value != 0.0, value)
+            }
+        }
+    }
+}
+
+/// Updates [`ActionState<A>`] from the inputs bound in [`ActionMap<A>`]
+pub fn action_system<A: Action>(
+    action_map: Res<ActionMap<A>>,
+    mut action_state: ResMut<ActionState<A>>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepads: Res<Gamepads>,
+) {
+    let inputs = ActionInputs {
+        keyboard: &keyboard,
+        mouse: &mouse,
+        gamepad_buttons: &gamepad_buttons,
+        gamepad_axes: &gamepad_axes,
+        gamepads: match action_map.gamepad {
+            Some(gamepad) => vec![gamepad],
+            None => gamepads.iter().copied().collect(),
+        },
+    };
+
+    action_state.clear();
+    let unbound = action_state
+        .pressed
+        .iter()
+        .chain(action_state.values.keys())
+        .filter(|action| !action_map.bindings.contains_key(action))
+        .copied()
+        .collect::<Vec<_>>();
+    for action in unbound {
+        action_state.set(action, false, 0.0);
+    }
+    for (action, bindings) in action_map.bindings.iter() {
+        let mut pressed = false;
+        let mut value: f32 = 0.0;
+        for binding in bindings {
+            let (binding_pressed, binding_value) = inputs.read(binding, &action_map.axis_settings);
+            pressed |= binding_pressed;
+            if binding_value.abs() > value.abs() {
+                value = binding_value;
+            }
+        }
+        action_state.set(*action, pressed, value);
+    }
+}
+
+/// Adds the [`ActionMap<A>`] and [`ActionState<A>`] resources, and updates the state of the
+/// actions `A` after the other inputs
+pub struct ActionPlugin<A: Action>(PhantomData<A>);
+
+impl<A: Action> Default for ActionPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: Action> Plugin for ActionPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionMap<A>>()
+            .init_resource::<ActionState<A>>()
+            .add_system_to_stage(CoreStage::PreUpdate, action_system::<A>.after(InputSystem));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gamepad::{
+        gamepad_event_system, GamepadEvent, GamepadEventRaw, GamepadEventType, GamepadSettings,
+    };
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        world::World,
+    };
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+    enum PlayerAction {
+        Jump,
+        Save,
+        Move,
+    }
+
+    fn setup_world() -> World {
+        let mut world = World::default();
+        world.insert_resource(ActionState::<PlayerAction>::default());
+        world.insert_resource(Input::<KeyCode>::default());
+        world.insert_resource(Input::<MouseButton>::default());
+        world.insert_resource(Input::<GamepadButton>::default());
+        world.insert_resource(Axis::<GamepadAxis>::default());
+        world.insert_resource(Axis::<GamepadButton>::default());
+        world.insert_resource(GamepadSettings::default());
+        world.insert_resource(Gamepads::default());
+        world.insert_resource(bevy_app::Events::<GamepadEvent>::default());
+        world.insert_resource(bevy_app::Events::<GamepadEventRaw>::default());
+        world
+    }
+
+    #[test]
+    fn actions_follow_their_bindings() {
+        let mut world = setup_world();
+        let mut action_map = ActionMap::<PlayerAction>::default();
+        action_map
+            .bind(PlayerAction::Jump, KeyCode::Space)
+            .bind(PlayerAction::Jump, GamepadButtonType::South)
+            .bind(
+                PlayerAction::Save,
+                Binding::Chord(vec![KeyCode::LControl.into(), KeyCode::S.into()]),
+            )
+            .bind(
+                PlayerAction::Move,
+                Binding::VirtualAxis {
+                    negative: KeyCode::Left.into(),
+                    positive: KeyCode::Right.into(),
+                },
+            )
+            .bind(PlayerAction::Move, GamepadAxisType::LeftStickX);
+        world.insert_resource(action_map);
+        let mut stage = SystemStage::single(action_system::<PlayerAction>);
+
+        // the gamepad is connected before the action system first runs
+        let gamepad = Gamepad(0);
+        world
+            .get_resource_mut::<bevy_app::Events<GamepadEventRaw>>()
+            .unwrap()
+            .send(GamepadEventRaw(gamepad, GamepadEventType::Connected));
+        SystemStage::single(gamepad_event_system).run(&mut world);
+
+        world
+            .get_resource_mut::<Input<KeyCode>>()
+            .unwrap()
+            .press(KeyCode::Space);
+        world
+            .get_resource_mut::<Input<KeyCode>>()
+            .unwrap()
+            .press(KeyCode::S);
+        world
+            .get_resource_mut::<Input<KeyCode>>()
+            .unwrap()
+            .press(KeyCode::Left);
+        stage.run(&mut world);
+        let state = world.get_resource::<ActionState<PlayerAction>>().unwrap();
+        assert!(state.pressed(PlayerAction::Jump));
+        assert!(state.just_pressed(PlayerAction::Jump));
+        assert_eq!(state.value(PlayerAction::Jump), 1.0);
+        assert!(!state.pressed(PlayerAction::Save));
+        assert!(state.pressed(PlayerAction::Move));
+        assert_eq!(state.value(PlayerAction::Move), -1.0);
+
+        // the gamepad stick is past the left arrow, and the chord is complete
+        world
+            .get_resource_mut::<Axis<GamepadAxis>>()
+            .unwrap()
+            .set(GamepadAxis(gamepad, GamepadAxisType::LeftStickX), 0.05);
+        world
+            .get_resource_mut::<Input<KeyCode>>()
+            .unwrap()
+            .press(KeyCode::LControl);
+        world
+            .get_resource_mut::<Input<KeyCode>>()
+            .unwrap()
+            .release(KeyCode::Left);
+        stage.run(&mut world);
+        let state = world.get_resource::<ActionState<PlayerAction>>().unwrap();
+        assert!(state.pressed(PlayerAction::Jump));
+        assert!(!state.just_pressed(PlayerAction::Jump));
+        assert!(state.just_pressed(PlayerAction::Save));
+        // within the dead zone
+        assert!(state.just_released(PlayerAction::Move));
+        assert_eq!(state.value(PlayerAction::Move), 0.0);
+
+        world
+            .get_resource_mut::<Axis<GamepadAxis>>()
+            .unwrap()
+            .set(GamepadAxis(gamepad, GamepadAxisType::LeftStickX), 0.7);
+        stage.run(&mut world);
+        let state = world.get_resource::<ActionState<PlayerAction>>().unwrap();
+        assert!(state.just_pressed(PlayerAction::Move));
+        assert_eq!(state.value(PlayerAction::Move), 0.7);
+    }
+
+    #[test]
+    fn actions_can_be_rebound() {
+        let mut world = setup_world();
+        let mut action_map = ActionMap::<PlayerAction>::default();
+        action_map.bind(PlayerAction::Jump, KeyCode::Space);
+        world.insert_resource(action_map);
+        let mut stage = SystemStage::single(action_system::<PlayerAction>);
+
+        world
+            .get_resource_mut::<Input<KeyCode>>()
+            .unwrap()
+            .press(KeyCode::Space);
+        stage.run(&mut world);
+        assert!(world
+            .get_resource::<ActionState<PlayerAction>>()
+            .unwrap()
+            .pressed(PlayerAction::Jump));
+
+        let mut action_map = world.get_resource_mut::<ActionMap<PlayerAction>>().unwrap();
+        action_map.rebind(PlayerAction::Jump, MouseButton::Left);
+        assert_eq!(
+            action_map.bindings(PlayerAction::Jump),
+            &[Binding::Button(InputButton::Mouse(MouseButton::Left))]
+        );
+        stage.run(&mut world);
+        assert!(world
+            .get_resource::<ActionState<PlayerAction>>()
+            .unwrap()
+            .just_released(PlayerAction::Jump));
+
+        let mut action_map = world.get_resource_mut::<ActionMap<PlayerAction>>().unwrap();
+        assert!(action_map.unbind(PlayerAction::Jump, &MouseButton::Left.into()));
+        assert!(!action_map.unbind(PlayerAction::Jump, &MouseButton::Left.into()));
+        assert!(action_map.bindings(PlayerAction::Jump).is_empty());
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn action_map_round_trips_through_ron() {
+        let mut action_map = ActionMap::<PlayerAction>::default();
+        action_map
+            .bind(PlayerAction::Jump, KeyCode::Space)
+            .bind(
+                PlayerAction::Move,
+                Binding::VirtualAxis {
+                    negative: KeyCode::A.into(),
+                    positive: KeyCode::D.into(),
+                },
+            )
+            .set_gamepad(Some(Gamepad(1)));
+        let serialized = ron::to_string(&action_map).unwrap();
+        let deserialized: ActionMap<PlayerAction> = ron::from_str(&serialized).unwrap();
+        assert_eq!(
+            deserialized.bindings(PlayerAction::Jump),
+            action_map.bindings(PlayerAction::Jump)
+        );
+        assert_eq!(
+            deserialized.bindings(PlayerAction::Move),
+            action_map.bindings(PlayerAction::Move)
+        );
+        assert_eq!(deserialized.gamepad(), Some(Gamepad(1)));
+    }
+}
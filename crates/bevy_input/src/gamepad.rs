@@ -1,12 +1,28 @@
 use crate::{Axis, Input};
 use bevy_app::{EventReader, EventWriter};
 use bevy_ecs::system::{Res, ResMut};
-use bevy_utils::HashMap;
+use bevy_utils::{Duration, HashMap, HashSet};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gamepad(pub usize);
 
+/// The gamepads that are connected, updated by [`gamepad_event_system`]
+#[derive(Debug, Default)]
+pub struct Gamepads {
+    gamepads: HashSet<Gamepad>,
+}
+
+impl Gamepads {
+    pub fn contains(&self, gamepad: &Gamepad) -> bool {
+        self.gamepads.contains(gamepad)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Gamepad> {
+        self.gamepads.iter()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum GamepadEventType {
@@ -24,6 +40,80 @@ pub struct GamepadEvent(pub Gamepad, pub GamepadEventType);
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct GamepadEventRaw(pub Gamepad, pub GamepadEventType);
 
+/// The intensity of the two rumble motors of a gamepad, from `0.0` (off) to `1.0` (full speed).
+///
+/// The strong motor is the low frequency one, usually on the left of the gamepad, and the weak
+/// motor the high frequency one.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadRumbleIntensity {
+    pub strong_motor: f32,
+    pub weak_motor: f32,
+}
+
+impl GamepadRumbleIntensity {
+    /// Both motors at full speed
+    pub const MAX: Self = GamepadRumbleIntensity {
+        strong_motor: 1.0,
+        weak_motor: 1.0,
+    };
+
+    /// Only the weak motor, at full speed
+    pub const WEAK_MAX: Self = GamepadRumbleIntensity {
+        strong_motor: 0.0,
+        weak_motor: 1.0,
+    };
+
+    /// Only the strong motor, at full speed
+    pub const STRONG_MAX: Self = GamepadRumbleIntensity {
+        strong_motor: 1.0,
+        weak_motor: 0.0,
+    };
+
+    /// Only the weak motor, at `intensity`
+    pub fn weak_motor(intensity: f32) -> Self {
+        Self {
+            strong_motor: 0.0,
+            weak_motor: intensity,
+        }
+    }
+
+    /// Only the strong motor, at `intensity`
+    pub fn strong_motor(intensity: f32) -> Self {
+        Self {
+            strong_motor: intensity,
+            weak_motor: 0.0,
+        }
+    }
+}
+
+/// Asks the gamepad backend to rumble a gamepad.
+///
+/// Rumble effects added to a gamepad play together until they run out or are stopped. Gamepads
+/// that don't support force feedback ignore these requests.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GamepadRumbleRequest {
+    /// Plays a rumble effect for `duration`, on top of the ones already playing
+    Add {
+        gamepad: Gamepad,
+        intensity: GamepadRumbleIntensity,
+        duration: Duration,
+    },
+    /// Stops all the rumble effects playing on the gamepad
+    Stop { gamepad: Gamepad },
+}
+
+impl GamepadRumbleRequest {
+    /// The gamepad targeted by the request
+    pub fn gamepad(&self) -> Gamepad {
+        match self {
+            GamepadRumbleRequest::Add { gamepad, .. } | GamepadRumbleRequest::Stop { gamepad } => {
+                *gamepad
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum GamepadButtonType {
@@ -202,6 +292,7 @@ impl ButtonAxisSettings {
 }
 
 pub fn gamepad_event_system(
+    mut gamepads: ResMut<Gamepads>,
     mut button_input: ResMut<Input<GamepadButton>>,
     mut axis: ResMut<Axis<GamepadAxis>>,
     mut button_axis: ResMut<Axis<GamepadButton>>,
@@ -214,6 +305,7 @@ pub fn gamepad_event_system(
         let (gamepad, event) = (event.0, &event.1);
         match event {
             GamepadEventType::Connected => {
+                gamepads.gamepads.insert(gamepad);
                 events.send(GamepadEvent(gamepad, event.clone()));
                 for button_type in ALL_BUTTON_TYPES.iter() {
                     let gamepad_button = GamepadButton(gamepad, *button_type);
@@ -225,6 +317,7 @@ pub fn gamepad_event_system(
                 }
             }
             GamepadEventType::Disconnected => {
+                gamepads.gamepads.remove(&gamepad);
                 events.send(GamepadEvent(gamepad, event.clone()));
                 for button_type in ALL_BUTTON_TYPES.iter() {
                     let gamepad_button = GamepadButton(gamepad, *button_type);
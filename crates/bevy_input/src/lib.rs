@@ -1,3 +1,4 @@
+pub mod action;
 mod axis;
 pub mod gamepad;
 mod input;
@@ -13,9 +14,10 @@ pub use input::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
+        action::{ActionMap, ActionPlugin, ActionState, Binding, InputButton},
         gamepad::{
             Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, GamepadEvent,
-            GamepadEventType,
+            GamepadEventType, GamepadRumbleIntensity, GamepadRumbleRequest, Gamepads,
         },
         keyboard::KeyCode,
         mouse::MouseButton,
@@ -31,7 +33,7 @@ use touch::{touch_screen_input_system, TouchInput, Touches};
 
 use gamepad::{
     gamepad_event_system, GamepadAxis, GamepadButton, GamepadEvent, GamepadEventRaw,
-    GamepadSettings,
+    GamepadRumbleRequest, GamepadSettings, Gamepads,
 };
 
 /// Adds keyboard and mouse input to an App
@@ -63,7 +65,9 @@ impl Plugin for InputPlugin {
             // gamepad
             .add_event::<GamepadEvent>()
             .add_event::<GamepadEventRaw>()
+            .add_event::<GamepadRumbleRequest>()
             .init_resource::<GamepadSettings>()
+            .init_resource::<Gamepads>()
             .init_resource::<Input<GamepadButton>>()
             .init_resource::<Axis<GamepadAxis>>()
             .init_resource::<Axis<GamepadButton>>()
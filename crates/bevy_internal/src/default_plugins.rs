@@ -1,3 +1,4 @@
+use bevy_animation::AnimationPlugin;
 use bevy_app::{PluginGroup, PluginGroupBuilder};
 
 use bevy_app::ScheduleRunnerPlugin;
@@ -14,6 +15,8 @@ use bevy_input::InputPlugin;
 use bevy_log::LogPlugin;
 #[cfg(feature = "bevy_pbr")]
 use bevy_pbr::PbrPlugin;
+#[cfg(feature = "bevy_picking")]
+use bevy_picking::PickingPlugin;
 #[cfg(feature = "bevy_render")]
 use bevy_render::RenderPlugin;
 use bevy_scene::ScenePlugin;
@@ -39,10 +42,12 @@ use bevy_winit::WinitPlugin;
 /// * [`WindowPlugin`]
 /// * [`AssetPlugin`]
 /// * [`ScenePlugin`]
+/// * [`AnimationPlugin`]
 /// * [`RenderPlugin`] - with feature `bevy_render`
 /// * [`SpritePlugin`] - with feature `bevy_sprite`
 /// * [`PbrPlugin`] - with feature `bevy_pbr`
 /// * [`UiPlugin`] - with feature `bevy_ui`
+/// * [`PickingPlugin`] - with feature `bevy_picking`
 /// * [`TextPlugin`] - with feature `bevy_text`
 /// * [`AudioPlugin`] - with feature `bevy_audio`
 /// * [`GilrsPlugin`] - with feature `bevy_gilrs`
@@ -61,6 +66,7 @@ impl PluginGroup for DefaultPlugins {
         group.add(WindowPlugin::default());
         group.add(AssetPlugin::default());
         group.add(ScenePlugin::default());
+        group.add(AnimationPlugin::default());
 
         #[cfg(feature = "bevy_render")]
         group.add(RenderPlugin::default());
@@ -74,6 +80,9 @@ impl PluginGroup for DefaultPlugins {
         #[cfg(feature = "bevy_ui")]
         group.add(UiPlugin::default());
 
+        #[cfg(feature = "bevy_picking")]
+        group.add(PickingPlugin::default());
+
         #[cfg(feature = "bevy_text")]
         group.add(TextPlugin::default());
 
@@ -12,14 +12,14 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         entity::*,
-        light::{DirectionalLight, PointLight},
+        light::{DirectionalLight, NotShadowCaster, NotShadowReceiver, PointLight, SpotLight},
         material::StandardMaterial,
     };
 }
 
 use bevy_app::prelude::*;
 use bevy_asset::{AddAsset, Assets, Handle};
-use bevy_render::{prelude::Color, shader};
+use bevy_render::{mesh::SkinnedMeshJoints, prelude::Color, shader};
 use material::StandardMaterial;
 use render_graph::add_pbr_graph;
 
@@ -31,10 +31,22 @@ impl Plugin for PbrPlugin {
     fn build(&self, app: &mut App) {
         app.add_asset::<StandardMaterial>()
             .register_type::<PointLight>()
+            .register_type::<DirectionalLight>()
+            .register_type::<SpotLight>()
+            .register_type::<NotShadowCaster>()
+            .register_type::<NotShadowReceiver>()
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 shader::asset_shader_defs_system::<StandardMaterial>,
             )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                shader::shader_defs_system::<NotShadowReceiver>,
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                shader::shader_defs_system::<SkinnedMeshJoints>,
+            )
             .init_resource::<AmbientLight>();
         add_pbr_graph(&mut app.world);
 
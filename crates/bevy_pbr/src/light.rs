@@ -1,10 +1,18 @@
 use bevy_core::{Pod, Zeroable};
 use bevy_ecs::reflect::ReflectComponent;
-use bevy_math::Vec3;
+use bevy_math::{Mat4, Vec3};
 use bevy_reflect::Reflect;
-use bevy_render::color::Color;
+use bevy_render::{
+    camera::{Camera, CameraProjection, OrthographicProjection},
+    color::Color,
+    render_graph::base,
+    shader::{ShaderDefIterator, ShaderDefs},
+};
 use bevy_transform::components::GlobalTransform;
 
+/// The near plane used when rendering the shadow map faces of a [`PointLight`]
+pub const POINT_LIGHT_SHADOW_NEAR: f32 = 0.1;
+
 /// A point light
 #[derive(Debug, Clone, Copy, Reflect)]
 #[reflect(Component)]
@@ -13,6 +21,14 @@ pub struct PointLight {
     pub intensity: f32,
     pub range: f32,
     pub radius: f32,
+    /// Whether this light renders a shadow cube map. Only meshes within `range` cast shadows.
+    pub shadows_enabled: bool,
+    /// Distance, in world units, by which receivers are moved towards the light before being
+    /// compared against the shadow map. Increase it to fight "shadow acne".
+    pub shadow_depth_bias: f32,
+    /// Distance, in world units, by which receivers are moved along their normal before being
+    /// compared against the shadow map
+    pub shadow_normal_bias: f32,
 }
 
 impl Default for PointLight {
@@ -22,10 +38,145 @@ impl Default for PointLight {
             intensity: 200.0,
             range: 20.0,
             radius: 0.0,
+            shadows_enabled: false,
+            shadow_depth_bias: PointLight::DEFAULT_SHADOW_DEPTH_BIAS,
+            shadow_normal_bias: PointLight::DEFAULT_SHADOW_NORMAL_BIAS,
+        }
+    }
+}
+
+impl PointLight {
+    pub const DEFAULT_SHADOW_DEPTH_BIAS: f32 = 0.02;
+    pub const DEFAULT_SHADOW_NORMAL_BIAS: f32 = 0.05;
+
+    /// The view projection used to render the given face of this light's shadow cube map.
+    ///
+    /// Faces are ordered +X, -X, +Y, -Y, +Z, -Z, matching `pbr.frag`.
+    pub fn shadow_view_projection(&self, position: Vec3, face: usize) -> Mat4 {
+        let (forward, up) = cube_map_face(face);
+        let view = Mat4::look_at_rh(position, position + forward, up);
+        let projection = Mat4::perspective_rh(
+            std::f32::consts::FRAC_PI_2,
+            1.0,
+            POINT_LIGHT_SHADOW_NEAR,
+            self.range,
+        );
+        projection * view
+    }
+}
+
+/// The (forward, up) directions of a face of a point light shadow cube map
+fn cube_map_face(face: usize) -> (Vec3, Vec3) {
+    match face {
+        0 => (Vec3::X, Vec3::Y),
+        1 => (-Vec3::X, Vec3::Y),
+        2 => (Vec3::Y, -Vec3::Z),
+        3 => (-Vec3::Y, Vec3::Z),
+        4 => (Vec3::Z, Vec3::Y),
+        5 => (-Vec3::Z, Vec3::Y),
+        _ => panic!("A cube map only has 6 faces, got face {}.", face),
+    }
+}
+
+/// A spot light, emitting light in a cone along the forward (-Z) direction of its transform.
+///
+/// The light is at full intensity within `inner_angle` of that direction and fades out towards
+/// `outer_angle`. Both angles are in radians, measured from the center of the cone.
+#[derive(Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct SpotLight {
+    pub color: Color,
+    pub intensity: f32,
+    pub range: f32,
+    pub radius: f32,
+    pub inner_angle: f32,
+    pub outer_angle: f32,
+}
+
+impl Default for SpotLight {
+    fn default() -> Self {
+        SpotLight {
+            color: Color::rgb(1.0, 1.0, 1.0),
+            intensity: 200.0,
+            range: 20.0,
+            radius: 0.0,
+            inner_angle: 0.0,
+            outer_angle: std::f32::consts::FRAC_PI_4,
         }
     }
 }
 
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub(crate) struct SpotLightUniform {
+    pub pos: [f32; 4],
+    pub color: [f32; 4],
+    // storing as a `[f32; 4]` for memory alignement
+    pub light_params: [f32; 4],
+    pub direction: [f32; 4],
+    // cone attenuation scale and offset
+    pub spot_params: [f32; 4],
+}
+
+impl SpotLightUniform {
+    pub fn new(light: &SpotLight, global_transform: &GlobalTransform) -> SpotLightUniform {
+        let (x, y, z) = global_transform.translation.into();
+        let (dir_x, dir_y, dir_z) = global_transform.forward().normalize().into();
+
+        // premultiply color by intensity
+        // we don't use the alpha at all, so no reason to multiply only [0..3]
+        let color: [f32; 4] = (light.color * light.intensity).into();
+
+        // precompute the smooth cone falloff, see
+        // https://google.github.io/filament/Filament.html#lighting/directlighting/punctuallights
+        let outer_angle = light.outer_angle.max(0.0);
+        let inner_angle = light.inner_angle.clamp(0.0, outer_angle);
+        let cos_outer = outer_angle.cos();
+        let scale = 1.0 / (inner_angle.cos() - cos_outer).max(1e-4);
+        let offset = -cos_outer * scale;
+
+        SpotLightUniform {
+            pos: [x, y, z, 1.0],
+            color,
+            light_params: [
+                1.0 / (light.range * light.range),
+                light.radius,
+                light.range,
+                0.0,
+            ],
+            direction: [dir_x, dir_y, dir_z, 0.0],
+            spot_params: [scale, offset, 0.0, 0.0],
+        }
+    }
+}
+
+/// Prevents the entity's mesh from casting shadows
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct NotShadowCaster;
+
+/// Prevents the entity's mesh from receiving shadows
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct NotShadowReceiver;
+
+impl ShaderDefs for NotShadowReceiver {
+    fn shader_defs_len(&self) -> usize {
+        1
+    }
+
+    fn get_shader_def(&self, index: usize) -> Option<&str> {
+        match index {
+            0 => Some("NOT_SHADOW_RECEIVER"),
+            _ => None,
+        }
+    }
+
+    fn iter_shader_defs(&self) -> ShaderDefIterator<'_> {
+        ShaderDefIterator::new(self)
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
 pub(crate) struct PointLightUniform {
@@ -33,10 +184,16 @@ pub(crate) struct PointLightUniform {
     pub color: [f32; 4],
     // storing as a `[f32; 4]` for memory alignement
     pub light_params: [f32; 4],
+    // shadow map tile (or -1 without shadows), depth bias, normal bias
+    pub shadow_params: [f32; 4],
 }
 
 impl PointLightUniform {
-    pub fn new(light: &PointLight, global_transform: &GlobalTransform) -> PointLightUniform {
+    pub fn new(
+        light: &PointLight,
+        global_transform: &GlobalTransform,
+        shadow_tile: Option<usize>,
+    ) -> PointLightUniform {
         let (x, y, z) = global_transform.translation.into();
 
         // premultiply color by intensity
@@ -46,7 +203,17 @@ impl PointLightUniform {
         PointLightUniform {
             pos: [x, y, z, 1.0],
             color,
-            light_params: [1.0 / (light.range * light.range), light.radius, 0.0, 0.0],
+            light_params: [
+                1.0 / (light.range * light.range),
+                light.radius,
+                light.range,
+                0.0,
+            ],
+            shadow_params: shadow_params(
+                shadow_tile,
+                light.shadow_depth_bias,
+                light.shadow_normal_bias,
+            ),
         }
     }
 }
@@ -77,21 +244,37 @@ impl PointLightUniform {
 /// | 32,000–100,000    | Direct sunlight                                |
 ///
 /// Source: [Wikipedia](https://en.wikipedia.org/wiki/Lux)
+///
+/// When `shadows_enabled` is set, shadows are cast by meshes within `shadow_projection`, an
+/// orthographic volume looking along the light's direction. The volume is centered on the 3D
+/// camera, or on the world origin when there is none, see [`directional_shadow_center`].
 #[derive(Debug, Clone, Copy, Reflect)]
 #[reflect(Component)]
 pub struct DirectionalLight {
     pub color: Color,
     pub illuminance: f32,
     direction: Vec3,
+    pub shadows_enabled: bool,
+    pub shadow_projection: OrthographicProjection,
+    /// Distance, in world units, by which receivers are moved towards the light before being
+    /// compared against the shadow map. Increase it to fight "shadow acne".
+    pub shadow_depth_bias: f32,
+    /// Distance, in world units, by which receivers are moved along their normal before being
+    /// compared against the shadow map
+    pub shadow_normal_bias: f32,
 }
 
 impl DirectionalLight {
+    pub const DEFAULT_SHADOW_DEPTH_BIAS: f32 = 0.02;
+    pub const DEFAULT_SHADOW_NORMAL_BIAS: f32 = 0.05;
+
     /// Create a new directional light component.
     pub fn new(color: Color, illuminance: f32, direction: Vec3) -> Self {
         DirectionalLight {
             color,
             illuminance,
             direction: direction.normalize(),
+            ..Default::default()
         }
     }
 
@@ -103,6 +286,19 @@ impl DirectionalLight {
     pub fn get_direction(&self) -> Vec3 {
         self.direction
     }
+
+    /// The view projection used to render this light's shadow map, with the shadow volume
+    /// centered on `center`
+    pub fn shadow_view_projection(&self, center: Vec3) -> Mat4 {
+        // avoid a degenerate view matrix when looking straight up or down
+        let up = if self.direction.abs().abs_diff_eq(Vec3::Y, 1e-4) {
+            Vec3::Z
+        } else {
+            Vec3::Y
+        };
+        let view = Mat4::look_at_rh(center, center + self.direction, up);
+        self.shadow_projection.get_projection_matrix() * view
+    }
 }
 
 impl Default for DirectionalLight {
@@ -111,19 +307,50 @@ impl Default for DirectionalLight {
             color: Color::rgb(1.0, 1.0, 1.0),
             illuminance: 100000.0,
             direction: Vec3::new(0.0, -1.0, 0.0),
+            shadows_enabled: false,
+            shadow_projection: OrthographicProjection {
+                left: -10.0,
+                right: 10.0,
+                bottom: -10.0,
+                top: 10.0,
+                near: -50.0,
+                far: 50.0,
+                ..Default::default()
+            },
+            shadow_depth_bias: DirectionalLight::DEFAULT_SHADOW_DEPTH_BIAS,
+            shadow_normal_bias: DirectionalLight::DEFAULT_SHADOW_NORMAL_BIAS,
         }
     }
 }
 
+/// The point the shadow volumes of [`DirectionalLight`]s are centered on: the position of the 3D
+/// camera, or the world origin when there is none
+pub fn directional_shadow_center<'a>(
+    cameras: impl Iterator<Item = (&'a Camera, &'a GlobalTransform)>,
+) -> Vec3 {
+    cameras
+        .filter(|(camera, _)| camera.name.as_deref() == Some(base::camera::CAMERA_3D))
+        .map(|(_, global_transform)| global_transform.translation)
+        .next()
+        .unwrap_or(Vec3::ZERO)
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
 pub(crate) struct DirectionalLightUniform {
     pub dir: [f32; 4],
     pub color: [f32; 4],
+    pub view_projection: [[f32; 4]; 4],
+    // shadow map tile (or -1 without shadows), depth bias, normal bias
+    pub shadow_params: [f32; 4],
 }
 
 impl DirectionalLightUniform {
-    pub fn new(light: &DirectionalLight) -> DirectionalLightUniform {
+    pub fn new(
+        light: &DirectionalLight,
+        shadow_tile: Option<usize>,
+        shadow_center: Vec3,
+    ) -> DirectionalLightUniform {
         // direction is negated to be ready for N.L
         let dir: [f32; 4] = [
             -light.direction.x,
@@ -148,10 +375,26 @@ impl DirectionalLightUniform {
         // we don't use the alpha at all, so no reason to multiply only [0..3]
         let color: [f32; 4] = (light.color * intensity).into();
 
-        DirectionalLightUniform { dir, color }
+        DirectionalLightUniform {
+            dir,
+            color,
+            view_projection: light
+                .shadow_view_projection(shadow_center)
+                .to_cols_array_2d(),
+            shadow_params: shadow_params(
+                shadow_tile,
+                light.shadow_depth_bias,
+                light.shadow_normal_bias,
+            ),
+        }
     }
 }
 
+fn shadow_params(shadow_tile: Option<usize>, depth_bias: f32, normal_bias: f32) -> [f32; 4] {
+    let tile = shadow_tile.map_or(-1.0, |tile| tile as f32);
+    [tile, depth_bias, normal_bias, 0.0]
+}
+
 // Ambient light color.
 #[derive(Debug)]
 pub struct AmbientLight {
@@ -168,3 +411,147 @@ impl Default for AmbientLight {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mirrors `point_light_shadow` in pbr.frag, returning the face, uv and depth of `offset`
+    fn shader_cube_map_lookup(offset: Vec3, range: f32) -> (usize, f32, f32, f32) {
+        let abs = offset.abs();
+        let (face, forward, up) = if abs.x >= abs.y && abs.x >= abs.z {
+            let sign = if offset.x > 0.0 { 1.0 } else { -1.0 };
+            (if offset.x > 0.0 { 0 } else { 1 }, Vec3::X * sign, Vec3::Y)
+        } else if abs.y >= abs.z {
+            let sign = if offset.y > 0.0 { 1.0 } else { -1.0 };
+            (
+                if offset.y > 0.0 { 2 } else { 3 },
+                Vec3::Y * sign,
+                -Vec3::Z * sign,
+            )
+        } else {
+            let sign = if offset.z > 0.0 { 1.0 } else { -1.0 };
+            (if offset.z > 0.0 { 4 } else { 5 }, Vec3::Z * sign, Vec3::Y)
+        };
+        let right = forward.cross(up);
+        let up = right.cross(forward);
+        let d = offset.dot(forward);
+        let near = POINT_LIGHT_SHADOW_NEAR;
+        (
+            face,
+            offset.dot(right) / d * 0.5 + 0.5,
+            0.5 - offset.dot(up) / d * 0.5,
+            range * (d - near) / ((range - near) * d),
+        )
+    }
+
+    #[test]
+    fn point_light_faces_match_shader_projection() {
+        let light = PointLight {
+            range: 10.0,
+            ..Default::default()
+        };
+        let position = Vec3::new(1.0, 2.0, 3.0);
+        let offsets = [
+            Vec3::new(2.0, 1.0, 0.5),
+            Vec3::new(-2.0, 1.0, -0.5),
+            Vec3::new(0.5, 2.0, 1.0),
+            Vec3::new(-0.5, -2.0, 1.0),
+            Vec3::new(0.5, 1.0, 2.0),
+            Vec3::new(-0.5, 1.5, -2.0),
+        ];
+        for (expected_face, offset) in offsets.iter().enumerate() {
+            let (face, u, v, depth) = shader_cube_map_lookup(*offset, light.range);
+            assert_eq!(face, expected_face);
+            let world_position = (position + *offset).extend(1.0);
+            let clip = light.shadow_view_projection(position, face) * world_position;
+            let ndc = clip.truncate() / clip.w;
+            assert!((ndc.x * 0.5 + 0.5 - u).abs() < 1e-5, "face {}", face);
+            assert!((0.5 - ndc.y * 0.5 - v).abs() < 1e-5, "face {}", face);
+            assert!((ndc.z - depth).abs() < 1e-5, "face {}", face);
+        }
+    }
+
+    #[test]
+    fn shadow_tile_is_uploaded_with_light() {
+        let light = DirectionalLight {
+            shadows_enabled: true,
+            ..Default::default()
+        };
+        let uniform = DirectionalLightUniform::new(&light, Some(0), Vec3::ZERO);
+        assert_eq!(
+            uniform.shadow_params,
+            [
+                0.0,
+                DirectionalLight::DEFAULT_SHADOW_DEPTH_BIAS,
+                DirectionalLight::DEFAULT_SHADOW_NORMAL_BIAS,
+                0.0
+            ]
+        );
+        let uniform =
+            PointLightUniform::new(&PointLight::default(), &GlobalTransform::identity(), None);
+        assert_eq!(uniform.shadow_params[0], -1.0);
+    }
+
+    #[test]
+    fn directional_shadow_volume_follows_camera() {
+        let camera = |name: &str| Camera {
+            name: Some(name.to_string()),
+            ..Default::default()
+        };
+        let cameras = [
+            (camera(base::camera::CAMERA_2D), GlobalTransform::identity()),
+            (
+                camera(base::camera::CAMERA_3D),
+                GlobalTransform::from_xyz(100.0, 5.0, -40.0),
+            ),
+        ];
+        let center = directional_shadow_center(cameras.iter().map(|(c, t)| (c, t)));
+        assert_eq!(center, Vec3::new(100.0, 5.0, -40.0));
+        assert_eq!(
+            directional_shadow_center(cameras[..1].iter().map(|(c, t)| (c, t))),
+            Vec3::ZERO
+        );
+
+        // a point next to the camera is inside the shadow volume, even far from the origin
+        let light = DirectionalLight::new(Color::WHITE, 1.0, Vec3::new(-1.0, -1.0, 0.0));
+        let clip = light.shadow_view_projection(center) * (center + Vec3::ONE).extend(1.0);
+        let ndc = clip.truncate() / clip.w;
+        assert!(ndc.abs().cmple(Vec3::ONE).all(), "{:?}", ndc);
+        let clip = light.shadow_view_projection(Vec3::ZERO) * (center + Vec3::ONE).extend(1.0);
+        let ndc = clip.truncate() / clip.w;
+        assert!(!ndc.abs().cmple(Vec3::ONE).all());
+    }
+
+    #[test]
+    fn spot_light_cone_attenuation() {
+        let light = SpotLight {
+            inner_angle: 0.2,
+            outer_angle: 0.4,
+            ..Default::default()
+        };
+        let uniform = SpotLightUniform::new(
+            &light,
+            &GlobalTransform::from_translation(Vec3::ONE).looking_at(Vec3::ZERO, Vec3::Y),
+        );
+        let direction = Vec3::new(
+            uniform.direction[0],
+            uniform.direction[1],
+            uniform.direction[2],
+        );
+        assert!(direction.abs_diff_eq(-Vec3::ONE.normalize(), 1e-5));
+        // mirrors the cone attenuation in pbr.frag
+        let attenuation = |angle: f32| {
+            let attenuation =
+                (angle.cos() * uniform.spot_params[0] + uniform.spot_params[1]).clamp(0.0, 1.0);
+            attenuation * attenuation
+        };
+        assert_eq!(attenuation(0.1), 1.0);
+        assert!((attenuation(0.2) - 1.0).abs() < 1e-4);
+        assert!(attenuation(0.3) > 0.0 && attenuation(0.3) < 1.0);
+        // halfway between the cone cosines, the squared falloff is a quarter
+        let halfway = ((0.2f32.cos() + 0.4f32.cos()) / 2.0).acos();
+        assert!((attenuation(halfway) - 0.25).abs() < 1e-4);
+        assert_eq!(attenuation(0.5), 0.0);
+    }
+}
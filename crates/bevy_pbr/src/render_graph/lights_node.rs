@@ -1,8 +1,9 @@
 use crate::{
     light::{
-        AmbientLight, DirectionalLight, DirectionalLightUniform, PointLight, PointLightUniform,
+        directional_shadow_center, AmbientLight, DirectionalLight, DirectionalLightUniform,
+        PointLight, PointLightUniform, SpotLight, SpotLightUniform,
     },
-    render_graph::uniform,
+    render_graph::{directional_light_shadow_tile, point_light_shadow_tile, uniform},
 };
 use bevy_core::{bytes_of, Pod, Zeroable};
 use bevy_ecs::{
@@ -10,27 +11,43 @@ use bevy_ecs::{
     world::World,
 };
 use bevy_render::{
+    camera::Camera,
     render_graph::{CommandQueue, Node, ResourceSlots, SystemNode},
     renderer::{
         BufferId, BufferInfo, BufferMapMode, BufferUsage, RenderContext, RenderResourceBinding,
         RenderResourceBindings, RenderResourceContext,
     },
+    texture::{
+        AddressMode, Extent3d, FilterMode, SamplerDescriptor, TextureDescriptor, TextureDimension,
+        TextureFormat, TextureUsage,
+    },
 };
 use bevy_transform::prelude::*;
 
 /// A Render Graph [Node] that write light data from the ECS to GPU buffers
+///
+/// It also creates the shadow atlas rendered by the [ShadowPassNode](super::ShadowPassNode)
 #[derive(Debug, Default)]
 pub struct LightsNode {
     command_queue: CommandQueue,
     max_point_lights: usize,
     max_dir_lights: usize,
+    max_spot_lights: usize,
+    shadow_atlas_size: u32,
 }
 
 impl LightsNode {
-    pub fn new(max_point_lights: usize, max_dir_lights: usize) -> Self {
+    pub fn new(
+        max_point_lights: usize,
+        max_dir_lights: usize,
+        max_spot_lights: usize,
+        shadow_atlas_size: u32,
+    ) -> Self {
         LightsNode {
             max_point_lights,
             max_dir_lights,
+            max_spot_lights,
+            shadow_atlas_size,
             command_queue: CommandQueue::default(),
         }
     }
@@ -54,6 +71,7 @@ struct LightCount {
     // storing as a `[u32; 4]` for memory alignement
     // Index 0 is for point lights,
     // Index 1 is for directional lights
+    // Index 2 is for spot lights
     pub num_lights: [u32; 4],
 }
 
@@ -64,6 +82,8 @@ impl SystemNode for LightsNode {
                 command_queue: self.command_queue.clone(),
                 max_point_lights: self.max_point_lights,
                 max_dir_lights: self.max_dir_lights,
+                max_spot_lights: self.max_spot_lights,
+                shadow_atlas_size: self.shadow_atlas_size,
                 light_buffer: None,
                 staging_buffer: None,
             })
@@ -80,6 +100,8 @@ pub struct LightsNodeSystemState {
     command_queue: CommandQueue,
     max_point_lights: usize,
     max_dir_lights: usize,
+    max_spot_lights: usize,
+    shadow_atlas_size: u32,
 }
 
 pub fn lights_node_system(
@@ -91,6 +113,8 @@ pub fn lights_node_system(
     mut render_resource_bindings: ResMut<RenderResourceBindings>,
     point_lights: Query<(&PointLight, &GlobalTransform)>,
     dir_lights: Query<&DirectionalLight>,
+    spot_lights: Query<(&SpotLight, &GlobalTransform)>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
 ) {
     let state = &mut state;
     let render_resource_context = &**render_resource_context;
@@ -110,6 +134,11 @@ pub fn lights_node_system(
     let dir_light_array_size = dir_light_size * dir_light_count;
     let dir_light_array_max_size = dir_light_size * state.max_dir_lights;
 
+    let spot_light_count = spot_lights.iter().len().min(state.max_spot_lights);
+    let spot_light_size = std::mem::size_of::<SpotLightUniform>();
+    let spot_light_array_size = spot_light_size * spot_light_count;
+    let spot_light_array_max_size = spot_light_size * state.max_spot_lights;
+
     let light_count_size = ambient_light_size + std::mem::size_of::<LightCount>();
 
     let point_light_uniform_start = light_count_size;
@@ -119,11 +148,17 @@ pub fn lights_node_system(
     let dir_light_uniform_end =
         light_count_size + point_light_array_max_size + dir_light_array_size;
 
-    let max_light_uniform_size =
+    let spot_light_uniform_start =
         light_count_size + point_light_array_max_size + dir_light_array_max_size;
+    let spot_light_uniform_end = spot_light_uniform_start + spot_light_array_size;
+
+    let max_light_uniform_size = light_count_size
+        + point_light_array_max_size
+        + dir_light_array_max_size
+        + spot_light_array_max_size;
 
     if let Some(staging_buffer) = state.staging_buffer {
-        if point_light_count == 0 && dir_light_count == 0 {
+        if point_light_count == 0 && dir_light_count == 0 && spot_light_count == 0 {
             return;
         }
 
@@ -150,6 +185,37 @@ pub fn lights_node_system(
             mapped_at_creation: true,
         });
         state.staging_buffer = Some(staging_buffer);
+
+        let shadow_atlas = render_resource_context.create_texture(TextureDescriptor {
+            size: Extent3d {
+                width: state.shadow_atlas_size,
+                height: state.shadow_atlas_size,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Depth32Float,
+            usage: TextureUsage::OUTPUT_ATTACHMENT | TextureUsage::SAMPLED,
+        });
+        render_resource_bindings.set(
+            uniform::SHADOW_ATLAS,
+            RenderResourceBinding::Texture(shadow_atlas),
+        );
+        // depth comparisons are done in the shader, so the atlas is sampled without filtering
+        let shadow_atlas_sampler = render_resource_context.create_sampler(&SamplerDescriptor {
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Nearest,
+            mipmap_filter: FilterMode::Nearest,
+            ..Default::default()
+        });
+        render_resource_bindings.set(
+            uniform::SHADOW_ATLAS_SAMPLER,
+            RenderResourceBinding::Sampler(shadow_atlas_sampler),
+        );
     }
 
     let staging_buffer = state.staging_buffer.unwrap();
@@ -164,27 +230,62 @@ pub fn lights_node_system(
             data[ambient_light_size..light_count_size].copy_from_slice(bytes_of(&[
                 point_light_count as u32,
                 dir_light_count as u32,
-                0,
+                spot_light_count as u32,
                 0,
             ]));
 
             // point light array
-            for ((point_light, global_transform), slot) in point_lights.iter().zip(
-                data[point_light_uniform_start..point_light_uniform_end]
-                    .chunks_exact_mut(point_light_size),
-            ) {
+            for (i, ((point_light, global_transform), slot)) in point_lights
+                .iter()
+                .zip(
+                    data[point_light_uniform_start..point_light_uniform_end]
+                        .chunks_exact_mut(point_light_size),
+                )
+                .enumerate()
+            {
+                let shadow_tile = if point_light.shadows_enabled {
+                    Some(point_light_shadow_tile(i))
+                } else {
+                    None
+                };
                 slot.copy_from_slice(bytes_of(&PointLightUniform::new(
                     point_light,
                     global_transform,
+                    shadow_tile,
                 )));
             }
 
             // directional light array
-            for (dir_light, slot) in dir_lights.iter().zip(
-                data[dir_light_uniform_start..dir_light_uniform_end]
-                    .chunks_exact_mut(dir_light_size),
+            let shadow_center = directional_shadow_center(cameras.iter());
+            for (i, (dir_light, slot)) in dir_lights
+                .iter()
+                .zip(
+                    data[dir_light_uniform_start..dir_light_uniform_end]
+                        .chunks_exact_mut(dir_light_size),
+                )
+                .enumerate()
+            {
+                let shadow_tile = if dir_light.shadows_enabled {
+                    Some(directional_light_shadow_tile(i))
+                } else {
+                    None
+                };
+                slot.copy_from_slice(bytes_of(&DirectionalLightUniform::new(
+                    dir_light,
+                    shadow_tile,
+                    shadow_center,
+                )));
+            }
+
+            // spot light array
+            for ((spot_light, global_transform), slot) in spot_lights.iter().zip(
+                data[spot_light_uniform_start..spot_light_uniform_end]
+                    .chunks_exact_mut(spot_light_size),
             ) {
-                slot.copy_from_slice(bytes_of(&DirectionalLightUniform::new(dir_light)));
+                slot.copy_from_slice(bytes_of(&SpotLightUniform::new(
+                    spot_light,
+                    global_transform,
+                )));
             }
         },
     );
@@ -1,25 +1,35 @@
 mod lights_node;
 mod pbr_pipeline;
+mod shadow_pass_node;
+mod shadow_pipeline;
 
 use bevy_ecs::world::World;
 pub use lights_node::*;
 pub use pbr_pipeline::*;
+pub use shadow_pass_node::*;
+pub use shadow_pipeline::*;
 
 /// the names of pbr graph nodes
 pub mod node {
     pub const TRANSFORM: &str = "transform";
+    pub const SKINNED_MESH_JOINTS: &str = "skinned_mesh_joints";
     pub const STANDARD_MATERIAL: &str = "standard_material";
     pub const LIGHTS: &str = "lights";
+    pub const SHADOW_PASS: &str = "shadow_pass";
 }
 
 /// the names of pbr uniforms
 pub mod uniform {
     pub const LIGHTS: &str = "Lights";
+    pub const SHADOW_ATLAS: &str = "ShadowAtlas";
+    pub const SHADOW_ATLAS_SAMPLER: &str = "ShadowAtlas_sampler";
+    pub const SHADOW_VIEW_PROJ: &str = "ShadowViewProj";
 }
 
 use crate::prelude::StandardMaterial;
 use bevy_asset::Assets;
 use bevy_render::{
+    mesh::SkinnedMeshJoints,
     pipeline::PipelineDescriptor,
     render_graph::{base, AssetRenderResourcesNode, RenderGraph, RenderResourcesNode},
     shader::Shader,
@@ -28,6 +38,13 @@ use bevy_transform::prelude::GlobalTransform;
 
 pub const MAX_POINT_LIGHTS: usize = 10;
 pub const MAX_DIRECTIONAL_LIGHTS: usize = 1;
+pub const MAX_SPOT_LIGHTS: usize = 10;
+/// the width and height, in texels, of a single shadow map
+pub const SHADOW_MAP_SIZE: u32 = 512;
+/// the number of shadow maps per row of the square shadow atlas. it must fit one shadow map per
+/// directional light and 6 per point light
+pub const SHADOW_ATLAS_COLUMNS: usize = 8;
+
 pub(crate) fn add_pbr_graph(world: &mut World) {
     {
         let mut graph = world.get_resource_mut::<RenderGraph>().unwrap();
@@ -35,6 +52,10 @@ pub(crate) fn add_pbr_graph(world: &mut World) {
             node::TRANSFORM,
             RenderResourcesNode::<GlobalTransform>::new(true),
         );
+        graph.add_system_node(
+            node::SKINNED_MESH_JOINTS,
+            RenderResourcesNode::<SkinnedMeshJoints>::new(true),
+        );
         graph.add_system_node(
             node::STANDARD_MATERIAL,
             AssetRenderResourcesNode::<StandardMaterial>::new(true),
@@ -42,7 +63,16 @@ pub(crate) fn add_pbr_graph(world: &mut World) {
 
         graph.add_system_node(
             node::LIGHTS,
-            LightsNode::new(MAX_POINT_LIGHTS, MAX_DIRECTIONAL_LIGHTS),
+            LightsNode::new(
+                MAX_POINT_LIGHTS,
+                MAX_DIRECTIONAL_LIGHTS,
+                MAX_SPOT_LIGHTS,
+                SHADOW_ATLAS_COLUMNS as u32 * SHADOW_MAP_SIZE,
+            ),
+        );
+        graph.add_node(
+            node::SHADOW_PASS,
+            ShadowPassNode::new(MAX_POINT_LIGHTS, MAX_DIRECTIONAL_LIGHTS),
         );
 
         // TODO: replace these with "autowire" groups
@@ -55,10 +85,127 @@ pub(crate) fn add_pbr_graph(world: &mut World) {
         graph
             .add_node_edge(node::LIGHTS, base::node::MAIN_PASS)
             .unwrap();
+        graph
+            .add_node_edge(node::SKINNED_MESH_JOINTS, base::node::MAIN_PASS)
+            .unwrap();
+        graph
+            .add_node_edge(node::TRANSFORM, node::SHADOW_PASS)
+            .unwrap();
+        graph
+            .add_node_edge(node::SKINNED_MESH_JOINTS, node::SHADOW_PASS)
+            .unwrap();
+        graph
+            .add_node_edge(node::LIGHTS, node::SHADOW_PASS)
+            .unwrap();
+        graph
+            .add_node_edge(node::SHADOW_PASS, base::node::MAIN_PASS)
+            .unwrap();
     }
-    let pipeline = build_pbr_pipeline(&mut world.get_resource_mut::<Assets<Shader>>().unwrap());
+    let mut shaders = world.get_resource_mut::<Assets<Shader>>().unwrap();
+    let pipeline = build_pbr_pipeline(&mut shaders);
+    let shadow_pipeline = build_shadow_pipeline(&mut shaders);
     let mut pipelines = world
         .get_resource_mut::<Assets<PipelineDescriptor>>()
         .unwrap();
     pipelines.set_untracked(PBR_PIPELINE_HANDLE, pipeline);
+    pipelines.set_untracked(SHADOW_PIPELINE_HANDLE, shadow_pipeline);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{light::NotShadowCaster, DirectionalLight, PbrBundle, PbrPlugin, PointLight};
+    use bevy_app::App;
+    use bevy_asset::AssetPlugin;
+    use bevy_core::CorePlugin;
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        system::IntoExclusiveSystem,
+    };
+    use bevy_render::{
+        render_graph::render_graph_schedule_executor_system,
+        renderer::{HeadlessRenderResourceContext, RenderResourceBindings, RenderResourceContext},
+        RenderPlugin,
+    };
+
+    #[test]
+    fn shadow_pass_runs_between_lights_and_main_pass() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(RenderPlugin::default())
+            .add_plugin(PbrPlugin);
+        let world = &mut app.world;
+        world.insert_resource::<Box<dyn RenderResourceContext>>(Box::new(
+            HeadlessRenderResourceContext::default(),
+        ));
+
+        let graph = world.get_resource::<RenderGraph>().unwrap();
+        let shadow_pass = graph.get_node_state(node::SHADOW_PASS).unwrap();
+        let lights = graph.get_node_id(node::LIGHTS).unwrap();
+        let main_pass = graph.get_node_id(base::node::MAIN_PASS).unwrap();
+        assert!(shadow_pass
+            .edges
+            .input_edges
+            .iter()
+            .any(|edge| edge.get_output_node() == lights));
+        assert!(shadow_pass
+            .edges
+            .output_edges
+            .iter()
+            .any(|edge| edge.get_input_node() == main_pass));
+
+        let mut directional_light = DirectionalLight::default();
+        directional_light.shadows_enabled = true;
+        world
+            .spawn()
+            .insert_bundle((directional_light, GlobalTransform::default()));
+        world.spawn().insert_bundle((
+            PointLight {
+                shadows_enabled: true,
+                ..Default::default()
+            },
+            GlobalTransform::default(),
+        ));
+        world
+            .spawn()
+            .insert_bundle(PbrBundle::default())
+            .insert(NotShadowCaster);
+
+        // the lights node creates the shadow atlas sampled by the main pass
+        SystemStage::single(render_graph_schedule_executor_system.exclusive_system()).run(world);
+        let bindings = world.get_resource::<RenderResourceBindings>().unwrap();
+        assert!(bindings
+            .get(uniform::SHADOW_ATLAS)
+            .and_then(|binding| binding.get_texture())
+            .is_some());
+        assert!(bindings
+            .get(uniform::SHADOW_ATLAS_SAMPLER)
+            .and_then(|binding| binding.get_sampler())
+            .is_some());
+    }
+
+    #[test]
+    fn skinned_shaders_compile() {
+        use bevy_render::shader::ShaderStage;
+
+        let skinned = ["SKINNED".to_string()];
+        for source in [
+            include_str!("pbr_pipeline/pbr.vert"),
+            include_str!("shadow_pipeline/shadow.vert"),
+        ]
+        .iter()
+        {
+            let shader = Shader::from_glsl(ShaderStage::Vertex, source);
+            shader.get_spirv(None).unwrap();
+            shader.get_spirv(Some(&skinned)).unwrap();
+        }
+    }
+
+    #[test]
+    fn shadow_atlas_fits_every_light() {
+        let tiles = MAX_DIRECTIONAL_LIGHTS + MAX_POINT_LIGHTS * 6;
+        assert!(tiles <= SHADOW_ATLAS_COLUMNS * SHADOW_ATLAS_COLUMNS);
+        assert_eq!(point_light_shadow_tile(MAX_POINT_LIGHTS - 1) + 6, tiles);
+    }
 }
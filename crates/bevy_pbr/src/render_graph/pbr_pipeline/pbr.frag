@@ -37,16 +37,37 @@
 // reflects the constants defined bevy_pbr/src/render_graph/mod.rs
 const int MAX_POINT_LIGHTS = 10;
 const int MAX_DIRECTIONAL_LIGHTS = 1;
+const int MAX_SPOT_LIGHTS = 10;
+const float SHADOW_MAP_SIZE = 512.0;
+const int SHADOW_ATLAS_COLUMNS = 8;
+// reflects the constant defined in bevy_pbr/src/light.rs
+const float POINT_LIGHT_SHADOW_NEAR = 0.1;
 
 struct PointLight {
     vec4 pos;
     vec4 color;
+    // x = 1 / range^2, y = radius, z = range
     vec4 lightParams;
+    // x = shadow atlas tile (-1 if shadows are disabled), y = depth bias, z = normal bias
+    vec4 shadowParams;
 };
  
 struct DirectionalLight {
     vec4 direction;
     vec4 color;
+    mat4 viewProj;
+    // x = shadow atlas tile (-1 if shadows are disabled), y = depth bias, z = normal bias
+    vec4 shadowParams;
+};
+
+struct SpotLight {
+    vec4 pos;
+    vec4 color;
+    // x = 1 / range^2, y = radius, z = range
+    vec4 lightParams;
+    vec4 direction;
+    // x = cone attenuation scale, y = cone attenuation offset
+    vec4 spotParams;
 };
 
 layout(location = 0) in vec3 v_WorldPosition;
@@ -68,10 +89,13 @@ layout(std140, set = 0, binding = 1) uniform CameraPosition {
 
 layout(std140, set = 1, binding = 0) uniform Lights {
     vec4 AmbientColor;
-    uvec4 NumLights; // x = point lights, y = directional lights
+    uvec4 NumLights; // x = point lights, y = directional lights, z = spot lights
     PointLight PointLights[MAX_POINT_LIGHTS];
     DirectionalLight DirectionalLights[MAX_DIRECTIONAL_LIGHTS];
+    SpotLight SpotLights[MAX_SPOT_LIGHTS];
 };
+layout(set = 1, binding = 1) uniform texture2D ShadowAtlas;
+layout(set = 1, binding = 2) uniform sampler ShadowAtlas_sampler;
 
 layout(set = 3, binding = 0) uniform StandardMaterial_base_color {
     vec4 base_color;
@@ -332,6 +356,17 @@ vec3 point_light(PointLight light, float roughness, float NdotV, vec3 N, vec3 V,
     return ((diffuse + specular) * light.color.rgb) * (rangeAttenuation * NoL);
 }
 
+vec3 spot_light(SpotLight light, float roughness, float NdotV, vec3 N, vec3 V, vec3 R, vec3 F0, vec3 diffuseColor) {
+    // a spot light is shaded like a point light, attenuated outside of its cone
+    // see https://google.github.io/filament/Filament.html#lighting/directlighting/punctuallights
+    PointLight point = PointLight(light.pos, light.color, light.lightParams, vec4(-1.0, 0.0, 0.0, 0.0));
+    vec3 frag_to_light = normalize(light.pos.xyz - v_WorldPosition.xyz);
+    float cd = dot(light.direction.xyz, -frag_to_light);
+    float spotAttenuation = saturate(cd * light.spotParams.x + light.spotParams.y);
+    spotAttenuation *= spotAttenuation;
+    return point_light(point, roughness, NdotV, N, V, R, F0, diffuseColor) * spotAttenuation;
+}
+
 vec3 dir_light(DirectionalLight light, float roughness, float NdotV, vec3 normal, vec3 view, vec3 R, vec3 F0, vec3 diffuseColor) {
     vec3 incident_light = light.direction.xyz;
 
@@ -349,6 +384,82 @@ vec3 dir_light(DirectionalLight light, float roughness, float NdotV, vec3 normal
 
 #endif
 
+#    ifndef NOT_SHADOW_RECEIVER
+// returns the lit fraction of a fragment at `uv` and `depth` in the given shadow map,
+// using 3x3 percentage-closer filtering
+float sample_shadow_map(int tile, vec2 uv, float depth) {
+    if (any(lessThan(uv, vec2(0.0))) || any(greaterThan(uv, vec2(1.0))) || depth > 1.0) {
+        return 1.0;
+    }
+    vec2 tile_origin = vec2(float(tile % SHADOW_ATLAS_COLUMNS), float(tile / SHADOW_ATLAS_COLUMNS));
+    float texel = 1.0 / SHADOW_MAP_SIZE;
+    float lit = 0.0;
+    for (int x = -1; x <= 1; ++x) {
+        for (int y = -1; y <= 1; ++y) {
+            // stay inside the tile so neighbouring shadow maps don't bleed in
+            vec2 tile_uv = clamp(uv + vec2(x, y) * texel, vec2(0.5 * texel), vec2(1.0 - 0.5 * texel));
+            vec2 atlas_uv = (tile_origin + tile_uv) / float(SHADOW_ATLAS_COLUMNS);
+            float occluder_depth = textureLod(sampler2D(ShadowAtlas, ShadowAtlas_sampler), atlas_uv, 0.0).r;
+            lit += depth <= occluder_depth ? 1.0 : 0.0;
+        }
+    }
+    return lit / 9.0;
+}
+
+float point_light_shadow(PointLight light, vec3 surface_normal) {
+    int tile = int(light.shadowParams.x);
+    if (tile < 0) {
+        return 1.0;
+    }
+    vec3 light_to_frag = v_WorldPosition.xyz + surface_normal * light.shadowParams.z - light.pos.xyz;
+    light_to_frag -= normalize(light_to_frag) * light.shadowParams.y;
+
+    // pick the cube map face, the (forward, up) pairs reflect PointLight::shadow_view_projection
+    vec3 abs_position = abs(light_to_frag);
+    int face;
+    vec3 forward;
+    vec3 up;
+    if (abs_position.x >= abs_position.y && abs_position.x >= abs_position.z) {
+        bool positive = light_to_frag.x > 0.0;
+        face = positive ? 0 : 1;
+        forward = vec3(positive ? 1.0 : -1.0, 0.0, 0.0);
+        up = vec3(0.0, 1.0, 0.0);
+    } else if (abs_position.y >= abs_position.z) {
+        bool positive = light_to_frag.y > 0.0;
+        face = positive ? 2 : 3;
+        forward = vec3(0.0, positive ? 1.0 : -1.0, 0.0);
+        up = vec3(0.0, 0.0, positive ? -1.0 : 1.0);
+    } else {
+        bool positive = light_to_frag.z > 0.0;
+        face = positive ? 4 : 5;
+        forward = vec3(0.0, 0.0, positive ? 1.0 : -1.0);
+        up = vec3(0.0, 1.0, 0.0);
+    }
+    vec3 right = cross(forward, up);
+    up = cross(right, forward);
+
+    // project onto the face with a 90 degree perspective projection
+    float view_depth = dot(light_to_frag, forward);
+    vec2 ndc = vec2(dot(light_to_frag, right), dot(light_to_frag, up)) / view_depth;
+    float far = light.lightParams.z;
+    float depth = far * (view_depth - POINT_LIGHT_SHADOW_NEAR) / ((far - POINT_LIGHT_SHADOW_NEAR) * view_depth);
+    return sample_shadow_map(tile + face, ndc * vec2(0.5, -0.5) + 0.5, depth);
+}
+
+float dir_light_shadow(DirectionalLight light, vec3 surface_normal) {
+    int tile = int(light.shadowParams.x);
+    if (tile < 0) {
+        return 1.0;
+    }
+    vec3 position = v_WorldPosition.xyz
+        + surface_normal * light.shadowParams.z
+        + light.direction.xyz * light.shadowParams.y;
+    vec4 clip = light.viewProj * vec4(position, 1.0);
+    vec3 ndc = clip.xyz / clip.w;
+    return sample_shadow_map(tile, ndc.xy * vec2(0.5, -0.5) + 0.5, ndc.z);
+}
+#    endif
+
 void main() {
     vec4 output_color = base_color;
 #ifdef STANDARDMATERIAL_BASE_COLOR_TEXTURE
@@ -383,6 +494,9 @@ void main() {
 #        endif
 #    endif
 
+    // the geometric normal is used to offset shadow lookups, normal maps would only add noise
+    vec3 surface_normal = N;
+
 #    ifdef STANDARDMATERIAL_NORMAL_MAP
     mat3 TBN = mat3(T, B, N);
     N = TBN * normalize(texture(sampler2D(StandardMaterial_normal_map, StandardMaterial_normal_map_sampler), v_Uv).rgb * 2.0 - 1.0);
@@ -421,10 +535,21 @@ void main() {
     // accumulate color
     vec3 light_accum = vec3(0.0);
     for (int i = 0; i < int(NumLights.x) && i < MAX_POINT_LIGHTS; ++i) {
-        light_accum += point_light(PointLights[i], roughness, NdotV, N, V, R, F0, diffuseColor);
+        float shadow = 1.0;
+#    ifndef NOT_SHADOW_RECEIVER
+        shadow = point_light_shadow(PointLights[i], surface_normal);
+#    endif
+        light_accum += shadow * point_light(PointLights[i], roughness, NdotV, N, V, R, F0, diffuseColor);
     }
     for (int i = 0; i < int(NumLights.y) && i < MAX_DIRECTIONAL_LIGHTS; ++i) {
-        light_accum += dir_light(DirectionalLights[i], roughness, NdotV, N, V, R, F0, diffuseColor);
+        float shadow = 1.0;
+#    ifndef NOT_SHADOW_RECEIVER
+        shadow = dir_light_shadow(DirectionalLights[i], surface_normal);
+#    endif
+        light_accum += shadow * dir_light(DirectionalLights[i], roughness, NdotV, N, V, R, F0, diffuseColor);
+    }
+    for (int i = 0; i < int(NumLights.z) && i < MAX_SPOT_LIGHTS; ++i) {
+        light_accum += spot_light(SpotLights[i], roughness, NdotV, N, V, R, F0, diffuseColor);
     }
 
     vec3 diffuse_ambient = EnvBRDFApprox(diffuseColor, 1.0, NdotV);
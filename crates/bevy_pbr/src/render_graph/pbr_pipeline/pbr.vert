@@ -8,6 +8,11 @@ layout(location = 2) in vec2 Vertex_Uv;
 layout(location = 3) in vec4 Vertex_Tangent;
 #endif
 
+#ifdef SKINNED
+layout(location = 4) in uvec4 Vertex_JointIndex;
+layout(location = 5) in vec4 Vertex_JointWeight;
+#endif
+
 layout(location = 0) out vec3 v_WorldPosition;
 layout(location = 1) out vec3 v_WorldNormal;
 layout(location = 2) out vec2 v_Uv;
@@ -24,13 +29,28 @@ layout(set = 2, binding = 0) uniform Transform {
     mat4 Model;
 };
 
+#ifdef SKINNED
+layout(set = 2, binding = 1) uniform SkinnedMeshJoints_joint_matrices {
+    mat4 Joints[256];
+};
+#endif
+
 void main() {
-    vec4 world_position = Model * vec4(Vertex_Position, 1.0);
+#ifdef SKINNED
+    // the joint matrices are in world space, so they replace the model matrix
+    mat4 model = Vertex_JointWeight.x * Joints[Vertex_JointIndex.x]
+        + Vertex_JointWeight.y * Joints[Vertex_JointIndex.y]
+        + Vertex_JointWeight.z * Joints[Vertex_JointIndex.z]
+        + Vertex_JointWeight.w * Joints[Vertex_JointIndex.w];
+#else
+    mat4 model = Model;
+#endif
+    vec4 world_position = model * vec4(Vertex_Position, 1.0);
     v_WorldPosition = world_position.xyz;
-    v_WorldNormal = mat3(Model) * Vertex_Normal;
+    v_WorldNormal = mat3(model) * Vertex_Normal;
     v_Uv = Vertex_Uv;
 #ifdef STANDARDMATERIAL_NORMAL_MAP
-    v_WorldTangent = vec4(mat3(Model) * Vertex_Tangent.xyz, Vertex_Tangent.w);
+    v_WorldTangent = vec4(mat3(model) * Vertex_Tangent.xyz, Vertex_Tangent.w);
 #endif
     gl_Position = ViewProj * world_position;
 }
@@ -0,0 +1,516 @@
+use crate::{
+    light::{directional_shadow_center, DirectionalLight, NotShadowCaster, PointLight},
+    render_graph::{uniform, SHADOW_ATLAS_COLUMNS, SHADOW_MAP_SIZE, SHADOW_PIPELINE_HANDLE},
+};
+use bevy_asset::{Assets, Handle};
+use bevy_core::Bytes;
+use bevy_ecs::{
+    query::{QueryState, Without},
+    world::{Mut, World},
+};
+use bevy_math::Mat4;
+use bevy_render::{
+    camera::Camera,
+    draw::Visible,
+    mesh::{Indices, Mesh},
+    pass::{
+        LoadOp, Operations, PassDescriptor, RenderPassDepthStencilAttachment, TextureAttachment,
+    },
+    pipeline::{
+        BindGroupDescriptorId, IndexFormat, PipelineCompiler, PipelineDescriptor, RenderPipelines,
+    },
+    render_graph::{Node, ResourceSlots},
+    renderer::{
+        BindGroupId, BufferId, BufferInfo, BufferMapMode, BufferUsage, RenderContext,
+        RenderResourceBinding, RenderResourceBindings, RenderResourceContext,
+    },
+    shader::Shader,
+};
+use bevy_transform::prelude::GlobalTransform;
+use std::{ops::Range, sync::Arc};
+
+/// The first shadow atlas tile of the `index`th directional light
+pub(crate) fn directional_light_shadow_tile(index: usize) -> usize {
+    index
+}
+
+/// The first of the 6 consecutive shadow atlas tiles (one per cube map face) of the `index`th
+/// point light
+pub(crate) fn point_light_shadow_tile(index: usize) -> usize {
+    super::MAX_DIRECTIONAL_LIGHTS + index * 6
+}
+
+/// A mesh rendered into every shadow map of the current frame
+#[derive(Debug)]
+struct ShadowCaster {
+    pipeline: Handle<PipelineDescriptor>,
+    transform_bind_group_descriptor: BindGroupDescriptorId,
+    transform_bind_group: BindGroupId,
+    dynamic_uniform_indices: Option<Arc<[u32]>>,
+    vertex_buffer: BufferId,
+    index_buffer: Option<(BufferId, IndexFormat)>,
+    draw_range: Range<u32>,
+}
+
+/// A shadow map to render this frame
+#[derive(Debug)]
+struct ShadowView {
+    tile: usize,
+    bind_group_descriptor: BindGroupDescriptorId,
+    bind_group: BindGroupId,
+}
+
+type ShadowCasterQuery = (
+    &'static Handle<Mesh>,
+    &'static mut RenderPipelines,
+    &'static Visible,
+);
+
+/// A Render Graph [Node] that renders the depth of every shadow casting mesh into the shadow
+/// atlas, once per shadow map.
+///
+/// Directional lights get a single orthographic shadow map while point lights get 6 perspective
+/// shadow maps, one per cube map face. Each shadow map is a [SHADOW_MAP_SIZE] tile of the atlas.
+pub struct ShadowPassNode {
+    max_point_lights: usize,
+    max_dir_lights: usize,
+    descriptor: PassDescriptor,
+    view_proj_buffer: Option<BufferId>,
+    staging_buffer: Option<BufferId>,
+    view_proj_stride: usize,
+    tile_bindings: Vec<RenderResourceBindings>,
+    clear_atlas: bool,
+    views: Vec<ShadowView>,
+    casters: Vec<ShadowCaster>,
+    caster_query: Option<QueryState<ShadowCasterQuery, Without<NotShadowCaster>>>,
+    point_light_query: Option<QueryState<(&'static PointLight, &'static GlobalTransform)>>,
+    dir_light_query: Option<QueryState<&'static DirectionalLight>>,
+    camera_query: Option<QueryState<(&'static Camera, &'static GlobalTransform)>>,
+}
+
+impl ShadowPassNode {
+    pub fn new(max_point_lights: usize, max_dir_lights: usize) -> Self {
+        ShadowPassNode {
+            max_point_lights,
+            max_dir_lights,
+            descriptor: PassDescriptor {
+                color_attachments: Vec::new(),
+                depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                    attachment: TextureAttachment::Name(uniform::SHADOW_ATLAS.to_string()),
+                    depth_ops: Some(Operations {
+                        load: LoadOp::Clear(1.0),
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }),
+                sample_count: 1,
+            },
+            view_proj_buffer: None,
+            staging_buffer: None,
+            view_proj_stride: 0,
+            tile_bindings: Vec::new(),
+            clear_atlas: false,
+            views: Vec::new(),
+            casters: Vec::new(),
+            caster_query: None,
+            point_light_query: None,
+            dir_light_query: None,
+            camera_query: None,
+        }
+    }
+
+    /// The number of shadow maps rendered this frame
+    pub fn shadow_map_count(&self) -> usize {
+        self.views.len()
+    }
+
+    /// The number of meshes rendered into each shadow map this frame
+    pub fn shadow_caster_count(&self) -> usize {
+        self.casters.len()
+    }
+
+    fn max_tiles(&self) -> usize {
+        self.max_dir_lights + self.max_point_lights * 6
+    }
+
+    /// Collects the view projection of every shadow map to render, keyed by atlas tile
+    fn collect_view_projections(&mut self, world: &mut World) -> Vec<(usize, Mat4)> {
+        let mut view_projections = Vec::new();
+
+        let camera_query = self.camera_query.get_or_insert_with(|| world.query());
+        let shadow_center = directional_shadow_center(camera_query.iter(world));
+        let dir_light_query = self.dir_light_query.get_or_insert_with(|| world.query());
+        for (i, light) in dir_light_query
+            .iter(world)
+            .take(self.max_dir_lights)
+            .enumerate()
+        {
+            if light.shadows_enabled {
+                view_projections.push((
+                    directional_light_shadow_tile(i),
+                    light.shadow_view_projection(shadow_center),
+                ));
+            }
+        }
+
+        let point_light_query = self.point_light_query.get_or_insert_with(|| world.query());
+        for (i, (light, global_transform)) in point_light_query
+            .iter(world)
+            .take(self.max_point_lights)
+            .enumerate()
+        {
+            if light.shadows_enabled {
+                for face in 0..6 {
+                    view_projections.push((
+                        point_light_shadow_tile(i) + face,
+                        light.shadow_view_projection(global_transform.translation, face),
+                    ));
+                }
+            }
+        }
+
+        view_projections
+    }
+
+    /// Writes the shadow map view projections to the staging buffer, creating the GPU buffers and
+    /// the per-tile bindings first if needed
+    fn write_view_projections(
+        &mut self,
+        render_resource_context: &dyn RenderResourceContext,
+        view_projections: &[(usize, Mat4)],
+    ) {
+        let max_tiles = self.max_tiles();
+        let staging_buffer = if let Some(staging_buffer) = self.staging_buffer {
+            render_resource_context.map_buffer(staging_buffer, BufferMapMode::Write);
+            staging_buffer
+        } else {
+            // every view projection is bound separately, so each one needs to be aligned
+            self.view_proj_stride = render_resource_context
+                .get_aligned_uniform_size(std::mem::size_of::<[[f32; 4]; 4]>(), true);
+            let size = self.view_proj_stride * max_tiles;
+            let buffer = render_resource_context.create_buffer(BufferInfo {
+                size,
+                buffer_usage: BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+                ..Default::default()
+            });
+            self.tile_bindings = (0..max_tiles)
+                .map(|tile| {
+                    let mut bindings = RenderResourceBindings::default();
+                    let start = (tile * self.view_proj_stride) as u64;
+                    bindings.set(
+                        uniform::SHADOW_VIEW_PROJ,
+                        RenderResourceBinding::Buffer {
+                            buffer,
+                            range: start..start + Mat4::IDENTITY.byte_len() as u64,
+                            dynamic_index: None,
+                        },
+                    );
+                    bindings
+                })
+                .collect();
+            self.view_proj_buffer = Some(buffer);
+
+            let staging_buffer = render_resource_context.create_buffer(BufferInfo {
+                size,
+                buffer_usage: BufferUsage::COPY_SRC | BufferUsage::MAP_WRITE,
+                mapped_at_creation: true,
+            });
+            self.staging_buffer = Some(staging_buffer);
+            staging_buffer
+        };
+
+        let stride = self.view_proj_stride;
+        render_resource_context.write_mapped_buffer(
+            staging_buffer,
+            0..(stride * max_tiles) as u64,
+            &mut |data, _renderer| {
+                for (tile, view_projection) in view_projections.iter() {
+                    let start = tile * stride;
+                    view_projection
+                        .write_bytes(&mut data[start..start + view_projection.byte_len()]);
+                }
+            },
+        );
+        render_resource_context.unmap_buffer(staging_buffer);
+    }
+}
+
+impl Node for ShadowPassNode {
+    fn prepare(&mut self, world: &mut World) {
+        self.views.clear();
+        self.casters.clear();
+
+        let view_projections = self.collect_view_projections(world);
+        // the atlas still needs to be cleared when shadows are enabled but nothing casts them
+        self.clear_atlas = !view_projections.is_empty();
+        if view_projections.is_empty() {
+            return;
+        }
+
+        world.resource_scope(
+            |world, render_resource_context: Mut<Box<dyn RenderResourceContext>>| {
+                let render_resource_context = &**render_resource_context;
+                self.write_view_projections(render_resource_context, &view_projections);
+
+                world.resource_scope(|world, mut pipeline_compiler: Mut<PipelineCompiler>| {
+                    world.resource_scope(
+                        |world, mut pipelines: Mut<Assets<PipelineDescriptor>>| {
+                            world.resource_scope(|world, mut shaders: Mut<Assets<Shader>>| {
+                                world.resource_scope(|world, meshes: Mut<Assets<Mesh>>| {
+                                    let shadow_pipeline = SHADOW_PIPELINE_HANDLE.typed();
+                                    let caster_query = self
+                                        .caster_query
+                                        .get_or_insert_with(|| world.query_filtered());
+                                    for (mesh_handle, mut render_pipelines, visible) in
+                                        caster_query.iter_mut(world)
+                                    {
+                                        if !visible.is_visible {
+                                            continue;
+                                        }
+
+                                        // don't render if the mesh or its buffers aren't ready yet
+                                        let mesh = if let Some(mesh) = meshes.get(mesh_handle) {
+                                            mesh
+                                        } else {
+                                            continue;
+                                        };
+                                        let vertex_buffer = if let Some(vertex_buffer) =
+                                            render_pipelines.bindings.vertex_attribute_buffer
+                                        {
+                                            vertex_buffer
+                                        } else {
+                                            continue;
+                                        };
+
+                                        // reuse the mesh layout and dynamic bindings of the main pipeline,
+                                        // the depth-only shader only needs to know if it is skinned
+                                        let mut specialization = if let Some(render_pipeline) =
+                                            render_pipelines.pipelines.first()
+                                        {
+                                            render_pipeline.specialization.clone()
+                                        } else {
+                                            continue;
+                                        };
+                                        specialization.sample_count = 1;
+                                        specialization
+                                            .shader_specialization
+                                            .shader_defs
+                                            .retain(|shader_def| shader_def == "SKINNED");
+
+                                        let pipeline = if let Some(pipeline) = pipeline_compiler
+                                            .get_specialized_pipeline(
+                                                &shadow_pipeline,
+                                                &specialization,
+                                            ) {
+                                            pipeline
+                                        } else {
+                                            pipeline_compiler.compile_pipeline(
+                                                render_resource_context,
+                                                &mut pipelines,
+                                                &mut shaders,
+                                                &shadow_pipeline,
+                                                &specialization,
+                                            )
+                                        };
+                                        let layout =
+                                            pipelines.get(&pipeline).unwrap().get_layout().unwrap();
+
+                                        if self.views.is_empty() {
+                                            let view_descriptor = layout.get_bind_group(0).unwrap();
+                                            for (tile, _) in view_projections.iter() {
+                                                if let Some(bind_group) = self.tile_bindings[*tile]
+                                                    .update_bind_group(
+                                                        view_descriptor,
+                                                        render_resource_context,
+                                                    )
+                                                {
+                                                    self.views.push(ShadowView {
+                                                        tile: *tile,
+                                                        bind_group_descriptor: view_descriptor.id,
+                                                        bind_group: bind_group.id,
+                                                    });
+                                                }
+                                            }
+                                        }
+
+                                        let transform_descriptor =
+                                            layout.get_bind_group(1).unwrap();
+                                        let transform_bind_group = if let Some(bind_group) =
+                                            render_pipelines.bindings.update_bind_group(
+                                                transform_descriptor,
+                                                render_resource_context,
+                                            ) {
+                                            bind_group
+                                        } else {
+                                            continue;
+                                        };
+
+                                        let draw_range = match mesh.indices() {
+                                            Some(Indices::U32(indices)) => 0..indices.len() as u32,
+                                            Some(Indices::U16(indices)) => 0..indices.len() as u32,
+                                            None => 0..mesh.count_vertices() as u32,
+                                        };
+
+                                        self.casters.push(ShadowCaster {
+                                            pipeline,
+                                            transform_bind_group_descriptor: transform_descriptor
+                                                .id,
+                                            transform_bind_group: transform_bind_group.id,
+                                            dynamic_uniform_indices: transform_bind_group
+                                                .dynamic_uniform_indices
+                                                .clone(),
+                                            vertex_buffer,
+                                            index_buffer: render_pipelines.bindings.index_buffer,
+                                            draw_range,
+                                        });
+                                    }
+                                });
+                            });
+                        },
+                    );
+                });
+            },
+        );
+    }
+
+    fn update(
+        &mut self,
+        world: &World,
+        render_context: &mut dyn RenderContext,
+        _input: &ResourceSlots,
+        _output: &mut ResourceSlots,
+    ) {
+        if !self.clear_atlas {
+            return;
+        }
+
+        let size = (self.view_proj_stride * self.max_tiles()) as u64;
+        render_context.copy_buffer_to_buffer(
+            self.staging_buffer.unwrap(),
+            0,
+            self.view_proj_buffer.unwrap(),
+            0,
+            size,
+        );
+
+        let render_resource_bindings = world.get_resource::<RenderResourceBindings>().unwrap();
+        let views = &self.views;
+        let casters = &self.casters;
+        render_context.begin_pass(
+            &self.descriptor,
+            render_resource_bindings,
+            &mut |render_pass| {
+                for view in views.iter() {
+                    let x = (view.tile % SHADOW_ATLAS_COLUMNS) as u32 * SHADOW_MAP_SIZE;
+                    let y = (view.tile / SHADOW_ATLAS_COLUMNS) as u32 * SHADOW_MAP_SIZE;
+                    render_pass.set_viewport(
+                        x as f32,
+                        y as f32,
+                        SHADOW_MAP_SIZE as f32,
+                        SHADOW_MAP_SIZE as f32,
+                        0.0,
+                        1.0,
+                    );
+                    for caster in casters.iter() {
+                        render_pass.set_pipeline(&caster.pipeline);
+                        render_pass.set_bind_group(
+                            0,
+                            view.bind_group_descriptor,
+                            view.bind_group,
+                            None,
+                        );
+                        render_pass.set_bind_group(
+                            1,
+                            caster.transform_bind_group_descriptor,
+                            caster.transform_bind_group,
+                            caster.dynamic_uniform_indices.as_deref(),
+                        );
+                        render_pass.set_vertex_buffer(0, caster.vertex_buffer, 0);
+                        if let Some((index_buffer, index_format)) = caster.index_buffer {
+                            render_pass.set_index_buffer(index_buffer, 0, index_format);
+                            render_pass.draw_indexed(caster.draw_range.clone(), 0, 0..1);
+                        } else {
+                            render_pass.draw(caster.draw_range.clone(), 0..1);
+                        }
+                    }
+                }
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PbrBundle, PbrPlugin};
+    use bevy_app::App;
+    use bevy_asset::AssetPlugin;
+    use bevy_core::CorePlugin;
+    use bevy_render::{
+        entity::PerspectiveCameraBundle, renderer::HeadlessRenderResourceContext, RenderPlugin,
+    };
+    use bevy_transform::prelude::Transform;
+
+    #[test]
+    fn prepare_collects_shadow_maps_and_casters() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_plugin(RenderPlugin::default())
+            .add_plugin(PbrPlugin);
+        let world = &mut app.world;
+        world.insert_resource::<Box<dyn RenderResourceContext>>(Box::new(
+            HeadlessRenderResourceContext::default(),
+        ));
+
+        let camera_position = bevy_math::Vec3::new(20.0, 10.0, -30.0);
+        world.spawn().insert_bundle(PerspectiveCameraBundle {
+            global_transform: GlobalTransform::from_translation(camera_position),
+            ..PerspectiveCameraBundle::new_3d()
+        });
+        let mut shadow_light = DirectionalLight::default();
+        shadow_light.shadows_enabled = true;
+        world.spawn().insert(shadow_light);
+        world.spawn().insert_bundle((
+            PointLight {
+                shadows_enabled: true,
+                ..Default::default()
+            },
+            GlobalTransform::from(Transform::from_xyz(1.0, 2.0, 3.0)),
+        ));
+        world.spawn().insert_bundle(PbrBundle::default());
+        world
+            .spawn()
+            .insert_bundle(PbrBundle::default())
+            .insert(NotShadowCaster);
+
+        let mut shadow_pass = ShadowPassNode::new(1, 1);
+        shadow_pass.prepare(world);
+        // shadows are enabled, so the atlas is cleared even though no mesh is ready to render
+        assert!(shadow_pass.clear_atlas);
+        assert_eq!(shadow_pass.shadow_caster_count(), 0);
+
+        // the directional light volume follows the 3D camera
+        let view_projections = shadow_pass.collect_view_projections(world);
+        let tiles = view_projections
+            .iter()
+            .map(|(tile, _)| *tile)
+            .collect::<Vec<_>>();
+        let point_light_tile = point_light_shadow_tile(0);
+        assert_eq!(
+            tiles,
+            [0].iter()
+                .copied()
+                .chain(point_light_tile..point_light_tile + 6)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            view_projections[0].1,
+            shadow_light.shadow_view_projection(camera_position)
+        );
+
+        // meshes with `NotShadowCaster` are never rendered into the shadow maps
+        let caster_query = shadow_pass.caster_query.as_mut().unwrap();
+        assert_eq!(caster_query.iter_mut(world).count(), 1);
+    }
+}
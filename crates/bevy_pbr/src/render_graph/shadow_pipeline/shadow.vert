@@ -0,0 +1,34 @@
+#version 450
+
+layout(location = 0) in vec3 Vertex_Position;
+
+#ifdef SKINNED
+layout(location = 1) in uvec4 Vertex_JointIndex;
+layout(location = 2) in vec4 Vertex_JointWeight;
+#endif
+
+layout(set = 0, binding = 0) uniform ShadowViewProj {
+    mat4 ViewProj;
+};
+
+layout(set = 1, binding = 0) uniform Transform {
+    mat4 Model;
+};
+
+#ifdef SKINNED
+layout(set = 1, binding = 1) uniform SkinnedMeshJoints_joint_matrices {
+    mat4 Joints[256];
+};
+#endif
+
+void main() {
+#ifdef SKINNED
+    mat4 model = Vertex_JointWeight.x * Joints[Vertex_JointIndex.x]
+        + Vertex_JointWeight.y * Joints[Vertex_JointIndex.y]
+        + Vertex_JointWeight.z * Joints[Vertex_JointIndex.z]
+        + Vertex_JointWeight.w * Joints[Vertex_JointIndex.w];
+#else
+    mat4 model = Model;
+#endif
+    gl_Position = ViewProj * model * vec4(Vertex_Position, 1.0);
+}
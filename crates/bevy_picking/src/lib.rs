@@ -0,0 +1,37 @@
+mod pick;
+mod ray;
+
+pub use pick::*;
+pub use ray::*;
+
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{Pickable, PickingCamera, PickingEvent, PickingPlugin};
+}
+
+use bevy_app::prelude::*;
+use bevy_ecs::schedule::{ParallelSystemDescriptorCoercion, SystemLabel};
+use bevy_input::InputSystem;
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub enum PickingSystem {
+    Picking,
+}
+
+/// Sends [`PickingEvent`]s for the [`Pickable`] meshes and sprites under the cursor of each
+/// [`PickingCamera`]
+#[derive(Default)]
+pub struct PickingPlugin;
+
+impl Plugin for PickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Pickable>()
+            .add_event::<PickingEvent>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                picking_system
+                    .label(PickingSystem::Picking)
+                    .after(InputSystem),
+            );
+    }
+}
@@ -0,0 +1,274 @@
+use crate::{Intersection, Ray};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    entity::Entity,
+    event::EventWriter,
+    query::{With, Without},
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_input::{mouse::MouseButton, Input};
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use bevy_render::{camera::Camera, draw::Visible, mesh::Mesh};
+use bevy_sprite::{Sprite, TextureAtlas, TextureAtlasSprite};
+use bevy_transform::components::GlobalTransform;
+use bevy_window::Windows;
+
+/// Marks an entity as a target of the picking rays, hit through its [`Mesh`] or [`Sprite`]
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct Pickable;
+
+/// Casts a ray from the cursor through the [`Camera`] it is attached to, and keeps the nearest
+/// [`Pickable`] entity it hits
+#[derive(Debug, Clone, Default)]
+pub struct PickingCamera {
+    ray: Option<Ray>,
+    hit: Option<(Entity, Intersection)>,
+}
+
+impl PickingCamera {
+    /// The ray going through the cursor, if the cursor is in the window of the camera
+    pub fn ray(&self) -> Option<Ray> {
+        self.ray
+    }
+
+    /// The entity under the cursor and where it is hit
+    pub fn hit(&self) -> Option<(Entity, Intersection)> {
+        self.hit
+    }
+}
+
+/// Sent when the cursor interacts with a [`Pickable`] entity through a [`PickingCamera`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum PickingEvent {
+    /// The cursor moved over the entity
+    HoverStarted { entity: Entity, hit: Intersection },
+    /// The cursor left the entity
+    HoverEnded { entity: Entity },
+    /// The left mouse button was pressed over the entity
+    Clicked { entity: Entity, hit: Intersection },
+}
+
+/// Finds the entities under the cursor and sends [`PickingEvent`]s
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn picking_system(
+    windows: Res<Windows>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    meshes: Res<Assets<Mesh>>,
+    texture_atlases: Res<Assets<TextureAtlas>>,
+    mut picking_events: EventWriter<PickingEvent>,
+    mut camera_query: Query<(&Camera, &GlobalTransform, &mut PickingCamera)>,
+    mesh_query: Query<
+        (Entity, &Handle<Mesh>, &GlobalTransform, Option<&Visible>),
+        (With<Pickable>, Without<Sprite>, Without<TextureAtlasSprite>),
+    >,
+    sprite_query: Query<(Entity, &Sprite, &GlobalTransform, Option<&Visible>), With<Pickable>>,
+    atlas_sprite_query: Query<
+        (
+            Entity,
+            &TextureAtlasSprite,
+            &Handle<TextureAtlas>,
+            &GlobalTransform,
+            Option<&Visible>,
+        ),
+        With<Pickable>,
+    >,
+) {
+    let is_visible = |visible: Option<&Visible>| {
+        !matches!(
+            visible,
+            Some(Visible {
+                is_visible: false,
+                ..
+            })
+        )
+    };
+    for (camera, camera_transform, mut picking_camera) in camera_query.iter_mut() {
+        let ray = windows.get(camera.window).and_then(|window| {
+            let window_size = Vec2::new(window.width(), window.height());
+            window.cursor_position().and_then(|cursor_position| {
+                Ray::from_screenspace(cursor_position, window_size, camera, camera_transform)
+            })
+        });
+
+        let mut nearest: Option<(Entity, Intersection)> = None;
+        let mut keep_nearest = |entity: Entity, hit: Option<Intersection>| match (hit, nearest) {
+            (Some(hit), Some((_, nearest_hit))) if hit.distance >= nearest_hit.distance => {}
+            (Some(hit), _) => nearest = Some((entity, hit)),
+            (None, _) => {}
+        };
+        if let Some(ray) = ray {
+            for (entity, mesh, transform, visible) in mesh_query.iter() {
+                if !is_visible(visible) {
+                    continue;
+                }
+                if let Some(mesh) = meshes.get(mesh) {
+                    keep_nearest(entity, ray.intersect_mesh(mesh, transform));
+                }
+            }
+            for (entity, sprite, transform, visible) in sprite_query.iter() {
+                if is_visible(visible) {
+                    keep_nearest(entity, ray.intersect_rect(sprite.size, transform));
+                }
+            }
+            for (entity, sprite, atlas, transform, visible) in atlas_sprite_query.iter() {
+                if !is_visible(visible) {
+                    continue;
+                }
+                let rect = texture_atlases
+                    .get(atlas)
+                    .and_then(|atlas| atlas.textures.get(sprite.index as usize));
+                if let Some(rect) = rect {
+                    let size = Vec2::new(rect.width(), rect.height());
+                    keep_nearest(entity, ray.intersect_rect(size, transform));
+                }
+            }
+        }
+
+        let previous = picking_camera.hit.map(|(entity, _)| entity);
+        let current = nearest.map(|(entity, _)| entity);
+        if previous != current {
+            if let Some(entity) = previous {
+                picking_events.send(PickingEvent::HoverEnded { entity });
+            }
+            if let Some((entity, hit)) = nearest {
+                picking_events.send(PickingEvent::HoverStarted { entity, hit });
+            }
+        }
+        if let Some((entity, hit)) = nearest {
+            if mouse_button_input.just_pressed(MouseButton::Left) {
+                picking_events.send(PickingEvent::Clicked { entity, hit });
+            }
+        }
+        picking_camera.ray = ray;
+        picking_camera.hit = nearest;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::{App, Events};
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::CorePlugin;
+    use bevy_ecs::schedule::{Stage, SystemStage};
+    use bevy_math::Vec3;
+    use bevy_render::{
+        camera::{CameraProjection, PerspectiveProjection},
+        mesh::shape,
+    };
+    use bevy_window::{Window, WindowDescriptor, WindowId};
+
+    #[test]
+    fn picks_nearest_entity_under_cursor() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .add_asset::<TextureAtlas>()
+            .add_event::<PickingEvent>()
+            .init_resource::<Input<MouseButton>>();
+        let world = &mut app.world;
+
+        let mut windows = Windows::default();
+        let mut window = Window::new(
+            WindowId::primary(),
+            &WindowDescriptor::default(),
+            800,
+            600,
+            1.0,
+            None,
+        );
+        window.update_cursor_position_from_backend(Some(Vec2::new(400.0, 300.0)));
+        windows.add(window);
+        world.insert_resource(windows);
+
+        let projection = PerspectiveProjection {
+            aspect_ratio: 800.0 / 600.0,
+            ..Default::default()
+        };
+        world.spawn().insert_bundle((
+            Camera {
+                projection_matrix: projection.get_projection_matrix(),
+                window: WindowId::primary(),
+                ..Default::default()
+            },
+            GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 10.0)),
+            PickingCamera::default(),
+        ));
+
+        let cube = world
+            .get_resource_mut::<Assets<Mesh>>()
+            .unwrap()
+            .add(Mesh::from(shape::Cube::new(1.0)));
+        let front = world
+            .spawn()
+            .insert_bundle((cube.clone(), GlobalTransform::identity(), Pickable))
+            .id();
+        world.spawn().insert_bundle((
+            cube.clone(),
+            GlobalTransform::from_translation(Vec3::new(0.0, 0.0, -3.0)),
+            Pickable,
+        ));
+        // closer, but not pickable
+        world
+            .spawn()
+            .insert_bundle((cube, GlobalTransform::from_translation(Vec3::Z * 5.0)));
+        let sprite = world
+            .spawn()
+            .insert_bundle((
+                Sprite::new(Vec2::new(10.0, 10.0)),
+                GlobalTransform::from_translation(Vec3::Z * 2.0),
+                Pickable,
+                Visible {
+                    is_visible: false,
+                    is_transparent: true,
+                },
+            ))
+            .id();
+
+        world
+            .get_resource_mut::<Input<MouseButton>>()
+            .unwrap()
+            .press(MouseButton::Left);
+        let mut stage = SystemStage::single(picking_system);
+        stage.run(world);
+
+        let events = world.get_resource::<Events<PickingEvent>>().unwrap();
+        let events = events
+            .get_reader()
+            .iter(events)
+            .cloned()
+            .collect::<Vec<_>>();
+        let hit = Intersection {
+            position: Vec3::new(0.0, 0.0, 0.5),
+            normal: Vec3::Z,
+            distance: 8.5,
+        };
+        assert_eq!(
+            events,
+            vec![
+                PickingEvent::HoverStarted { entity: front, hit },
+                PickingEvent::Clicked { entity: front, hit }
+            ]
+        );
+
+        // showing the sprite in front of the cube moves the hover to it
+        world.get_mut::<Visible>(sprite).unwrap().is_visible = true;
+        stage.run(world);
+        let mut camera_query = world.query::<&PickingCamera>();
+        let (entity, hit) = camera_query.iter(world).next().unwrap().hit().unwrap();
+        assert_eq!(entity, sprite);
+        assert_eq!(hit.distance, 7.0);
+        let events = world.get_resource::<Events<PickingEvent>>().unwrap();
+        let events = events
+            .iter_current_update_events()
+            .skip(2)
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0], PickingEvent::HoverEnded { entity: front });
+    }
+}
@@ -0,0 +1,311 @@
+use bevy_math::{Mat4, Vec2, Vec3};
+use bevy_render::{
+    camera::Camera,
+    mesh::{Indices, Mesh, VertexAttributeValues},
+    pipeline::PrimitiveTopology,
+};
+use bevy_transform::components::GlobalTransform;
+
+/// A half-line in world space, used to find the entities under the cursor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    /// Normalized direction of the ray
+    pub direction: Vec3,
+}
+
+/// Where a [`Ray`] hits an entity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Intersection {
+    /// World space position of the hit
+    pub position: Vec3,
+    /// World space normal of the surface at the hit
+    pub normal: Vec3,
+    /// Distance from the origin of the ray to the hit
+    pub distance: f32,
+}
+
+impl Ray {
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+
+    /// Builds the ray going through `cursor_position` from `camera`.
+    ///
+    /// `cursor_position` is in logical pixels from the bottom left corner of the window, as
+    /// returned by [`Window::cursor_position`](bevy_window::Window::cursor_position). The ray
+    /// starts on the near plane of the camera, which works for both perspective and orthographic
+    /// projections.
+    pub fn from_screenspace(
+        cursor_position: Vec2,
+        window_size: Vec2,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Option<Self> {
+        if window_size.x <= 0.0 || window_size.y <= 0.0 {
+            return None;
+        }
+        let ndc = cursor_position / window_size * 2.0 - Vec2::ONE;
+        let ndc_to_world: Mat4 =
+            camera_transform.compute_matrix() * camera.projection_matrix.inverse();
+        let near = ndc_to_world.project_point3(ndc.extend(0.0));
+        let far = ndc_to_world.project_point3(ndc.extend(1.0));
+        let direction = far - near;
+        if !near.is_finite() || !direction.is_finite() || direction.length_squared() == 0.0 {
+            return None;
+        }
+        Some(Self::new(near, direction))
+    }
+
+    /// The point at `distance` along the ray
+    pub fn position(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+
+    /// Intersects the ray with a triangle, from either side.
+    ///
+    /// Returns the distance along the ray and the barycentric coordinates of the hit relative to
+    /// the second and third vertices.
+    pub fn intersect_triangle(&self, triangle: [Vec3; 3]) -> Option<(f32, Vec2)> {
+        intersect_triangle(self.origin, self.direction, triangle)
+    }
+
+    /// Intersects the ray with the triangles of `mesh`, placed by `transform`
+    ///
+    /// Only meshes with a [`PrimitiveTopology::TriangleList`] topology can be hit.
+    pub fn intersect_mesh(&self, mesh: &Mesh, transform: &GlobalTransform) -> Option<Intersection> {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions,
+            _ => return None,
+        };
+        let normals = match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            Some(VertexAttributeValues::Float32x3(normals)) if normals.len() == positions.len() => {
+                Some(normals)
+            }
+            _ => None,
+        };
+
+        // the local direction is kept unnormalized so distances along it match world distances
+        let model = transform.compute_matrix();
+        let world_to_local = model.inverse();
+        let origin = world_to_local.transform_point3(self.origin);
+        let direction = world_to_local.transform_vector3(self.direction);
+
+        let mut nearest: Option<(f32, Vec2, [usize; 3])> = None;
+        let mut test = |indices: [usize; 3]| {
+            if indices.iter().any(|index| *index >= positions.len()) {
+                return;
+            }
+            let triangle = [
+                Vec3::from(positions[indices[0]]),
+                Vec3::from(positions[indices[1]]),
+                Vec3::from(positions[indices[2]]),
+            ];
+            if let Some((distance, barycentric)) = intersect_triangle(origin, direction, triangle) {
+                match nearest {
+                    Some((nearest_distance, _, _)) if nearest_distance <= distance => {}
+                    _ => nearest = Some((distance, barycentric, indices)),
+                }
+            }
+        };
+        match mesh.indices() {
+            Some(Indices::U16(indices)) => indices
+                .chunks_exact(3)
+                .for_each(|i| test([i[0] as usize, i[1] as usize, i[2] as usize])),
+            Some(Indices::U32(indices)) => indices
+                .chunks_exact(3)
+                .for_each(|i| test([i[0] as usize, i[1] as usize, i[2] as usize])),
+            None => (0..positions.len() / 3).for_each(|i| test([3 * i, 3 * i + 1, 3 * i + 2])),
+        }
+
+        let (distance, barycentric, indices) = nearest?;
+        let local_normal = match normals {
+            Some(normals) => {
+                Vec3::from(normals[indices[0]]) * (1.0 - barycentric.x - barycentric.y)
+                    + Vec3::from(normals[indices[1]]) * barycentric.x
+                    + Vec3::from(normals[indices[2]]) * barycentric.y
+            }
+            None => {
+                let a = Vec3::from(positions[indices[0]]);
+                let b = Vec3::from(positions[indices[1]]);
+                let c = Vec3::from(positions[indices[2]]);
+                (b - a).cross(c - a)
+            }
+        };
+        Some(Intersection {
+            position: self.position(distance),
+            normal: transform_normal(&world_to_local, local_normal),
+            distance,
+        })
+    }
+
+    /// Intersects the ray with a rectangle of `size` centered on the XY plane of `transform`,
+    /// like a [`Sprite`](bevy_sprite::Sprite)
+    pub fn intersect_rect(&self, size: Vec2, transform: &GlobalTransform) -> Option<Intersection> {
+        let world_to_local = transform.compute_matrix().inverse();
+        let origin = world_to_local.transform_point3(self.origin);
+        let direction = world_to_local.transform_vector3(self.direction);
+        if direction.z == 0.0 {
+            return None;
+        }
+        let distance = -origin.z / direction.z;
+        if distance < 0.0 {
+            return None;
+        }
+        let hit = origin + direction * distance;
+        if hit.x.abs() > size.x / 2.0 || hit.y.abs() > size.y / 2.0 {
+            return None;
+        }
+        Some(Intersection {
+            position: self.position(distance),
+            normal: transform_normal(&world_to_local, Vec3::Z),
+            distance,
+        })
+    }
+}
+
+/// Möller–Trumbore ray/triangle intersection
+fn intersect_triangle(origin: Vec3, direction: Vec3, triangle: [Vec3; 3]) -> Option<(f32, Vec2)> {
+    const EPSILON: f32 = 1e-7;
+    let edge1 = triangle[1] - triangle[0];
+    let edge2 = triangle[2] - triangle[0];
+    let p = direction.cross(edge2);
+    let determinant = edge1.dot(p);
+    if determinant.abs() < EPSILON {
+        // the ray is parallel to the triangle
+        return None;
+    }
+    let inverse_determinant = 1.0 / determinant;
+    let s = origin - triangle[0];
+    let u = s.dot(p) * inverse_determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(edge1);
+    let v = direction.dot(q) * inverse_determinant;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let distance = edge2.dot(q) * inverse_determinant;
+    if distance < 0.0 {
+        return None;
+    }
+    Some((distance, Vec2::new(u, v)))
+}
+
+fn transform_normal(world_to_local: &Mat4, normal: Vec3) -> Vec3 {
+    world_to_local
+        .transpose()
+        .transform_vector3(normal)
+        .normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Quat;
+    use bevy_render::{
+        camera::{CameraProjection, OrthographicProjection, PerspectiveProjection},
+        mesh::shape,
+    };
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!(a.abs_diff_eq(b, 1e-4), "{} != {}", a, b);
+    }
+
+    #[test]
+    fn ray_from_perspective_camera() {
+        let projection = PerspectiveProjection {
+            aspect_ratio: 2.0,
+            ..Default::default()
+        };
+        let camera = Camera {
+            projection_matrix: projection.get_projection_matrix(),
+            ..Default::default()
+        };
+        let transform = GlobalTransform::from_translation(Vec3::new(1.0, 2.0, 10.0))
+            .looking_at(Vec3::new(1.0, 2.0, 0.0), Vec3::Y);
+        let window_size = Vec2::new(800.0, 400.0);
+
+        let ray =
+            Ray::from_screenspace(window_size / 2.0, window_size, &camera, &transform).unwrap();
+        assert_near(ray.origin, Vec3::new(1.0, 2.0, 9.0));
+        assert_near(ray.direction, -Vec3::Z);
+
+        // the right edge of the screen is at half the horizontal field of view
+        let ray = Ray::from_screenspace(Vec2::new(800.0, 200.0), window_size, &camera, &transform)
+            .unwrap();
+        let half_fov_x = (projection.fov / 2.0).tan() * projection.aspect_ratio;
+        assert_near(ray.direction, Vec3::new(half_fov_x, 0.0, -1.0).normalize());
+    }
+
+    #[test]
+    fn ray_from_orthographic_camera() {
+        let mut projection = OrthographicProjection::default();
+        projection.update(800.0, 600.0);
+        let camera = Camera {
+            projection_matrix: projection.get_projection_matrix(),
+            ..Default::default()
+        };
+        let transform = GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 999.9));
+
+        let ray = Ray::from_screenspace(
+            Vec2::new(600.0, 100.0),
+            Vec2::new(800.0, 600.0),
+            &camera,
+            &transform,
+        )
+        .unwrap();
+        assert_near(ray.direction, -Vec3::Z);
+        assert_near(
+            ray.origin * Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(200.0, -200.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn ray_hits_transformed_mesh() {
+        let mesh = Mesh::from(shape::Cube::new(2.0));
+        let transform = GlobalTransform {
+            translation: Vec3::new(0.0, 0.0, -5.0),
+            rotation: Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+            scale: Vec3::splat(2.0),
+        };
+        let ray = Ray::new(Vec3::ZERO, -Vec3::Z);
+        let hit = ray.intersect_mesh(&mesh, &transform).unwrap();
+        assert!((hit.distance - 3.0).abs() < 1e-4);
+        assert_near(hit.position, Vec3::new(0.0, 0.0, -3.0));
+        assert_near(hit.normal, Vec3::Z);
+
+        let miss = Ray::new(Vec3::new(3.0, 0.0, 0.0), -Vec3::Z);
+        assert!(miss.intersect_mesh(&mesh, &transform).is_none());
+        let behind = Ray::new(Vec3::new(0.0, 0.0, -10.0), -Vec3::Z);
+        assert!(behind.intersect_mesh(&mesh, &transform).is_none());
+    }
+
+    #[test]
+    fn ray_hits_rect() {
+        let transform = GlobalTransform {
+            translation: Vec3::new(10.0, 0.0, 0.0),
+            scale: Vec3::new(2.0, 1.0, 1.0),
+            ..Default::default()
+        };
+        let size = Vec2::new(20.0, 10.0);
+        let ray = Ray::new(Vec3::new(29.0, 4.0, 100.0), -Vec3::Z);
+        let hit = ray.intersect_rect(size, &transform).unwrap();
+        assert_eq!(hit.distance, 100.0);
+        assert_near(hit.position, Vec3::new(29.0, 4.0, 0.0));
+        assert_near(hit.normal, Vec3::Z);
+
+        let outside = Ray::new(Vec3::new(31.0, 4.0, 100.0), -Vec3::Z);
+        assert!(outside.intersect_rect(size, &transform).is_none());
+        let away = Ray::new(Vec3::new(29.0, 4.0, 100.0), Vec3::Z);
+        assert!(away.intersect_rect(size, &transform).is_none());
+    }
+}
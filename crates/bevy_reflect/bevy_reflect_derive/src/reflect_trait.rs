@@ -34,16 +34,28 @@ pub fn reflect_trait(_args: TokenStream, input: TokenStream) -> TokenStream {
 
         #[derive(Clone)]
         pub struct #reflect_trait_ident {
-            get_func: fn(&dyn #bevy_reflect_path::Reflect) -> Option<&dyn #trait_ident>,
-            get_mut_func: fn(&mut dyn #bevy_reflect_path::Reflect) -> Option<&mut dyn #trait_ident>,
+            get_func: fn(&dyn #bevy_reflect_path::Reflect) -> Option<&(dyn #trait_ident + 'static)>,
+            get_mut_func: fn(&mut dyn #bevy_reflect_path::Reflect) -> Option<&mut (dyn #trait_ident + 'static)>,
         }
 
         impl #reflect_trait_ident {
-            fn get<'a>(&self, reflect_value: &'a dyn #bevy_reflect_path::Reflect) -> Option<&'a dyn #trait_ident> {
+            fn get<'a>(&self, reflect_value: &'a dyn #bevy_reflect_path::Reflect) -> Option<&'a (dyn #trait_ident + 'static)> {
                 (self.get_func)(reflect_value)
             }
 
-            fn get_mut<'a>(&self, reflect_value: &'a mut dyn #bevy_reflect_path::Reflect) -> Option<&'a mut dyn #trait_ident> {
+            fn get_mut<'a>(&self, reflect_value: &'a mut dyn #bevy_reflect_path::Reflect) -> Option<&'a mut (dyn #trait_ident + 'static)> {
+                (self.get_mut_func)(reflect_value)
+            }
+        }
+
+        impl #bevy_reflect_path::TraitTypeData for #reflect_trait_ident {
+            type Trait = dyn #trait_ident;
+
+            fn get<'a>(&self, reflect_value: &'a dyn #bevy_reflect_path::Reflect) -> Option<&'a Self::Trait> {
+                (self.get_func)(reflect_value)
+            }
+
+            fn get_mut<'a>(&self, reflect_value: &'a mut dyn #bevy_reflect_path::Reflect) -> Option<&'a mut Self::Trait> {
                 (self.get_mut_func)(reflect_value)
             }
         }
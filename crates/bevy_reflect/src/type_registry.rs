@@ -219,6 +219,21 @@ where
     }
 }
 
+/// [`TypeData`] that casts reflected values to trait objects of a trait.
+///
+/// This is implemented by the `ReflectMyTrait` type data generated by `#[reflect_trait]` on
+/// `MyTrait`, with `dyn MyTrait` as [`TraitTypeData::Trait`].
+pub trait TraitTypeData: TypeData + Clone {
+    /// The trait object type, like `dyn MyTrait`
+    type Trait: ?Sized + 'static;
+
+    /// Casts `value` to a trait object, if its type implements the trait.
+    fn get<'a>(&self, value: &'a dyn Reflect) -> Option<&'a Self::Trait>;
+
+    /// Casts `value` to a mutable trait object, if its type implements the trait.
+    fn get_mut<'a>(&self, value: &'a mut dyn Reflect) -> Option<&'a mut Self::Trait>;
+}
+
 /// Trait used to generate `TypeData` for trait reflection.
 ///
 /// This is used by the `#[derive(Reflect)]` macro to generate an implementation of [TypeData]
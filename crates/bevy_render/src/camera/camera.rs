@@ -1,4 +1,5 @@
 use super::CameraProjection;
+use crate::primitives::Frustum;
 use bevy_ecs::{
     change_detection::DetectChanges,
     component::Component,
@@ -112,3 +113,11 @@ pub fn camera_system<T: CameraProjection + Component>(
         }
     }
 }
+
+/// Updates the [`Frustum`] of the cameras from their projection and [`GlobalTransform`]
+pub fn update_frusta_system(mut query: Query<(&Camera, &GlobalTransform, &mut Frustum)>) {
+    for (camera, transform, mut frustum) in query.iter_mut() {
+        let view_projection = camera.projection_matrix * transform.compute_matrix().inverse();
+        *frustum = Frustum::from_view_projection(&view_projection);
+    }
+}
@@ -45,14 +45,14 @@ impl Default for PerspectiveProjection {
 }
 
 // TODO: make this a component instead of a property
-#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
 #[reflect_value(Serialize, Deserialize)]
 pub enum WindowOrigin {
     Center,
     BottomLeft,
 }
 
-#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Reflect, Serialize, Deserialize)]
 #[reflect_value(Serialize, Deserialize)]
 pub enum ScalingMode {
     /// Manually specify left/right/top/bottom values.
@@ -66,7 +66,7 @@ pub enum ScalingMode {
     FixedHorizontal,
 }
 
-#[derive(Debug, Clone, Reflect)]
+#[derive(Debug, Clone, Copy, Reflect)]
 #[reflect(Component)]
 pub struct OrthographicProjection {
     pub left: f32,
@@ -1,9 +1,19 @@
 use super::{Camera, DepthCalculation};
-use crate::{draw::OutsideFrustum, prelude::Visible};
+use crate::{
+    draw::OutsideFrustum,
+    prelude::Visible,
+    primitives::{Aabb, Frustum, NoFrustumCulling},
+};
 use bevy_core::FloatOrd;
-use bevy_ecs::{entity::Entity, query::Without, reflect::ReflectComponent, system::Query};
+use bevy_ecs::{
+    component::Disabled,
+    entity::Entity,
+    query::{IncludeDisabled, Without},
+    reflect::ReflectComponent,
+    system::Query,
+};
 use bevy_reflect::Reflect;
-use bevy_transform::prelude::GlobalTransform;
+use bevy_transform::prelude::{GlobalTransform, Parent};
 
 #[derive(Debug)]
 pub struct VisibleEntity {
@@ -200,17 +210,29 @@ mod rendering_mask_tests {
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn visible_entities_system(
     mut camera_query: Query<(
         &Camera,
         &GlobalTransform,
         &mut VisibleEntities,
         Option<&RenderLayers>,
+        Option<&Frustum>,
     )>,
-    visible_query: Query<(Entity, &Visible, Option<&RenderLayers>), Without<OutsideFrustum>>,
+    visible_query: Query<
+        (
+            Entity,
+            &Visible,
+            Option<&RenderLayers>,
+            Option<&Aabb>,
+            Option<&NoFrustumCulling>,
+        ),
+        Without<OutsideFrustum>,
+    >,
     visible_transform_query: Query<&GlobalTransform, Without<OutsideFrustum>>,
+    hierarchy_query: Query<(Option<&Parent>, Option<&Disabled>), IncludeDisabled>,
 ) {
-    for (camera, camera_global_transform, mut visible_entities, maybe_camera_mask) in
+    for (camera, camera_global_transform, mut visible_entities, maybe_camera_mask, maybe_frustum) in
         camera_query.iter_mut()
     {
         visible_entities.value.clear();
@@ -219,7 +241,9 @@ pub fn visible_entities_system(
 
         let mut no_transform_order = 0.0;
         let mut transparent_entities = Vec::new();
-        for (entity, visible, maybe_entity_mask) in visible_query.iter() {
+        for (entity, visible, maybe_entity_mask, maybe_aabb, maybe_no_frustum_culling) in
+            visible_query.iter()
+        {
             if !visible.is_visible {
                 continue;
             }
@@ -229,7 +253,24 @@ pub fn visible_entities_system(
                 continue;
             }
 
-            let order = if let Ok(global_transform) = visible_transform_query.get(entity) {
+            // disabled entities are skipped by the query, but not their descendants
+            if is_disabled_in_hierarchy(entity, &hierarchy_query) {
+                continue;
+            }
+
+            let maybe_global_transform = visible_transform_query.get(entity).ok();
+            if let (Some(frustum), Some(aabb), Some(global_transform), None) = (
+                maybe_frustum,
+                maybe_aabb,
+                maybe_global_transform,
+                maybe_no_frustum_culling,
+            ) {
+                if !frustum.intersects_obb(aabb, &global_transform.compute_matrix()) {
+                    continue;
+                }
+            }
+
+            let order = if let Some(global_transform) = maybe_global_transform {
                 let position = global_transform.translation;
                 // smaller distances are sorted to lower indices by using the distance from the
                 // camera
@@ -261,3 +302,121 @@ pub fn visible_entities_system(
         // to prevent holding unneeded memory
     }
 }
+
+/// Returns `true` if `entity` or one of its ancestors is [`Disabled`]
+fn is_disabled_in_hierarchy(
+    entity: Entity,
+    hierarchy_query: &Query<(Option<&Parent>, Option<&Disabled>), IncludeDisabled>,
+) -> bool {
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        match hierarchy_query.get(entity) {
+            Ok((_, Some(_))) => return true,
+            Ok((parent, None)) => current = parent.map(|parent| parent.0),
+            Err(_) => return false,
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod frustum_culling_tests {
+    use super::*;
+    use crate::camera::{CameraProjection, PerspectiveProjection};
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        world::World,
+    };
+    use bevy_math::Vec3;
+
+    #[test]
+    fn culls_entities_outside_frustum() {
+        let mut world = World::default();
+        let projection = PerspectiveProjection::default();
+        let camera_transform = GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 10.0));
+        let view_projection =
+            projection.get_projection_matrix() * camera_transform.compute_matrix().inverse();
+        let camera = world
+            .spawn()
+            .insert_bundle((
+                Camera::default(),
+                camera_transform,
+                VisibleEntities::default(),
+                Frustum::from_view_projection(&view_projection),
+            ))
+            .id();
+
+        let aabb = Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0));
+        let spawn = |world: &mut World, position: Vec3| {
+            world
+                .spawn()
+                .insert_bundle((
+                    Visible::default(),
+                    GlobalTransform::from_translation(position),
+                    aabb,
+                ))
+                .id()
+        };
+        let inside = spawn(&mut world, Vec3::ZERO);
+        let behind = spawn(&mut world, Vec3::new(0.0, 0.0, 20.0));
+        let unculled = spawn(&mut world, Vec3::new(0.0, 0.0, 20.0));
+        world.entity_mut(unculled).insert(NoFrustumCulling);
+        // entities without bounds are never culled
+        let unbounded = world
+            .spawn()
+            .insert_bundle((
+                Visible::default(),
+                GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 20.0)),
+            ))
+            .id();
+
+        SystemStage::single(visible_entities_system).run(&mut world);
+
+        let visible = world
+            .get::<VisibleEntities>(camera)
+            .unwrap()
+            .iter()
+            .map(|visible_entity| visible_entity.entity)
+            .collect::<Vec<_>>();
+        assert!(visible.contains(&inside));
+        assert!(!visible.contains(&behind));
+        assert!(visible.contains(&unculled));
+        assert!(visible.contains(&unbounded));
+    }
+
+    #[test]
+    fn skips_disabled_subtrees() {
+        let mut world = World::default();
+        let camera = world
+            .spawn()
+            .insert_bundle((
+                Camera::default(),
+                GlobalTransform::identity(),
+                VisibleEntities::default(),
+            ))
+            .id();
+
+        let root = world.spawn().insert(Visible::default()).id();
+        let child = world
+            .spawn()
+            .insert_bundle((Visible::default(), Parent(root)))
+            .id();
+        let grandchild = world
+            .spawn()
+            .insert_bundle((Visible::default(), Parent(child)))
+            .id();
+        world.entity_mut(child).insert(Disabled);
+
+        SystemStage::single(visible_entities_system).run(&mut world);
+
+        let visible = world
+            .get::<VisibleEntities>(camera)
+            .unwrap()
+            .iter()
+            .map(|visible_entity| visible_entity.entity)
+            .collect::<Vec<_>>();
+        assert!(visible.contains(&root));
+        assert!(!visible.contains(&child));
+        assert!(!visible.contains(&grandchild));
+    }
+}
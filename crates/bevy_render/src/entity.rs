@@ -5,6 +5,7 @@ use crate::{
     },
     pipeline::RenderPipelines,
     prelude::Visible,
+    primitives::Frustum,
     render_graph::base,
     Draw, Mesh,
 };
@@ -33,6 +34,7 @@ pub struct PerspectiveCameraBundle {
     pub camera: Camera,
     pub perspective_projection: PerspectiveProjection,
     pub visible_entities: VisibleEntities,
+    pub frustum: Frustum,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
 }
@@ -50,6 +52,7 @@ impl PerspectiveCameraBundle {
             },
             perspective_projection: Default::default(),
             visible_entities: Default::default(),
+            frustum: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
         }
@@ -65,6 +68,7 @@ impl Default for PerspectiveCameraBundle {
             },
             perspective_projection: Default::default(),
             visible_entities: Default::default(),
+            frustum: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
         }
@@ -79,6 +83,7 @@ pub struct OrthographicCameraBundle {
     pub camera: Camera,
     pub orthographic_projection: OrthographicProjection,
     pub visible_entities: VisibleEntities,
+    pub frustum: Frustum,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
 }
@@ -99,6 +104,7 @@ impl OrthographicCameraBundle {
                 ..Default::default()
             },
             visible_entities: Default::default(),
+            frustum: Default::default(),
             transform: Transform::from_xyz(0.0, 0.0, far - 0.1),
             global_transform: Default::default(),
         }
@@ -116,6 +122,7 @@ impl OrthographicCameraBundle {
                 ..Default::default()
             },
             visible_entities: Default::default(),
+            frustum: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
         }
@@ -129,6 +136,7 @@ impl OrthographicCameraBundle {
             },
             orthographic_projection: Default::default(),
             visible_entities: Default::default(),
+            frustum: Default::default(),
             transform: Default::default(),
             global_transform: Default::default(),
         }
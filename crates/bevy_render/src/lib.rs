@@ -6,6 +6,7 @@ pub mod entity;
 pub mod mesh;
 pub mod pass;
 pub mod pipeline;
+pub mod primitives;
 pub mod render_graph;
 pub mod renderer;
 pub mod shader;
@@ -46,10 +47,12 @@ use camera::{
     ActiveCameras, Camera, DepthCalculation, OrthographicProjection, PerspectiveProjection,
     RenderLayers, ScalingMode, VisibleEntities, WindowOrigin,
 };
+use mesh::{SkinnedMesh, SkinnedMeshInverseBindposes};
 use pipeline::{
     IndexFormat, PipelineCompiler, PipelineDescriptor, PipelineSpecialization, PrimitiveTopology,
     ShaderSpecialization, VertexBufferLayout,
 };
+use primitives::{Aabb, Frustum, NoFrustumCulling};
 use render_graph::{
     base::{self, BaseRenderGraphConfig, MainPass},
     RenderGraph,
@@ -69,6 +72,10 @@ use texture::ImageTextureLoader;
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum RenderSystem {
+    /// Computes the [`Aabb`](primitives::Aabb) of the entities with a [`Mesh`]
+    CalculateBounds,
+    /// Computes the [`Frustum`](primitives::Frustum) of the cameras
+    UpdateFrusta,
     VisibleEntities,
 }
 
@@ -145,6 +152,7 @@ impl Plugin for RenderPlugin {
         )
         .init_asset_loader::<ShaderLoader>()
         .add_asset::<Mesh>()
+        .add_asset::<SkinnedMeshInverseBindposes>()
         .add_asset::<Texture>()
         .add_asset::<Shader>()
         .add_asset::<PipelineDescriptor>()
@@ -153,10 +161,14 @@ impl Plugin for RenderPlugin {
         .register_type::<Draw>()
         .register_type::<Visible>()
         .register_type::<OutsideFrustum>()
+        .register_type::<Aabb>()
+        .register_type::<Frustum>()
+        .register_type::<NoFrustumCulling>()
         .register_type::<RenderPipelines>()
         .register_type::<OrthographicProjection>()
         .register_type::<PerspectiveProjection>()
         .register_type::<MainPass>()
+        .register_type::<SkinnedMesh>()
         .register_type::<VisibleEntities>()
         .register_type::<Color>()
         .register_type::<ShaderSpecialization>()
@@ -179,11 +191,28 @@ impl Plugin for RenderPlugin {
         .add_system_to_stage(CoreStage::PostUpdate, camera::active_cameras_system)
         .add_system_to_stage(
             CoreStage::PostUpdate,
-            camera::camera_system::<OrthographicProjection>.before(RenderSystem::VisibleEntities),
+            camera::camera_system::<OrthographicProjection>.before(RenderSystem::UpdateFrusta),
         )
         .add_system_to_stage(
             CoreStage::PostUpdate,
-            camera::camera_system::<PerspectiveProjection>.before(RenderSystem::VisibleEntities),
+            camera::camera_system::<PerspectiveProjection>.before(RenderSystem::UpdateFrusta),
+        )
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            camera::update_frusta_system
+                .label(RenderSystem::UpdateFrusta)
+                .after(TransformSystem::TransformPropagate)
+                .before(RenderSystem::VisibleEntities),
+        )
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            mesh::calculate_bounds_system
+                .label(RenderSystem::CalculateBounds)
+                .before(RenderSystem::VisibleEntities),
+        )
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            mesh::skinned_mesh_joints_system.after(TransformSystem::TransformPropagate),
         )
         .add_system_to_stage(
             CoreStage::PostUpdate,
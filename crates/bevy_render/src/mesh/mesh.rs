@@ -1,7 +1,9 @@
 mod conversions;
 
+use super::SkinnedMesh;
 use crate::{
     pipeline::{IndexFormat, PrimitiveTopology, RenderPipelines, VertexFormat},
+    primitives::{Aabb, NoFrustumCulling},
     renderer::{BufferInfo, BufferUsage, RenderResourceContext, RenderResourceId},
 };
 use bevy_asset::{AssetEvent, Assets, Handle};
@@ -9,8 +11,8 @@ use bevy_core::cast_slice;
 use bevy_ecs::{
     entity::Entity,
     event::EventReader,
-    query::{Changed, With},
-    system::{Local, Query, QuerySet, Res},
+    query::{Changed, Or, With, Without},
+    system::{Commands, Local, Query, QuerySet, Res},
     world::Mut,
 };
 use bevy_math::*;
@@ -468,6 +470,17 @@ impl Mesh {
 
         self.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
     }
+
+    /// Computes the [`Aabb`] of the [`Mesh::ATTRIBUTE_POSITION`] of a mesh, if it has any
+    pub fn compute_aabb(&self) -> Option<Aabb> {
+        let positions = self.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+        let mut positions = positions.iter().map(|position| Vec3::from(*position));
+        let first = positions.next()?;
+        let (minimum, maximum) = positions.fold((first, first), |(minimum, maximum), position| {
+            (minimum.min(position), maximum.max(position))
+        });
+        Some(Aabb::from_min_max(minimum, maximum))
+    }
 }
 
 fn face_normal(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> [f32; 3] {
@@ -495,6 +508,68 @@ fn remove_current_mesh_resources(
     remove_resource_save(render_resource_context, handle, INDEX_BUFFER_ASSET_INDEX);
 }
 
+/// Adds an [`Aabb`] to the entities with a [`Mesh`], and keeps it up to date when the mesh
+/// changes. Entities whose mesh isn't available, or has no positions, are left without bounds.
+///
+/// Skinned meshes are left without bounds, as their vertices move away from the ones of their
+/// mesh.
+#[allow(clippy::type_complexity)]
+pub fn calculate_bounds_system(
+    mut commands: Commands,
+    meshes: Res<Assets<Mesh>>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    queries: QuerySet<(
+        Query<
+            (Entity, &Handle<Mesh>, Option<&Aabb>),
+            (
+                Without<NoFrustumCulling>,
+                Without<SkinnedMesh>,
+                Or<(Without<Aabb>, Changed<Handle<Mesh>>)>,
+            ),
+        >,
+        Query<
+            (Entity, &Handle<Mesh>, Option<&Aabb>),
+            (Without<NoFrustumCulling>, Without<SkinnedMesh>),
+        >,
+    )>,
+) {
+    let mut update_bounds = |entity: Entity, handle: &Handle<Mesh>, aabb: Option<&Aabb>| {
+        match meshes.get(handle).and_then(|mesh| mesh.compute_aabb()) {
+            Some(aabb) => {
+                commands.entity(entity).insert(aabb);
+            }
+            // the bounds of the previous mesh would cull the entity wrongly
+            None if aabb.is_some() => {
+                commands.entity(entity).remove::<Aabb>();
+            }
+            None => {}
+        }
+    };
+
+    let mut changed_meshes = HashSet::default();
+    for event in mesh_events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                changed_meshes.insert(handle.clone_weak());
+            }
+            AssetEvent::Removed { .. } => {}
+        }
+    }
+    if !changed_meshes.is_empty() {
+        for (entity, handle, aabb) in queries.q1().iter() {
+            if changed_meshes.contains(handle) {
+                update_bounds(entity, handle, aabb);
+            }
+        }
+    }
+
+    for (entity, handle, aabb) in queries.q0().iter() {
+        if !changed_meshes.contains(handle) {
+            update_bounds(entity, handle, aabb);
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct MeshEntities {
     entities: HashSet<Entity>,
@@ -633,3 +708,47 @@ fn update_entity_mesh(
         render_pipelines.bindings.vertex_attribute_buffer = Some(vertex_attribute_buffer_resource);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::shape;
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, HandleId};
+    use bevy_core::CorePlugin;
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        system::IntoSystem,
+    };
+
+    #[test]
+    fn calculate_bounds() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>();
+        let world = &mut app.world;
+        let small = Mesh::from(shape::Cube { size: 1.0 });
+        let large = Mesh::from(shape::Cube { size: 4.0 });
+        let large_aabb = large.compute_aabb().unwrap();
+        let handle = world.get_resource_mut::<Assets<Mesh>>().unwrap().add(small);
+        let entity = world.spawn().insert(handle).id();
+        let mut stage = SystemStage::single(calculate_bounds_system.system());
+        stage.run(world);
+        assert!(world.get::<Aabb>(entity).is_some());
+
+        // the mesh changes to one that isn't loaded yet
+        let handle = Handle::<Mesh>::weak(HandleId::random::<Mesh>());
+        world.entity_mut(entity).insert(handle.clone());
+        stage.run(world);
+        assert!(world.get::<Aabb>(entity).is_none());
+
+        world
+            .get_resource_mut::<Assets<Mesh>>()
+            .unwrap()
+            .set_untracked(handle, large);
+        stage.run(world);
+        let aabb = world.get::<Aabb>(entity).unwrap();
+        assert_eq!(aabb.half_extents, large_aabb.half_extents);
+    }
+}
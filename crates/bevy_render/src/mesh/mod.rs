@@ -2,5 +2,7 @@
 mod mesh;
 /// Generation for some primitive shape meshes.
 pub mod shape;
+mod skinning;
 
 pub use mesh::*;
+pub use skinning::*;
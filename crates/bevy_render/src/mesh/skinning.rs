@@ -0,0 +1,270 @@
+use super::Mesh;
+use crate::{
+    self as bevy_render,
+    renderer::RenderResources,
+    shader::{ShaderDefIterator, ShaderDefs},
+};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+    reflect::{ReflectComponent, ReflectMapEntities},
+    system::{Commands, Query, Res},
+};
+use bevy_math::Mat4;
+use bevy_reflect::{Reflect, TypeUuid};
+use bevy_transform::components::GlobalTransform;
+use std::ops::Deref;
+
+/// The maximum number of joints of a [`SkinnedMesh`] that can be rendered, which is the size of
+/// the joint matrix array of the mesh shaders
+pub const MAX_JOINTS: usize = 256;
+
+/// Binds a mesh to the joint entities of a skeleton. The vertices of the mesh are influenced by
+/// the joints listed in their [`Mesh::ATTRIBUTE_JOINT_INDEX`](super::Mesh::ATTRIBUTE_JOINT_INDEX)
+/// attribute, weighted by their [`Mesh::ATTRIBUTE_JOINT_WEIGHT`](super::Mesh::ATTRIBUTE_JOINT_WEIGHT)
+/// attribute.
+#[derive(Debug, Default, Clone, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct SkinnedMesh {
+    pub inverse_bindposes: Handle<SkinnedMeshInverseBindposes>,
+    pub joints: Vec<Entity>,
+}
+
+impl MapEntities for SkinnedMesh {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        for joint in self.joints.iter_mut() {
+            *joint = entity_map.get(*joint)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl SkinnedMesh {
+    /// Computes the matrix of every joint, which transforms a vertex from its bind pose to the
+    /// current pose of the skeleton. `joint_transforms` must yield the [`GlobalTransform`] of
+    /// every joint, in the order of [`SkinnedMesh::joints`].
+    pub fn joint_matrices<'a>(
+        &self,
+        inverse_bindposes: &SkinnedMeshInverseBindposes,
+        joint_transforms: impl IntoIterator<Item = &'a GlobalTransform>,
+    ) -> Vec<Mat4> {
+        joint_transforms
+            .into_iter()
+            .zip(inverse_bindposes.iter())
+            .map(|(transform, inverse_bindpose)| transform.compute_matrix() * *inverse_bindpose)
+            .collect()
+    }
+}
+
+/// The joint matrices of a [`SkinnedMesh`] for the current frame, bound to the shaders as the
+/// `SkinnedMeshJoints_joint_matrices` uniform. Its `SKINNED` shader def makes the mesh shaders
+/// transform the vertices by their joints rather than by the `Transform` uniform.
+///
+/// It is kept up to date by [`skinned_mesh_joints_system`], while the mesh, its inverse bind
+/// matrices and its joints are available.
+#[derive(Debug, Clone, RenderResources)]
+pub struct SkinnedMeshJoints {
+    pub joint_matrices: Box<[Mat4; MAX_JOINTS]>,
+}
+
+impl SkinnedMeshJoints {
+    /// Creates the joints from the matrices computed by [`SkinnedMesh::joint_matrices`]. There
+    /// must be at most [`MAX_JOINTS`] of them.
+    pub fn new(joint_matrices: &[Mat4]) -> Self {
+        let mut joints = SkinnedMeshJoints {
+            joint_matrices: Box::new([Mat4::IDENTITY; MAX_JOINTS]),
+        };
+        joints.set(joint_matrices);
+        joints
+    }
+
+    /// Replaces the joint matrices. There must be at most [`MAX_JOINTS`] of them.
+    pub fn set(&mut self, joint_matrices: &[Mat4]) {
+        self.joint_matrices[..joint_matrices.len()].copy_from_slice(joint_matrices);
+    }
+}
+
+impl ShaderDefs for SkinnedMeshJoints {
+    fn shader_defs_len(&self) -> usize {
+        1
+    }
+
+    fn get_shader_def(&self, index: usize) -> Option<&str> {
+        match index {
+            0 => Some("SKINNED"),
+            _ => None,
+        }
+    }
+
+    fn iter_shader_defs(&self) -> ShaderDefIterator<'_> {
+        ShaderDefIterator::new(self)
+    }
+}
+
+/// Updates the [`SkinnedMeshJoints`] of every [`SkinnedMesh`] from the [`GlobalTransform`] of its
+/// joints.
+///
+/// A skinned mesh is only rendered skinned when its mesh has both the
+/// [`Mesh::ATTRIBUTE_JOINT_INDEX`] and [`Mesh::ATTRIBUTE_JOINT_WEIGHT`] attributes, its inverse bind
+/// matrices are loaded, it has at most [`MAX_JOINTS`] joints and every joint has a
+/// [`GlobalTransform`]. It is rendered in its bind pose otherwise.
+pub fn skinned_mesh_joints_system(
+    mut commands: Commands,
+    meshes: Res<Assets<Mesh>>,
+    inverse_bindposes: Res<Assets<SkinnedMeshInverseBindposes>>,
+    mut skinned_meshes: Query<(
+        Entity,
+        &SkinnedMesh,
+        &Handle<Mesh>,
+        Option<&mut SkinnedMeshJoints>,
+    )>,
+    joint_transforms: Query<&GlobalTransform>,
+) {
+    for (entity, skinned_mesh, mesh_handle, joints) in skinned_meshes.iter_mut() {
+        let joint_matrices = match (
+            meshes.get(mesh_handle),
+            inverse_bindposes.get(&skinned_mesh.inverse_bindposes),
+        ) {
+            (Some(mesh), Some(inverse_bindposes))
+                if mesh.attribute(Mesh::ATTRIBUTE_JOINT_INDEX).is_some()
+                    && mesh.attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT).is_some()
+                    && skinned_mesh.joints.len() <= MAX_JOINTS =>
+            {
+                skinned_mesh
+                    .joints
+                    .iter()
+                    .map(|&joint| joint_transforms.get(joint).ok())
+                    .collect::<Option<Vec<_>>>()
+                    .map(|transforms| skinned_mesh.joint_matrices(inverse_bindposes, transforms))
+            }
+            _ => None,
+        };
+
+        match (joint_matrices, joints) {
+            (Some(joint_matrices), Some(mut joints)) => joints.set(&joint_matrices),
+            (Some(joint_matrices), None) => {
+                commands
+                    .entity(entity)
+                    .insert(SkinnedMeshJoints::new(&joint_matrices));
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<SkinnedMeshJoints>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// The inverse bind matrices of the joints of a [`SkinnedMesh`], which transform vertices from
+/// model space to the local space of each joint.
+#[derive(Debug, TypeUuid)]
+#[uuid = "b9f155a9-54ec-4026-988f-e0a03e99a76f"]
+pub struct SkinnedMeshInverseBindposes(Box<[Mat4]>);
+
+impl From<Vec<Mat4>> for SkinnedMeshInverseBindposes {
+    fn from(value: Vec<Mat4>) -> Self {
+        Self(value.into_boxed_slice())
+    }
+}
+
+impl Deref for SkinnedMeshInverseBindposes {
+    type Target = [Mat4];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mesh::VertexAttributeValues, pipeline::PrimitiveTopology};
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::CorePlugin;
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        system::IntoSystem,
+    };
+    use bevy_math::Vec3;
+
+    #[test]
+    fn joint_matrices() {
+        let inverse_bindposes = SkinnedMeshInverseBindposes::from(vec![
+            Mat4::IDENTITY,
+            Mat4::from_translation(-Vec3::Y),
+        ]);
+        let skinned_mesh = SkinnedMesh {
+            joints: vec![Entity::new(0), Entity::new(1)],
+            ..Default::default()
+        };
+        let joint_transforms = [
+            GlobalTransform::from_translation(Vec3::X),
+            GlobalTransform::from_translation(Vec3::Y * 2.0),
+        ];
+        let matrices = skinned_mesh.joint_matrices(&inverse_bindposes, joint_transforms.iter());
+        assert_eq!(matrices.len(), 2);
+        assert_eq!(matrices[0].transform_point3(Vec3::ZERO), Vec3::X);
+        // a vertex bound at the joint's rest position follows the joint
+        assert_eq!(matrices[1].transform_point3(Vec3::Y), Vec3::Y * 2.0);
+    }
+
+    #[test]
+    fn skinned_mesh_joints() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .add_asset::<SkinnedMeshInverseBindposes>();
+        let world = &mut app.world;
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0, 1.0, 0.0]]);
+        mesh.set_attribute(
+            Mesh::ATTRIBUTE_JOINT_INDEX,
+            VertexAttributeValues::Uint16x4(vec![[0, 1, 0, 0]]),
+        );
+        mesh.set_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, vec![[0.5, 0.5, 0.0, 0.0]]);
+        let mesh = world.get_resource_mut::<Assets<Mesh>>().unwrap().add(mesh);
+        let inverse_bindposes = world
+            .get_resource_mut::<Assets<SkinnedMeshInverseBindposes>>()
+            .unwrap()
+            .add(SkinnedMeshInverseBindposes::from(vec![
+                Mat4::IDENTITY,
+                Mat4::from_translation(-Vec3::Y),
+            ]));
+        let root = world
+            .spawn()
+            .insert(GlobalTransform::from_translation(Vec3::X))
+            .id();
+        let tip = world
+            .spawn()
+            .insert(GlobalTransform::from_translation(Vec3::Y * 2.0))
+            .id();
+        let skinned = world
+            .spawn()
+            .insert_bundle((
+                mesh,
+                SkinnedMesh {
+                    inverse_bindposes,
+                    joints: vec![root, tip],
+                },
+            ))
+            .id();
+
+        let mut stage = SystemStage::single(skinned_mesh_joints_system.system());
+        stage.run(world);
+        let joints = world.get::<SkinnedMeshJoints>(skinned).unwrap();
+        assert_eq!(
+            joints.joint_matrices[1].transform_point3(Vec3::Y),
+            Vec3::Y * 2.0
+        );
+        assert_eq!(joints.iter_shader_defs().collect::<Vec<_>>(), ["SKINNED"]);
+
+        // the mesh is rendered in its bind pose once a joint is missing
+        world.despawn(tip);
+        stage.run(world);
+        assert!(world.get::<SkinnedMeshJoints>(skinned).is_none());
+    }
+}
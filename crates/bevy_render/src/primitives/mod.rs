@@ -0,0 +1,203 @@
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_math::{Mat4, Vec3, Vec4};
+use bevy_reflect::Reflect;
+
+/// An axis-aligned bounding box, in the local space of an entity
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Aabb {
+    pub center: Vec3,
+    pub half_extents: Vec3,
+}
+
+impl Aabb {
+    pub fn from_min_max(minimum: Vec3, maximum: Vec3) -> Self {
+        Self {
+            center: (maximum + minimum) * 0.5,
+            half_extents: (maximum - minimum) * 0.5,
+        }
+    }
+
+    pub fn min(&self) -> Vec3 {
+        self.center - self.half_extents
+    }
+
+    pub fn max(&self) -> Vec3 {
+        self.center + self.half_extents
+    }
+
+    /// The smallest sphere containing the box
+    pub fn bounding_sphere(&self) -> Sphere {
+        Sphere {
+            center: self.center,
+            radius: self.half_extents.length(),
+        }
+    }
+
+    /// The radius of the box projected on `axis`, once transformed by `model`
+    fn relative_radius(&self, axis: Vec3, model: &Mat4) -> f32 {
+        let half_extents = self.half_extents;
+        (axis.dot(model.x_axis.truncate()) * half_extents.x).abs()
+            + (axis.dot(model.y_axis.truncate()) * half_extents.y).abs()
+            + (axis.dot(model.z_axis.truncate()) * half_extents.z).abs()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Sphere {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+/// A plane, where points `p` with `normal.dot(p) + d > 0` are on the side the normal points to
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Plane {
+    /// The normal of the plane in `xyz`, and `d` in `w`
+    pub normal_d: Vec4,
+}
+
+impl Plane {
+    /// Builds a plane from an unnormalized normal and `d`. A zero normal makes a plane no point
+    /// is ever behind.
+    pub fn new(normal_d: Vec4) -> Self {
+        let length = normal_d.truncate().length();
+        if length > 0.0 {
+            Self {
+                normal_d: normal_d / length,
+            }
+        } else {
+            Self {
+                normal_d: Vec4::ZERO,
+            }
+        }
+    }
+
+    pub fn normal(&self) -> Vec3 {
+        self.normal_d.truncate()
+    }
+
+    pub fn d(&self) -> f32 {
+        self.normal_d.w
+    }
+
+    /// Signed distance from the plane to `point`, positive on the side the normal points to
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        self.normal().dot(point) + self.d()
+    }
+}
+
+/// The volume seen by a [`Camera`](crate::camera::Camera), as six planes pointing inward.
+///
+/// Cameras with this component only see the entities whose [`Aabb`] intersects it.
+#[derive(Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct Frustum {
+    /// In order: left, right, bottom, top, near and far
+    #[reflect(ignore)]
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// Extracts the planes of the frustum from a view projection matrix, mapping depth to `0..1`
+    pub fn from_view_projection(view_projection: &Mat4) -> Self {
+        let row0 = view_projection.row(0);
+        let row1 = view_projection.row(1);
+        let row2 = view_projection.row(2);
+        let row3 = view_projection.row(3);
+        Self {
+            planes: [
+                Plane::new(row3 + row0),
+                Plane::new(row3 - row0),
+                Plane::new(row3 + row1),
+                Plane::new(row3 - row1),
+                Plane::new(row2),
+                Plane::new(row3 - row2),
+            ],
+        }
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(sphere.center) + sphere.radius >= 0.0)
+    }
+
+    /// Whether the box, once transformed by `model`, intersects the frustum. Boxes near its
+    /// corners may be reported as intersecting without actually being in it.
+    pub fn intersects_obb(&self, aabb: &Aabb, model: &Mat4) -> bool {
+        let center = model.transform_point3(aabb.center);
+        self.planes.iter().all(|plane| {
+            plane.signed_distance(center) + aabb.relative_radius(plane.normal(), model) >= 0.0
+        })
+    }
+}
+
+/// Opts an entity out of frustum culling: it is drawn by the cameras it is visible to, whether
+/// its [`Aabb`] is in their [`Frustum`] or not
+#[derive(Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct NoFrustumCulling;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Quat;
+
+    fn perspective_frustum() -> Frustum {
+        // looking at -Z from the origin, with a 90 degrees field of view
+        let projection = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, 1.0, 100.0);
+        Frustum::from_view_projection(&projection)
+    }
+
+    #[test]
+    fn frustum_planes_from_perspective() {
+        let frustum = perspective_frustum();
+        let near = frustum.planes[4];
+        assert!((near.signed_distance(Vec3::new(0.0, 0.0, -1.0))).abs() < 1e-5);
+        assert!((near.normal() - -Vec3::Z).length() < 1e-5);
+        let far = frustum.planes[5];
+        assert!((far.signed_distance(Vec3::new(0.0, 0.0, -100.0))).abs() < 1e-3);
+        let left = frustum.planes[0];
+        assert!(left.signed_distance(Vec3::new(-9.0, 0.0, -10.0)) > 0.0);
+        assert!(left.signed_distance(Vec3::new(-11.0, 0.0, -10.0)) < 0.0);
+    }
+
+    #[test]
+    fn sphere_intersection() {
+        let frustum = perspective_frustum();
+        let sphere = |x: f32, z: f32| Sphere {
+            center: Vec3::new(x, 0.0, z),
+            radius: 1.0,
+        };
+        assert!(frustum.intersects_sphere(&sphere(0.0, -50.0)));
+        assert!(frustum.intersects_sphere(&sphere(0.0, -0.5)));
+        assert!(frustum.intersects_sphere(&sphere(10.5, -10.0)));
+        assert!(!frustum.intersects_sphere(&sphere(0.0, 2.5)));
+        assert!(!frustum.intersects_sphere(&sphere(0.0, -102.0)));
+        assert!(!frustum.intersects_sphere(&sphere(15.0, -10.0)));
+    }
+
+    #[test]
+    fn obb_intersection() {
+        let frustum = perspective_frustum();
+        let aabb = Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0));
+        assert_eq!(aabb.min(), Vec3::splat(-1.0));
+        assert!(frustum.intersects_obb(&aabb, &Mat4::from_translation(Vec3::new(0.0, 0.0, -50.0))));
+        assert!(!frustum.intersects_obb(&aabb, &Mat4::from_translation(Vec3::new(0.0, 0.0, 5.0))));
+        // a thin box just left of the frustum, only reaching into it once rotated
+        let thin = Aabb::from_min_max(Vec3::new(-0.1, -4.0, -0.1), Vec3::new(0.1, 4.0, 0.1));
+        let position = Vec3::new(-12.5, 0.0, -10.0);
+        assert!(!frustum.intersects_obb(&thin, &Mat4::from_translation(position)));
+        let rotated = Mat4::from_rotation_translation(
+            Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            position,
+        );
+        assert!(frustum.intersects_obb(&thin, &rotated));
+        // scale is taken into account
+        let behind = Vec3::new(0.0, 0.0, 2.0);
+        assert!(!frustum.intersects_obb(&aabb, &Mat4::from_translation(behind)));
+        let scaled =
+            Mat4::from_scale_rotation_translation(Vec3::splat(4.0), Quat::IDENTITY, behind);
+        assert!(frustum.intersects_obb(&aabb, &scaled));
+    }
+}
@@ -0,0 +1,141 @@
+use super::{Edge, NodeState, RenderGraph, ResourceSlots};
+use bevy_app::{App, Plugin};
+use bevy_ecs::system::{Local, Res};
+use bevy_utils::tracing::{error, info};
+use std::path::PathBuf;
+
+/// Formats `graph` in the [Graphviz](https://graphviz.org) DOT language, to debug how its nodes
+/// are connected.
+///
+/// Each node shows its input slots on the left and its output slots on the right. Solid edges
+/// connect an output slot to an input slot, and dashed edges are node edges, which only order the
+/// nodes.
+pub fn render_graph_to_dot(graph: &RenderGraph) -> String {
+    let mut nodes = graph.iter_nodes().collect::<Vec<_>>();
+    nodes.sort_by_key(|node| node_name(node));
+
+    let mut dot = String::new();
+    dot.push_str("digraph render_graph {\n");
+    dot.push_str("    rankdir=LR;\n");
+    dot.push_str("    node [shape=record];\n");
+    for node in nodes.iter() {
+        dot.push_str(&format!(
+            "    \"{}\" [label=\"{{{{{}}}|{}\\n({})|{{{}}}}}\"];\n",
+            node.id.uuid(),
+            slots_label("i", &node.input_slots),
+            escape_record(&node_name(node)),
+            escape_record(node.type_name),
+            slots_label("o", &node.output_slots),
+        ));
+    }
+    for node in nodes.iter() {
+        for edge in node.edges.output_edges.iter() {
+            match edge {
+                Edge::SlotEdge {
+                    input_node,
+                    input_index,
+                    output_node,
+                    output_index,
+                } => dot.push_str(&format!(
+                    "    \"{}\":o{} -> \"{}\":i{};\n",
+                    output_node.uuid(),
+                    output_index,
+                    input_node.uuid(),
+                    input_index
+                )),
+                Edge::NodeEdge {
+                    input_node,
+                    output_node,
+                } => dot.push_str(&format!(
+                    "    \"{}\" -> \"{}\" [style=dashed];\n",
+                    output_node.uuid(),
+                    input_node.uuid()
+                )),
+            }
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+fn node_name(node: &NodeState) -> String {
+    match &node.name {
+        Some(name) => name.to_string(),
+        None => node.id.uuid().to_string(),
+    }
+}
+
+/// Formats the fields of a record label for the slots, with the ports `{port_prefix}{index}`
+fn slots_label(port_prefix: &str, slots: &ResourceSlots) -> String {
+    slots
+        .iter()
+        .enumerate()
+        .map(|(index, slot)| {
+            format!(
+                "<{}{}> {}: {:?}",
+                port_prefix,
+                index,
+                escape_record(&slot.info.name),
+                slot.info.resource_type
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Escapes the characters that have a meaning in quoted record labels
+fn escape_record(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for c in string.chars() {
+        if matches!(c, '\\' | '"' | '{' | '}' | '|' | '<' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Writes the [`RenderGraph`] to a file in the Graphviz DOT language, as formatted by
+/// [`render_graph_to_dot`].
+///
+/// The file is written after the first frame is rendered, once the nodes added at startup are in
+/// the graph. Render it with `dot -Tsvg render_graph.dot`.
+pub struct RenderGraphDotPlugin {
+    pub path: PathBuf,
+}
+
+impl Default for RenderGraphDotPlugin {
+    fn default() -> Self {
+        RenderGraphDotPlugin {
+            path: PathBuf::from("render_graph.dot"),
+        }
+    }
+}
+
+struct RenderGraphDotPath(PathBuf);
+
+impl Plugin for RenderGraphDotPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(RenderGraphDotPath(self.path.clone()))
+            .add_system_to_stage(crate::RenderStage::PostRender, render_graph_dot_system);
+    }
+}
+
+fn render_graph_dot_system(
+    mut written: Local<bool>,
+    path: Res<RenderGraphDotPath>,
+    render_graph: Res<RenderGraph>,
+) {
+    if *written {
+        return;
+    }
+    *written = true;
+    match std::fs::write(&path.0, render_graph_to_dot(&render_graph)) {
+        Ok(()) => info!("Wrote the render graph to {}", path.0.display()),
+        Err(err) => error!(
+            "Failed to write the render graph to {}: {}",
+            path.0.display(),
+            err
+        ),
+    }
+}
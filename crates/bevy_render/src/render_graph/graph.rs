@@ -303,7 +303,10 @@ impl Debug for RenderGraph {
 mod tests {
     use super::RenderGraph;
     use crate::{
-        render_graph::{Edge, Node, NodeId, RenderGraphError, ResourceSlotInfo, ResourceSlots},
+        render_graph::{
+            render_graph_to_dot, Edge, Node, NodeId, RenderGraphError, ResourceSlotInfo,
+            ResourceSlots,
+        },
         renderer::{RenderContext, RenderResourceType},
     };
     use bevy_ecs::world::World;
@@ -481,4 +484,30 @@ mod tests {
             "Adding to a duplicate edge should return an error"
         );
     }
+
+    #[test]
+    fn test_graph_dot() {
+        let mut graph = RenderGraph::default();
+        let a_id = graph.add_node("A", TestNode::new(0, 1));
+        let b_id = graph.add_node("B", TestNode::new(1, 0));
+        let c_id = graph.add_node("C", TestNode::new(0, 0));
+        graph.add_slot_edge("A", 0, "B", 0).unwrap();
+        graph.add_node_edge("B", "C").unwrap();
+
+        let dot = render_graph_to_dot(&graph);
+        assert!(dot.contains(&format!(
+            "\"{}\" [label=\"{{{{}}|A\\n(bevy_render::render_graph::graph::tests::TestNode)|{{<o0> out_0: Texture}}}}\"];",
+            a_id.uuid()
+        )));
+        assert!(dot.contains(&format!(
+            "\"{}\":o0 -> \"{}\":i0;",
+            a_id.uuid(),
+            b_id.uuid()
+        )));
+        assert!(dot.contains(&format!(
+            "\"{}\" -> \"{}\" [style=dashed];",
+            b_id.uuid(),
+            c_id.uuid()
+        )));
+    }
 }
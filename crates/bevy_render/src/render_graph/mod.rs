@@ -1,5 +1,6 @@
 pub mod base;
 mod command;
+mod dot;
 mod edge;
 mod graph;
 mod node;
@@ -9,6 +10,7 @@ mod schedule;
 mod system;
 
 pub use command::*;
+pub use dot::*;
 pub use edge::*;
 pub use graph::*;
 pub use node::*;
@@ -298,6 +298,8 @@ impl ShaderStages {
 pub struct ShaderLoader;
 
 impl AssetLoader for ShaderLoader {
+    type Settings = ();
+
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
@@ -8,6 +8,8 @@ use bevy_utils::BoxedFuture;
 pub struct HdrTextureLoader;
 
 impl AssetLoader for HdrTextureLoader {
+    type Settings = ();
+
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
@@ -1,13 +1,60 @@
-use super::texture::{ImageType, Texture, TextureError};
+use super::{
+    texture::{ImageType, Texture, TextureError},
+    AddressMode, FilterMode, SamplerDescriptor, TextureFormat,
+};
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Loader for images that can be read by the `image` crate.
+///
+/// Its settings are [`ImageTextureSettings`].
 #[derive(Clone, Default)]
 pub struct ImageTextureLoader;
 
+/// The settings of [`ImageTextureLoader`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ImageTextureSettings {
+    /// Whether the image stores sRGB colors, or linear data such as normal maps
+    pub srgb: bool,
+    pub address_mode: AddressMode,
+    pub mag_filter: FilterMode,
+    pub min_filter: FilterMode,
+    pub mipmap_filter: FilterMode,
+}
+
+impl Default for ImageTextureSettings {
+    fn default() -> Self {
+        let sampler = SamplerDescriptor::default();
+        Self {
+            srgb: true,
+            address_mode: sampler.address_mode_u,
+            mag_filter: sampler.mag_filter,
+            min_filter: sampler.min_filter,
+            mipmap_filter: sampler.mipmap_filter,
+        }
+    }
+}
+
+impl ImageTextureSettings {
+    fn apply(&self, texture: &mut Texture) {
+        if !self.srgb {
+            texture.format = match texture.format {
+                TextureFormat::Rgba8UnormSrgb => TextureFormat::Rgba8Unorm,
+                TextureFormat::Bgra8UnormSrgb => TextureFormat::Bgra8Unorm,
+                format => format,
+            };
+        }
+        texture.sampler.set_address_mode(self.address_mode);
+        texture.sampler.mag_filter = self.mag_filter;
+        texture.sampler.min_filter = self.min_filter;
+        texture.sampler.mipmap_filter = self.mipmap_filter;
+    }
+}
+
 const FILE_EXTENSIONS: &[&str] = &[
     #[cfg(feature = "png")]
     "png",
@@ -24,22 +71,26 @@ const FILE_EXTENSIONS: &[&str] = &[
 ];
 
 impl AssetLoader for ImageTextureLoader {
+    type Settings = ImageTextureSettings;
+
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
+            let settings = load_context.settings::<Self::Settings>()?;
             // use the file extension for the image type
             let ext = load_context.path().extension().unwrap().to_str().unwrap();
 
-            let dyn_img =
+            let mut dyn_img =
                 Texture::from_buffer(bytes, ImageType::Extension(ext)).map_err(|err| {
                     FileTextureError {
                         error: err,
                         path: format!("{}", load_context.path().display()),
                     }
                 })?;
+            settings.apply(&mut dyn_img);
 
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
@@ -77,4 +128,24 @@ mod tests {
             assert!(image::ImageFormat::from_extension(ext).is_some())
         }
     }
+
+    #[test]
+    fn test_settings() {
+        let mut texture = Texture::default();
+        ImageTextureSettings::default().apply(&mut texture);
+        assert_eq!(texture.format, TextureFormat::Rgba8UnormSrgb);
+        assert_eq!(texture.sampler.mag_filter, FilterMode::Nearest);
+
+        ImageTextureSettings {
+            srgb: false,
+            address_mode: AddressMode::Repeat,
+            mag_filter: FilterMode::Linear,
+            ..Default::default()
+        }
+        .apply(&mut texture);
+        assert_eq!(texture.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(texture.sampler.address_mode_v, AddressMode::Repeat);
+        assert_eq!(texture.sampler.mag_filter, FilterMode::Linear);
+        assert_eq!(texture.sampler.min_filter, FilterMode::Linear);
+    }
 }
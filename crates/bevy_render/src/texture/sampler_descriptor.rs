@@ -1,4 +1,5 @@
 use crate::pipeline::CompareFunction;
+use serde::{Deserialize, Serialize};
 use std::num::NonZeroU8;
 
 /// Describes a sampler
@@ -45,7 +46,7 @@ impl Default for SamplerDescriptor {
 }
 
 /// How edges should be handled in texture addressing.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub enum AddressMode {
     ClampToEdge = 0,
     Repeat = 1,
@@ -59,7 +60,7 @@ impl Default for AddressMode {
 }
 
 /// Texel mixing mode when sampling between texels.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub enum FilterMode {
     Nearest = 0,
     Linear = 1,
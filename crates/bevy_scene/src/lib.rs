@@ -4,17 +4,20 @@ mod scene;
 mod scene_loader;
 mod scene_spawner;
 pub mod serde;
+mod snapshot;
 
 pub use command::*;
 pub use dynamic_scene::*;
 pub use scene::*;
 pub use scene_loader::*;
 pub use scene_spawner::*;
+pub use snapshot::*;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        DynamicScene, Scene, SceneSpawner, SpawnSceneAsChildCommands, SpawnSceneCommands,
+        DynamicScene, Scene, SceneSpawner, SnapshotBuffer, SnapshotFilter,
+        SpawnSceneAsChildCommands, SpawnSceneCommands, WorldSnapshot,
     };
 }
 
@@ -21,6 +21,8 @@ impl FromWorld for SceneLoader {
 }
 
 impl AssetLoader for SceneLoader {
+    type Settings = ();
+
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
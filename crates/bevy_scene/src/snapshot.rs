@@ -0,0 +1,619 @@
+use bevy_ecs::{
+    component::{Component, ComponentId},
+    entity::{Entity, EntityMap, MapEntitiesError},
+    reflect::{ReflectComponent, ReflectMapEntities, ReflectResource},
+    world::World,
+};
+use bevy_reflect::{serde::ReflectSerializer, Reflect, TypeRegistry, TypeRegistryArc};
+use bevy_utils::HashSet;
+use std::{
+    any::TypeId,
+    collections::VecDeque,
+    hash::{Hash, Hasher},
+};
+use thiserror::Error;
+
+/// Selects the components and resources captured by a [`WorldSnapshot`].
+///
+/// By default every component registered with `#[reflect(Component)]` is captured, and no
+/// resource is.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotFilter {
+    allowed_components: Option<HashSet<TypeId>>,
+    denied_components: HashSet<TypeId>,
+    resources: HashSet<TypeId>,
+}
+
+impl SnapshotFilter {
+    /// Only captures the allowed components, instead of every reflected component
+    pub fn allow<T: Component>(mut self) -> Self {
+        self.allowed_components
+            .get_or_insert_with(Default::default)
+            .insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Never captures the component `T`
+    pub fn deny<T: Component>(mut self) -> Self {
+        self.denied_components.insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Captures the resource `T`, which must be registered with `#[reflect(Resource)]`
+    pub fn with_resource<T: Component>(mut self) -> Self {
+        self.resources.insert(TypeId::of::<T>());
+        self
+    }
+
+    pub fn allows_component(&self, type_id: TypeId) -> bool {
+        !self.denied_components.contains(&type_id)
+            && match &self.allowed_components {
+                Some(allowed_components) => allowed_components.contains(&type_id),
+                None => true,
+            }
+    }
+
+    pub fn allows_resource(&self, type_id: TypeId) -> bool {
+        self.resources.contains(&type_id)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("snapshot contains the unregistered component `{type_name}`. consider adding `#[reflect(Component)]` to your type")]
+    UnregisteredComponent { type_name: String },
+    #[error("snapshot contains the unregistered resource `{type_name}`. consider adding `#[reflect(Resource)]` to your type")]
+    UnregisteredResource { type_name: String },
+    #[error("failed to map the entities of a restored component")]
+    MapEntities(#[from] MapEntitiesError),
+}
+
+struct SnapshotEntity {
+    entity: Entity,
+    components: Vec<(TypeId, Box<dyn Reflect>)>,
+}
+
+/// The state of the components and resources of a [`World`] selected by a [`SnapshotFilter`],
+/// which can be restored later, for example to roll back a networked game to a past tick.
+///
+/// Only entities with at least one captured component are part of the snapshot. Restoring it
+/// despawns such entities spawned since the snapshot, and respawns those despawned since then
+/// with the same [`Entity`] ids.
+pub struct WorldSnapshot {
+    entities: Vec<SnapshotEntity>,
+    resources: Vec<(TypeId, Box<dyn Reflect>)>,
+}
+
+impl WorldSnapshot {
+    /// Captures the components and resources of `world` allowed by `filter`. Types that aren't
+    /// registered in `type_registry` are ignored.
+    pub fn from_world(
+        world: &World,
+        filter: &SnapshotFilter,
+        type_registry: &TypeRegistryArc,
+    ) -> Self {
+        let type_registry = type_registry.read();
+        let mut entities = Vec::new();
+        for archetype in world.archetypes().iter() {
+            let captured =
+                captured_components(world, archetype.components(), filter, &type_registry);
+            if captured.is_empty() {
+                continue;
+            }
+            for entity in archetype.entities() {
+                let components = captured
+                    .iter()
+                    .filter_map(|(type_id, reflect_component)| {
+                        reflect_component
+                            .reflect_component(world, *entity)
+                            .map(|component| (*type_id, component.clone_value()))
+                    })
+                    .collect();
+                entities.push(SnapshotEntity {
+                    entity: *entity,
+                    components,
+                });
+            }
+        }
+        entities.sort_by_key(|snapshot_entity| snapshot_entity.entity.id());
+
+        let mut resources = filter
+            .resources
+            .iter()
+            .filter_map(|type_id| {
+                let reflect_resource = type_registry.get_type_data::<ReflectResource>(*type_id)?;
+                let resource = reflect_resource.reflect_resource(world)?;
+                Some((*type_id, resource.clone_value()))
+            })
+            .collect::<Vec<_>>();
+        resources.sort_by(|(_, a), (_, b)| a.type_name().cmp(b.type_name()));
+
+        Self {
+            entities,
+            resources,
+        }
+    }
+
+    /// The entities captured in this snapshot
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities
+            .iter()
+            .map(|snapshot_entity| snapshot_entity.entity)
+    }
+
+    /// Restores the captured components and resources of `world`, using the same `filter` as
+    /// when capturing them.
+    ///
+    /// Entities whose id is now used by an entity that isn't part of the snapshot are respawned
+    /// with another id. The returned map gives the entity each captured entity was restored to,
+    /// and is used to update the components registered with `#[reflect(MapEntities)]`.
+    pub fn restore(
+        &self,
+        world: &mut World,
+        filter: &SnapshotFilter,
+        type_registry: &TypeRegistryArc,
+    ) -> Result<EntityMap, SnapshotError> {
+        let type_registry = type_registry.read();
+
+        // check the captured types before changing the world, to not leave it partially restored
+        for snapshot_entity in self.entities.iter() {
+            for (type_id, component) in snapshot_entity.components.iter() {
+                if type_registry
+                    .get_type_data::<ReflectComponent>(*type_id)
+                    .is_none()
+                {
+                    return Err(SnapshotError::UnregisteredComponent {
+                        type_name: component.type_name().to_string(),
+                    });
+                }
+            }
+        }
+        for (type_id, resource) in self.resources.iter() {
+            if filter.allows_resource(*type_id)
+                && type_registry
+                    .get_type_data::<ReflectResource>(*type_id)
+                    .is_none()
+            {
+                return Err(SnapshotError::UnregisteredResource {
+                    type_name: resource.type_name().to_string(),
+                });
+            }
+        }
+
+        // despawn the entities spawned since the snapshot
+        let snapshot_entities = self.entities().collect::<HashSet<_>>();
+        let mut spawned = Vec::new();
+        for archetype in world.archetypes().iter() {
+            if !captured_components(world, archetype.components(), filter, &type_registry)
+                .is_empty()
+            {
+                spawned.extend(
+                    archetype
+                        .entities()
+                        .iter()
+                        .filter(|entity| !snapshot_entities.contains(entity)),
+                );
+            }
+        }
+        for entity in spawned {
+            world.despawn(entity);
+        }
+
+        let mut entity_map = EntityMap::default();
+        let mut remapped = false;
+        for snapshot_entity in self.entities.iter() {
+            let entity = match world.get_or_spawn(snapshot_entity.entity) {
+                Some(entity) => entity.id(),
+                None => {
+                    remapped = true;
+                    world.spawn().id()
+                }
+            };
+            entity_map.insert(snapshot_entity.entity, entity);
+        }
+
+        for snapshot_entity in self.entities.iter() {
+            let entity = entity_map.get(snapshot_entity.entity)?;
+            // remove the components added since the snapshot
+            let current = captured_components(
+                world,
+                world.entity(entity).archetype().components(),
+                filter,
+                &type_registry,
+            );
+            for (type_id, reflect_component) in current {
+                if !snapshot_entity
+                    .components
+                    .iter()
+                    .any(|(snapshot_type_id, _)| *snapshot_type_id == type_id)
+                {
+                    reflect_component.remove_component(world, entity);
+                }
+            }
+
+            for (type_id, component) in snapshot_entity.components.iter() {
+                let reflect_component = type_registry
+                    .get_type_data::<ReflectComponent>(*type_id)
+                    .ok_or_else(|| SnapshotError::UnregisteredComponent {
+                    type_name: component.type_name().to_string(),
+                })?;
+                if world.entity(entity).contains_type_id(*type_id) {
+                    reflect_component.apply_component(world, entity, &**component);
+                } else {
+                    reflect_component.add_component(world, entity, &**component);
+                }
+            }
+        }
+
+        for type_id in filter.resources.iter() {
+            let snapshot_resource = self
+                .resources
+                .iter()
+                .find(|(snapshot_type_id, _)| snapshot_type_id == type_id);
+            let reflect_resource = match type_registry.get_type_data::<ReflectResource>(*type_id) {
+                Some(reflect_resource) => reflect_resource,
+                None => match snapshot_resource {
+                    Some((_, resource)) => {
+                        return Err(SnapshotError::UnregisteredResource {
+                            type_name: resource.type_name().to_string(),
+                        })
+                    }
+                    // the resource was neither captured nor can be removed
+                    None => continue,
+                },
+            };
+            match snapshot_resource {
+                Some((_, resource)) if reflect_resource.reflect_resource(world).is_some() => {
+                    reflect_resource.apply_resource(world, &**resource)
+                }
+                Some((_, resource)) => reflect_resource.insert_resource(world, &**resource),
+                None => reflect_resource.remove_resource(world),
+            }
+        }
+
+        if remapped {
+            // only the restored components are updated, and their references to entities outside
+            // of the snapshot are kept as they are, even if those entities no longer exist
+            entity_map.keep_unmapped(true);
+            for registration in type_registry.iter() {
+                if !filter.allows_component(registration.type_id()) {
+                    continue;
+                }
+                if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                    map_entities_reflect.map_entities(world, &entity_map)?;
+                }
+            }
+            entity_map.keep_unmapped(false);
+        }
+
+        Ok(entity_map)
+    }
+
+    /// A checksum of the captured values, to detect when the worlds of networked peers diverge.
+    ///
+    /// Values are hashed through their serialized form, so they must be serializable with a
+    /// [`ReflectSerializer`]. Maps are hashed in their iteration order, which can differ between
+    /// peers for hash maps.
+    pub fn checksum(&self, type_registry: &TypeRegistryArc) -> Result<u64, ron::Error> {
+        let type_registry = type_registry.read();
+        let mut hasher = Fnv1aHasher::default();
+        for snapshot_entity in self.entities.iter() {
+            snapshot_entity.entity.to_bits().hash(&mut hasher);
+            let mut components = snapshot_entity.components.iter().collect::<Vec<_>>();
+            components.sort_by(|(_, a), (_, b)| a.type_name().cmp(b.type_name()));
+            for (_, component) in components {
+                hash_value(&**component, &type_registry, &mut hasher)?;
+            }
+        }
+        for (_, resource) in self.resources.iter() {
+            hash_value(&**resource, &type_registry, &mut hasher)?;
+        }
+        Ok(hasher.finish())
+    }
+}
+
+/// The components among `component_ids` captured with `filter`
+fn captured_components<'a>(
+    world: &World,
+    component_ids: impl Iterator<Item = ComponentId>,
+    filter: &SnapshotFilter,
+    type_registry: &'a TypeRegistry,
+) -> Vec<(TypeId, &'a ReflectComponent)> {
+    component_ids
+        .filter_map(|component_id| {
+            let type_id = world.components().get_info(component_id)?.type_id()?;
+            if !filter.allows_component(type_id) {
+                return None;
+            }
+            let reflect_component = type_registry.get_type_data::<ReflectComponent>(type_id)?;
+            Some((type_id, reflect_component))
+        })
+        .collect()
+}
+
+fn hash_value(
+    value: &dyn Reflect,
+    type_registry: &TypeRegistry,
+    hasher: &mut impl Hasher,
+) -> Result<(), ron::Error> {
+    let serialized = ron::to_string(&ReflectSerializer::new(value, type_registry))?;
+    hasher.write(serialized.as_bytes());
+    Ok(())
+}
+
+/// A hasher giving the same results on every platform and Rust version, unlike the standard
+/// library hashers
+struct Fnv1aHasher(u64);
+
+impl Default for Fnv1aHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1aHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// The last snapshots of a [`World`], by tick, to roll back to any of them
+pub struct SnapshotBuffer {
+    capacity: usize,
+    snapshots: VecDeque<(u32, WorldSnapshot)>,
+}
+
+impl SnapshotBuffer {
+    /// Creates a buffer keeping the last `capacity` snapshots
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            snapshots: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Adds the snapshot of `tick`, dropping the oldest snapshot when the buffer is full.
+    ///
+    /// Snapshots of `tick` or later ticks are replaced, as they belong to a timeline that was
+    /// rolled back.
+    pub fn push(&mut self, tick: u32, snapshot: WorldSnapshot) {
+        while matches!(self.snapshots.back(), Some((last_tick, _)) if *last_tick >= tick) {
+            self.snapshots.pop_back();
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back((tick, snapshot));
+    }
+
+    /// The snapshot of `tick`, if it is still in the buffer
+    pub fn get(&self, tick: u32) -> Option<&WorldSnapshot> {
+        self.snapshots
+            .iter()
+            .find(|(snapshot_tick, _)| *snapshot_tick == tick)
+            .map(|(_, snapshot)| snapshot)
+    }
+
+    /// The most recent snapshot and its tick
+    pub fn latest(&self) -> Option<(u32, &WorldSnapshot)> {
+        self.snapshots
+            .back()
+            .map(|(tick, snapshot)| (*tick, snapshot))
+    }
+
+    /// The snapshots from the oldest to the most recent, with their ticks
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &WorldSnapshot)> {
+        self.snapshots
+            .iter()
+            .map(|(tick, snapshot)| (*tick, snapshot))
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        entity::MapEntities,
+        reflect::{ReflectComponent, ReflectMapEntities, ReflectResource},
+    };
+    use bevy_reflect::Reflect;
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Position(f32);
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    #[derive(Reflect, Debug, PartialEq)]
+    #[reflect(Component, MapEntities)]
+    struct Target(Entity);
+
+    impl Default for Target {
+        fn default() -> Self {
+            Target(Entity::new(u32::MAX))
+        }
+    }
+
+    impl MapEntities for Target {
+        fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+            self.0 = entity_map.get(self.0)?;
+            Ok(())
+        }
+    }
+
+    fn type_registry() -> TypeRegistryArc {
+        let type_registry = TypeRegistryArc::default();
+        {
+            let mut type_registry = type_registry.write();
+            type_registry.register::<Position>();
+            type_registry.register::<Health>();
+            type_registry.register::<Score>();
+            type_registry.register::<Target>();
+        }
+        type_registry
+    }
+
+    #[test]
+    fn restores_components_resources_and_entities() {
+        let type_registry = type_registry();
+        let filter = SnapshotFilter::default()
+            .deny::<Health>()
+            .with_resource::<Score>();
+        let mut world = World::default();
+        world.insert_resource(Score(1));
+        let kept = world
+            .spawn()
+            .insert_bundle((Position(1.0), Health(10)))
+            .id();
+        let despawned = world.spawn().insert(Position(2.0)).id();
+        let untracked = world.spawn().insert(Health(5)).id();
+
+        let snapshot = WorldSnapshot::from_world(&world, &filter, &type_registry);
+        assert_eq!(
+            snapshot.entities().collect::<Vec<_>>(),
+            vec![kept, despawned]
+        );
+        let checksum = snapshot.checksum(&type_registry).unwrap();
+
+        world.get_mut::<Position>(kept).unwrap().0 = 3.0;
+        world.get_mut::<Health>(kept).unwrap().0 = 0;
+        world.entity_mut(kept).insert(Target(untracked));
+        world.despawn(despawned);
+        let spawned = world.spawn().insert(Position(4.0)).id();
+        world.get_resource_mut::<Score>().unwrap().0 = 2;
+        assert_ne!(
+            WorldSnapshot::from_world(&world, &filter, &type_registry)
+                .checksum(&type_registry)
+                .unwrap(),
+            checksum
+        );
+
+        let entity_map = snapshot
+            .restore(&mut world, &filter, &type_registry)
+            .unwrap();
+        assert_eq!(entity_map.get(despawned).unwrap(), despawned);
+        assert_eq!(world.get::<Position>(kept), Some(&Position(1.0)));
+        assert!(world.get::<Target>(kept).is_none());
+        // denied components are left as they are
+        assert_eq!(world.get::<Health>(kept), Some(&Health(0)));
+        assert_eq!(world.get::<Position>(despawned), Some(&Position(2.0)));
+        assert!(world.get_entity(spawned).is_none());
+        assert_eq!(world.get::<Health>(untracked), Some(&Health(5)));
+        assert_eq!(world.get_resource::<Score>(), Some(&Score(1)));
+        assert_eq!(
+            WorldSnapshot::from_world(&world, &filter, &type_registry)
+                .checksum(&type_registry)
+                .unwrap(),
+            checksum
+        );
+    }
+
+    #[test]
+    fn remaps_entities_whose_id_was_reused() {
+        let type_registry = type_registry();
+        let filter = SnapshotFilter::default().allow::<Target>();
+        let mut world = World::default();
+        let target = world.spawn().id();
+        let follower = world.spawn().insert(Target(target)).id();
+        let snapshot = WorldSnapshot::from_world(&world, &filter, &type_registry);
+
+        // the id of the follower is reused by an entity outside of the snapshot
+        world.despawn(follower);
+        let reused = world.spawn().id();
+        assert_eq!(reused.id(), follower.id());
+
+        let entity_map = snapshot
+            .restore(&mut world, &filter, &type_registry)
+            .unwrap();
+        let restored = entity_map.get(follower).unwrap();
+        assert_ne!(restored, follower);
+        assert!(world.get_entity(reused).is_some());
+        assert_eq!(world.get::<Target>(restored), Some(&Target(target)));
+    }
+
+    #[test]
+    fn keeps_references_to_entities_outside_of_snapshot() {
+        let type_registry = type_registry();
+        let filter = SnapshotFilter::default().deny::<Health>();
+        let mut world = World::default();
+        let dead = world.spawn().id();
+        let leader = world.spawn().insert(Position(1.0)).id();
+        let follower = world.spawn().insert(Target(dead)).id();
+        let snapshot = WorldSnapshot::from_world(&world, &filter, &type_registry);
+
+        // the follower references an entity outside of the snapshot which no longer exists, while
+        // the id of the leader is reused by an entity outside of the snapshot
+        world.despawn(dead);
+        world.despawn(leader);
+        let reused = world.spawn().insert(Health(1)).id();
+        assert_eq!(reused.id(), leader.id());
+
+        let entity_map = snapshot
+            .restore(&mut world, &filter, &type_registry)
+            .unwrap();
+        let restored = entity_map.get(leader).unwrap();
+        assert_ne!(restored, leader);
+        assert_eq!(world.get::<Position>(restored), Some(&Position(1.0)));
+        assert_eq!(world.get::<Target>(follower), Some(&Target(dead)));
+        assert_eq!(world.get::<Health>(reused), Some(&Health(1)));
+    }
+
+    #[test]
+    fn snapshot_buffer_keeps_last_ticks() {
+        let world = World::default();
+        let type_registry = type_registry();
+        let filter = SnapshotFilter::default();
+        let mut buffer = SnapshotBuffer::new(2);
+        for tick in 0..3 {
+            buffer.push(
+                tick,
+                WorldSnapshot::from_world(&world, &filter, &type_registry),
+            );
+        }
+        assert_eq!(
+            buffer.iter().map(|(tick, _)| tick).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(buffer.get(0).is_none());
+
+        // after a rollback to tick 1, the following ticks are simulated again
+        buffer.push(
+            1,
+            WorldSnapshot::from_world(&world, &filter, &type_registry),
+        );
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer.latest().map(|(tick, _)| tick), Some(1));
+    }
+}
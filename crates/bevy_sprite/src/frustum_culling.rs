@@ -1,9 +1,10 @@
 use bevy_asset::{Assets, Handle};
-use bevy_ecs::prelude::{Commands, Entity, Query, Res, With};
+use bevy_ecs::prelude::{Added, Commands, Entity, Or, Query, Res, With, Without};
 use bevy_math::Vec2;
 use bevy_render::{
     camera::{ActiveCameras, Camera},
     draw::OutsideFrustum,
+    primitives::NoFrustumCulling,
 };
 use bevy_transform::components::Transform;
 use bevy_window::Windows;
@@ -28,6 +29,24 @@ impl Rect {
     }
 }
 
+/// Keeps sprites out of the mesh frustum culling of cameras: their mesh is a unit quad resized
+/// when drawn, so its bounds don't match the sprite
+#[allow(clippy::type_complexity)]
+pub fn sprite_no_frustum_culling_system(
+    mut commands: Commands,
+    sprites: Query<
+        Entity,
+        (
+            Or<(Added<Sprite>, Added<TextureAtlasSprite>)>,
+            Without<NoFrustumCulling>,
+        ),
+    >,
+) {
+    for entity in sprites.iter() {
+        commands.entity(entity).insert(NoFrustumCulling);
+    }
+}
+
 pub fn sprite_frustum_culling_system(
     mut commands: Commands,
     windows: Res<Windows>,
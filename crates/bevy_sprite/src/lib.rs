@@ -70,6 +70,10 @@ impl Plugin for SpritePlugin {
             .register_type::<Sprite>()
             .register_type::<SpriteResizeMode>()
             .add_system_to_stage(CoreStage::PostUpdate, sprite_system)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                frustum_culling::sprite_no_frustum_culling_system,
+            )
             .add_system_to_stage(CoreStage::PostUpdate, material_texture_detection_system)
             .add_system_to_stage(
                 CoreStage::PostUpdate,
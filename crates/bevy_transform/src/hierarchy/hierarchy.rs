@@ -1,10 +1,10 @@
-use crate::components::{Children, Parent};
+use crate::components::{Children, Parent, PreviousParent};
 use bevy_ecs::{
-    entity::Entity,
+    entity::{Entity, EntityMap},
     system::{Command, EntityCommands},
     world::World,
 };
-use bevy_utils::tracing::debug;
+use bevy_utils::{tracing::debug, HashSet};
 
 #[derive(Debug)]
 pub struct DespawnRecursive {
@@ -55,6 +55,35 @@ impl<'a, 'b> DespawnRecursiveExt for EntityCommands<'a, 'b> {
     }
 }
 
+/// A [`CloneHook`](bevy_ecs::world::CloneHook) keeping the hierarchy of cloned entities
+/// consistent, added to the [`CloneRegistry`](bevy_ecs::world::CloneRegistry) by the
+/// [`TransformPlugin`](crate::TransformPlugin).
+///
+/// Copies only keep the children that are cloned with them, and become roots if their parent is
+/// not cloned.
+pub fn clone_hierarchy_hook(world: &mut World, entity_map: &EntityMap) {
+    let cloned = entity_map.keys().collect::<HashSet<_>>();
+    for copy in entity_map.values() {
+        let mut copy = world.entity_mut(copy);
+        if let Some(mut children) = copy.get_mut::<Children>() {
+            children.0.retain(|child| cloned.contains(child));
+            if children.0.is_empty() {
+                copy.remove::<Children>();
+            }
+        }
+        if let Some(parent) = copy.get::<Parent>() {
+            if !cloned.contains(&parent.0) {
+                copy.remove::<Parent>();
+            }
+        }
+        if let Some(previous_parent) = copy.get::<PreviousParent>() {
+            if !cloned.contains(&previous_parent.0) {
+                copy.remove::<PreviousParent>();
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bevy_ecs::{
@@ -62,8 +91,13 @@ mod tests {
         world::World,
     };
 
-    use super::DespawnRecursiveExt;
-    use crate::{components::Children, hierarchy::BuildChildren};
+    use super::{clone_hierarchy_hook, DespawnRecursiveExt};
+    use crate::{
+        components::{Children, Parent, PreviousParent},
+        hierarchy::{BuildChildren, BuildWorldChildren},
+    };
+    use bevy_ecs::world::CloneRegistry;
+    use bevy_reflect::TypeRegistryArc;
 
     #[test]
     fn despawn_recursive() {
@@ -139,4 +173,41 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn clone_hierarchy() {
+        let mut world = World::default();
+        let type_registry = TypeRegistryArc::default();
+        type_registry.write().register::<Children>();
+        type_registry.write().register::<Parent>();
+        type_registry.write().register::<PreviousParent>();
+        world.insert_resource(type_registry);
+        world
+            .get_resource_or_insert_with(CloneRegistry::default)
+            .add_hook(clone_hierarchy_hook);
+        let child = world.spawn().id();
+        let other_child = world.spawn().id();
+        let parent = world.spawn().push_children(&[child, other_child]).id();
+
+        // only the cloned children are kept
+        let copies = world.clone_entities(&[parent, child]).unwrap();
+        assert_eq!(&**world.get::<Children>(copies[0]).unwrap(), &[copies[1]]);
+        assert_eq!(world.get::<Parent>(copies[1]), Some(&Parent(copies[0])));
+        assert_eq!(
+            world.get::<PreviousParent>(copies[1]),
+            Some(&PreviousParent(copies[0]))
+        );
+        assert_eq!(
+            &**world.get::<Children>(parent).unwrap(),
+            &[child, other_child]
+        );
+
+        // copies without cloned children or parent are roots
+        let copy = world.clone_entity(parent).unwrap();
+        assert!(world.get::<Children>(copy).is_none());
+        let copy = world.clone_entity(child).unwrap();
+        assert!(world.get::<Parent>(copy).is_none());
+        assert!(world.get::<PreviousParent>(copy).is_none());
+        assert_eq!(world.get::<Parent>(child), Some(&Parent(parent)));
+    }
 }
@@ -8,8 +8,14 @@ pub mod prelude {
 }
 
 use bevy_app::prelude::*;
-use bevy_ecs::schedule::{ParallelSystemDescriptorCoercion, SystemLabel};
-use prelude::{parent_update_system, Children, GlobalTransform, Parent, PreviousParent, Transform};
+use bevy_ecs::{
+    schedule::{ParallelSystemDescriptorCoercion, SystemLabel},
+    world::CloneRegistry,
+};
+use prelude::{
+    clone_hierarchy_hook, parent_update_system, Children, GlobalTransform, Parent, PreviousParent,
+    Transform,
+};
 
 #[derive(Default)]
 pub struct TransformPlugin;
@@ -22,6 +28,9 @@ pub enum TransformSystem {
 
 impl Plugin for TransformPlugin {
     fn build(&self, app: &mut App) {
+        app.world
+            .get_resource_or_insert_with(CloneRegistry::default)
+            .add_hook(clone_hierarchy_hook);
         app.register_type::<Children>()
             .register_type::<Parent>()
             .register_type::<PreviousParent>()
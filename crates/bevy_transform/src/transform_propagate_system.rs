@@ -1,12 +1,17 @@
 use crate::components::{Children, GlobalTransform, Parent, Transform};
 use bevy_ecs::{
+    component::Disabled,
     entity::Entity,
     query::{Changed, With, Without},
-    system::Query,
+    system::{Local, Query},
 };
+use bevy_utils::HashSet;
 
 /// Update [`GlobalTransform`] component of entities based on entity hierarchy and
 /// [`Transform`] component.
+///
+/// [`Disabled`] entities and their descendants are not updated. They are updated again once they
+/// are enabled.
 pub fn transform_propagate_system(
     mut root_query: Query<
         (Entity, Option<&Children>, &Transform, &mut GlobalTransform),
@@ -15,10 +20,21 @@ pub fn transform_propagate_system(
     mut transform_query: Query<(&Transform, &mut GlobalTransform), With<Parent>>,
     changed_transform_query: Query<Entity, Changed<Transform>>,
     children_query: Query<Option<&Children>, (With<Parent>, With<GlobalTransform>)>,
+    disabled_query: Query<Entity, (With<Disabled>, With<GlobalTransform>)>,
+    mut disabled: Local<HashSet<Entity>>,
 ) {
+    // entities enabled since the last run are updated again. Removed `Disabled` components can't
+    // tell them apart, as they are cleared at the end of the frame, and entities enabled after
+    // this system ran would never be updated
+    let now_disabled = disabled_query.iter().collect::<HashSet<_>>();
+    let enabled = disabled
+        .difference(&now_disabled)
+        .copied()
+        .collect::<HashSet<_>>();
+    *disabled = now_disabled;
     for (entity, children, transform, mut global_transform) in root_query.iter_mut() {
         let mut changed = false;
-        if changed_transform_query.get(entity).is_ok() {
+        if changed_transform_query.get(entity).is_ok() || enabled.contains(&entity) {
             *global_transform = GlobalTransform::from(*transform);
             changed = true;
         }
@@ -30,6 +46,7 @@ pub fn transform_propagate_system(
                     &changed_transform_query,
                     &mut transform_query,
                     &children_query,
+                    &enabled,
                     *child,
                     changed,
                 );
@@ -43,10 +60,11 @@ fn propagate_recursive(
     changed_transform_query: &Query<Entity, Changed<Transform>>,
     transform_query: &mut Query<(&Transform, &mut GlobalTransform), With<Parent>>,
     children_query: &Query<Option<&Children>, (With<Parent>, With<GlobalTransform>)>,
+    enabled: &HashSet<Entity>,
     entity: Entity,
     mut changed: bool,
 ) {
-    changed |= changed_transform_query.get(entity).is_ok();
+    changed |= changed_transform_query.get(entity).is_ok() || enabled.contains(&entity);
 
     let global_matrix = {
         if let Ok((transform, mut global_transform)) = transform_query.get_mut(entity) {
@@ -66,6 +84,7 @@ fn propagate_recursive(
                 changed_transform_query,
                 transform_query,
                 children_query,
+                enabled,
                 *child,
                 changed,
             );
@@ -76,6 +95,7 @@ fn propagate_recursive(
 #[cfg(test)]
 mod test {
     use bevy_ecs::{
+        component::Disabled,
         schedule::{Schedule, Stage, SystemStage},
         system::{CommandQueue, Commands},
         world::World,
@@ -190,4 +210,76 @@ mod test {
             GlobalTransform::from_xyz(1.0, 0.0, 0.0) * Transform::from_xyz(0.0, 0.0, 3.0)
         );
     }
+
+    #[test]
+    fn did_not_propagate_disabled() {
+        let mut world = World::default();
+
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(parent_update_system);
+        update_stage.add_system(transform_propagate_system);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update", update_stage);
+
+        let mut child = None;
+        let mut grandchild = None;
+        let root = world
+            .spawn()
+            .insert_bundle((
+                Transform::from_xyz(1.0, 0.0, 0.0),
+                GlobalTransform::identity(),
+            ))
+            .with_children(|parent| {
+                child = Some(
+                    parent
+                        .spawn_bundle((
+                            Transform::from_xyz(0.0, 2.0, 0.0),
+                            GlobalTransform::identity(),
+                        ))
+                        .with_children(|parent| {
+                            grandchild = Some(
+                                parent
+                                    .spawn_bundle((
+                                        Transform::from_xyz(0.0, 0.0, 3.0),
+                                        GlobalTransform::identity(),
+                                    ))
+                                    .id(),
+                            );
+                        })
+                        .id(),
+                );
+            })
+            .id();
+        let (child, grandchild) = (child.unwrap(), grandchild.unwrap());
+        schedule.run(&mut world);
+        world.clear_trackers();
+
+        // the disabled subtree keeps its transforms
+        world.entity_mut(child).insert(Disabled);
+        world.get_mut::<Transform>(root).unwrap().translation.x = 5.0;
+        schedule.run(&mut world);
+        world.clear_trackers();
+        assert_eq!(
+            *world.get::<GlobalTransform>(root).unwrap(),
+            GlobalTransform::from_xyz(5.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            *world.get::<GlobalTransform>(grandchild).unwrap(),
+            GlobalTransform::from_xyz(1.0, 2.0, 3.0)
+        );
+
+        // and is updated once it is enabled again, even if it was enabled in a previous frame
+        world.entity_mut(child).remove::<Disabled>();
+        world.clear_trackers();
+        schedule.run(&mut world);
+        assert_eq!(
+            *world.get::<GlobalTransform>(child).unwrap(),
+            GlobalTransform::from_xyz(5.0, 2.0, 0.0)
+        );
+        assert_eq!(
+            *world.get::<GlobalTransform>(grandchild).unwrap(),
+            GlobalTransform::from_xyz(5.0, 2.0, 3.0)
+        );
+    }
 }
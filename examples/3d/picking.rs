@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+
+/// Highlights the shape under the cursor, and logs where it is clicked.
+fn main() {
+    App::new()
+        .insert_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(highlight_picked)
+        .run();
+}
+
+struct Materials {
+    normal: Handle<StandardMaterial>,
+    hovered: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let shape_materials = Materials {
+        normal: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+        hovered: materials.add(Color::rgb(0.9, 0.3, 0.3).into()),
+    };
+    let cube = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
+    let sphere = meshes.add(Mesh::from(shape::Icosphere {
+        radius: 0.5,
+        subdivisions: 4,
+    }));
+    for x in -2..=2 {
+        commands
+            .spawn_bundle(PbrBundle {
+                mesh: if x % 2 == 0 {
+                    cube.clone()
+                } else {
+                    sphere.clone()
+                },
+                material: shape_materials.normal.clone(),
+                transform: Transform::from_xyz(x as f32 * 1.5, 0.5, 0.0),
+                ..Default::default()
+            })
+            .insert(Pickable);
+    }
+    commands.insert_resource(shape_materials);
+    // light
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..Default::default()
+    });
+    // camera
+    commands
+        .spawn_bundle(PerspectiveCameraBundle {
+            transform: Transform::from_xyz(0.0, 4.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..Default::default()
+        })
+        .insert(PickingCamera::default());
+}
+
+fn highlight_picked(
+    materials: Res<Materials>,
+    mut events: EventReader<PickingEvent>,
+    mut shapes: Query<&mut Handle<StandardMaterial>>,
+) {
+    for event in events.iter() {
+        match event {
+            PickingEvent::HoverStarted { entity, .. } => {
+                if let Ok(mut material) = shapes.get_mut(*entity) {
+                    *material = materials.hovered.clone();
+                }
+            }
+            PickingEvent::HoverEnded { entity } => {
+                if let Ok(mut material) = shapes.get_mut(*entity) {
+                    *material = materials.normal.clone();
+                }
+            }
+            PickingEvent::Clicked { entity, hit } => {
+                info!(
+                    "clicked {:?} at {} (normal {}, {} away)",
+                    entity, hit.position, hit.normal, hit.distance
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+
+/// Demonstrates shadows cast by a directional light and a point light.
+/// Press `D` or `P` to toggle the shadows of the directional or point light.
+fn main() {
+    App::new()
+        .insert_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(toggle_shadows)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // ground plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 10.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..Default::default()
+    });
+    // cubes casting shadows
+    let cube = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
+    for x in -2..=2 {
+        commands.spawn_bundle(PbrBundle {
+            mesh: cube.clone(),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            transform: Transform::from_xyz(x as f32 * 1.5, 0.5, 0.0),
+            ..Default::default()
+        });
+    }
+    // a sphere that neither casts nor receives shadows
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Icosphere {
+                radius: 0.5,
+                subdivisions: 4,
+            })),
+            material: materials.add(Color::rgb(0.6, 0.6, 0.9).into()),
+            transform: Transform::from_xyz(0.0, 2.0, 2.0),
+            ..Default::default()
+        })
+        .insert(NotShadowCaster)
+        .insert(NotShadowReceiver);
+    // lights
+    let mut directional_light =
+        DirectionalLight::new(Color::WHITE, 20000.0, Vec3::new(-1.0, -2.0, -1.0));
+    directional_light.shadows_enabled = true;
+    commands.spawn().insert(directional_light);
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(0.0, 3.0, 2.5),
+        point_light: PointLight {
+            intensity: 400.0,
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    // camera
+    commands.spawn_bundle(PerspectiveCameraBundle {
+        transform: Transform::from_xyz(-4.0, 6.0, 9.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..Default::default()
+    });
+}
+
+fn toggle_shadows(
+    input: Res<Input<KeyCode>>,
+    mut directional_lights: Query<&mut DirectionalLight>,
+    mut point_lights: Query<&mut PointLight>,
+) {
+    if input.just_pressed(KeyCode::D) {
+        for mut light in directional_lights.iter_mut() {
+            light.shadows_enabled = !light.shadows_enabled;
+        }
+    }
+    if input.just_pressed(KeyCode::P) {
+        for mut light in point_lights.iter_mut() {
+            light.shadows_enabled = !light.shadows_enabled;
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    render::texture::{FilterMode, ImageTextureSettings},
+};
 
 /// This example illustrates various ways to load assets
 fn main() {
@@ -43,6 +46,16 @@ fn setup(
     // Then any asset in the folder can be accessed like this:
     let monkey_handle = asset_server.get_handle("models/monkey/Monkey.gltf#Mesh0/Primitive0");
 
+    // Loaders can be configured with settings. They are part of the identity of the asset, so
+    // loading the same file with different settings loads different assets
+    let _linear_icon: Handle<Texture> = asset_server.load_with_settings(
+        "branding/icon.png",
+        ImageTextureSettings {
+            mag_filter: FilterMode::Linear,
+            ..Default::default()
+        },
+    );
+
     // You can also add assets directly to their Assets<T> storage:
     let material_handle = materials.add(StandardMaterial {
         base_color: Color::rgb(0.8, 0.7, 0.6),
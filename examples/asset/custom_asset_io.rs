@@ -40,6 +40,15 @@ impl AssetIo for CustomAssetIo {
         info!("watch_for_changes()");
         self.0.watch_for_changes()
     }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        info!("write_path({:?})", path);
+        self.0.write_path(path, bytes)
+    }
 }
 
 /// A plugin used to execute the override of the asset io
@@ -0,0 +1,62 @@
+use bevy::{
+    asset::{AssetIo, LayeredAssetIo, PackAssetIo},
+    prelude::*,
+};
+
+/// This example loads assets from a pack archive, built from the `assets` folder with:
+///
+/// `cargo run -p asset_packer -- assets assets.pack`
+///
+/// Loose files in the `assets` folder are layered over the pack, so they override packed files
+/// during development.
+fn main() {
+    App::new()
+        .add_plugins_with(DefaultPlugins, |group| {
+            // the pack asset io plugin must be inserted in-between the `CorePlugin` and the
+            // `AssetPlugin`, so that the IO task pool exists and only one asset server is created
+            group.add_before::<bevy::asset::AssetPlugin, _>(PackAssetIoPlugin)
+        })
+        .add_startup_system(setup)
+        .run();
+}
+
+/// A plugin layering the platform default asset io over the pack
+struct PackAssetIoPlugin;
+
+impl Plugin for PackAssetIoPlugin {
+    fn build(&self, app: &mut App) {
+        let task_pool = app
+            .world
+            .get_resource::<bevy::tasks::IoTaskPool>()
+            .expect("`IoTaskPool` resource not found.")
+            .0
+            .clone();
+
+        let default_io = bevy::asset::create_platform_default_asset_io(app);
+        let asset_io: Box<dyn AssetIo> = match PackAssetIo::open("assets.pack") {
+            Ok(pack) => {
+                info!("loading {} packed files", pack.files().count());
+                Box::new(LayeredAssetIo::new(default_io, Box::new(pack)))
+            }
+            Err(e) => {
+                warn!("failed to open the pack, loading loose files only: {:?}", e);
+                default_io
+            }
+        };
+
+        app.insert_resource(AssetServer::with_boxed_io(asset_io, task_pool));
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let texture_handle = asset_server.load("branding/icon.png");
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    commands.spawn_bundle(SpriteBundle {
+        material: materials.add(texture_handle.into()),
+        ..Default::default()
+    });
+}
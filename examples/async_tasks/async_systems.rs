@@ -0,0 +1,62 @@
+use bevy::{app::AppExit, ecs::event::Events, prelude::*, utils::Duration};
+
+/// This example shows how to write logic that spans many frames, like a cutscene, linearly with
+/// an async system. The async system runs on the `AsyncComputeTaskPool`, and accesses the world
+/// between awaits.
+fn main() {
+    App::new()
+        .add_plugins(MinimalPlugins)
+        .add_event::<DoorOpened>()
+        .insert_resource(DoorTimer(Timer::from_seconds(2.0, false)))
+        .add_system(open_door)
+        .add_async_system(cutscene)
+        .run();
+}
+
+#[derive(Clone)]
+struct DoorOpened {
+    door: &'static str,
+}
+
+struct DoorTimer(Timer);
+
+/// A regular system that opens a door after a while
+fn open_door(
+    time: Res<Time>,
+    mut timer: ResMut<DoorTimer>,
+    mut door_opened: EventWriter<DoorOpened>,
+) {
+    if timer.0.tick(time.delta()).just_finished() {
+        door_opened.send(DoorOpened { door: "front door" });
+    }
+}
+
+/// An async system that reads like a script, and is spread over many frames
+async fn cutscene(world: AsyncWorld) {
+    // events are read from now on, so the door can't open before the cutscene waits for it
+    let door_opened = world.next_event::<DoorOpened>();
+
+    println!("The lights go off.");
+    world.sleep(Duration::from_secs_f32(0.5)).await;
+    println!("Footsteps can be heard...");
+    world.next_frame().await;
+
+    // waits for an event sent by a regular system
+    let DoorOpened { door } = door_opened.await;
+    println!("The {} opens.", door);
+
+    // accesses the world between awaits
+    let seconds = world
+        .with(|world| {
+            world
+                .get_resource_mut::<Events<AppExit>>()
+                .unwrap()
+                .send(AppExit);
+            world
+                .get_resource::<Time>()
+                .unwrap()
+                .seconds_since_startup()
+        })
+        .await;
+    println!("The end, after {:.1} seconds.", seconds);
+}
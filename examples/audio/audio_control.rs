@@ -0,0 +1,37 @@
+use bevy::{audio::AudioSink, prelude::*};
+
+/// This example illustrates how to control a sound while it plays: space pauses and resumes the
+/// music, and the up and down arrows change its volume
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(control_music)
+        .run();
+}
+
+struct MusicController(Handle<AudioSink>);
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, audio: Res<Audio>) {
+    let music = asset_server.load("sounds/Windless Slopes.mp3");
+    let sink = audio.play_with_settings(music, PlaybackSettings::LOOP.with_volume(0.75));
+    commands.insert_resource(MusicController(sink));
+}
+
+fn control_music(
+    keyboard_input: Res<Input<KeyCode>>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    music_controller: Res<MusicController>,
+) {
+    // the sink is only there once the music started playing
+    if let Some(sink) = audio_sinks.get(&music_controller.0) {
+        if keyboard_input.just_pressed(KeyCode::Space) {
+            sink.toggle();
+        }
+        if keyboard_input.just_pressed(KeyCode::Up) {
+            sink.set_volume(sink.volume() + 0.1);
+        } else if keyboard_input.just_pressed(KeyCode::Down) {
+            sink.set_volume((sink.volume() - 0.1).max(0.0));
+        }
+    }
+}
@@ -49,6 +49,8 @@ pub struct RustSourceCode(pub String);
 pub struct RustSourceCodeLoader;
 
 impl AssetLoader for RustSourceCodeLoader {
+    type Settings = ();
+
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
@@ -0,0 +1,55 @@
+use bevy_asset::PackWriter;
+use std::{
+    env, fs,
+    fs::File,
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+};
+
+/// Packs an asset folder into a pack archive read by `PackAssetIo`.
+///
+/// Usage: `cargo run -p asset_packer -- [assets folder] [output]`, which defaults to packing
+/// `assets` into `assets.pack`.
+fn main() {
+    let mut args = env::args_os().skip(1);
+    let assets = args
+        .next()
+        .map_or_else(|| PathBuf::from("assets"), PathBuf::from);
+    let output = args
+        .next()
+        .map_or_else(|| PathBuf::from("assets.pack"), PathBuf::from);
+
+    let mut files = Vec::new();
+    collect_files(&assets, &mut files).expect("Failed to read the assets folder.");
+
+    let mut writer = PackWriter::new(BufWriter::new(
+        File::create(&output).expect("Failed to create the pack."),
+    ))
+    .expect("Failed to write the pack.");
+    for path in files.iter() {
+        let bytes = fs::read(path).expect("Failed to read an asset.");
+        let relative_path = path.strip_prefix(&assets).unwrap();
+        writer
+            .add_file(relative_path, &bytes)
+            .expect("Failed to write the pack.");
+        println!("packed {}", relative_path.display());
+    }
+    writer.finish().expect("Failed to write the pack.");
+    println!("packed {} files into {}", files.len(), output.display());
+}
+
+/// Collects the files in `path` recursively, sorted so packs are reproducible
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir() {
+            collect_files(&entry, files)?;
+        } else {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}
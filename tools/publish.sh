@@ -13,10 +13,11 @@ crates=(
     bevy_reflect/bevy_reflect_derive
     bevy_reflect
     bevy_asset
-    bevy_audio
     bevy_core
     bevy_diagnostic
     bevy_transform
+    bevy_audio
+    bevy_animation
     bevy_window
     bevy_render
     bevy_input
@@ -27,6 +28,7 @@ crates=(
     bevy_sprite
     bevy_text
     bevy_ui
+    bevy_picking
     bevy_winit
     bevy_wgpu
     bevy_internal